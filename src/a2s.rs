@@ -0,0 +1,113 @@
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+// --- ЗАПИТИ ДО ІГРОВОГО СЕРВЕРА (Steam A2S через UDP) ---
+//
+// Сервер відповідає на query-порт, той самий, що в steam://connect.
+
+const TIMEOUT: Duration = Duration::from_secs(2);
+const A2S_INFO: &[u8] = b"\xFF\xFF\xFF\xFFTSource Engine Query\0";
+
+fn connect(ip: &str, port: u32) -> Result<UdpSocket, String> {
+    let port = u16::try_from(port).map_err(|_| format!("invalid port {}", port))?;
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| e.to_string())?;
+    socket.set_read_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+    socket.connect((ip, port)).map_err(|e| e.to_string())?;
+    Ok(socket)
+}
+
+/// Час відповіді або текст помилки.
+pub type Ping = Result<Duration, String>;
+
+/// Час відповіді на A2S_INFO. Відповідь-челендж теж рахується: пакет пройшов туди й назад.
+pub fn ping(ip: &str, port: u32) -> Ping {
+    let socket = connect(ip, port)?;
+    let started = Instant::now();
+    socket.send(A2S_INFO).map_err(|e| e.to_string())?;
+    let mut buf = [0u8; 1400];
+    socket.recv(&mut buf).map_err(|e| e.to_string())?;
+    Ok(started.elapsed())
+}
+
+const A2S_RULES: u8 = 0x56;
+const S2C_CHALLENGE: u8 = 0x41;
+const S2A_RULES: u8 = 0x45;
+// Заголовок однопакетної відповіді (-1) і частини багатопакетної (-2)
+const SINGLE: [u8; 4] = [0xFF; 4];
+const SPLIT: [u8; 4] = [0xFE, 0xFF, 0xFF, 0xFF];
+
+// Отримує повну відповідь, склеюючи частини багатопакетної (формат Source)
+fn receive(socket: &UdpSocket) -> Result<Vec<u8>, String> {
+    let mut buf = [0u8; 1400];
+    let len = socket.recv(&mut buf).map_err(|e| e.to_string())?;
+    let packet = &buf[..len];
+    if packet.starts_with(&SINGLE) {
+        return Ok(packet[4..].to_vec());
+    }
+    if !packet.starts_with(&SPLIT) || len < 12 {
+        return Err("unexpected packet".to_owned());
+    }
+    // Стиснені відповіді (старший біт id) Squad не надсилає - не підтримуємо
+    if packet[7] & 0x80 != 0 {
+        return Err("compressed replies are not supported".to_owned());
+    }
+
+    let total = packet[8] as usize;
+    let mut parts: Vec<Option<Vec<u8>>> = vec![None; total];
+    let mut packet = packet.to_vec();
+    loop {
+        let number = packet[9] as usize;
+        if number < total {
+            parts[number] = Some(packet[12..].to_vec());
+        }
+        if parts.iter().all(Option::is_some) {
+            break;
+        }
+        let len = socket.recv(&mut buf).map_err(|e| e.to_string())?;
+        if len < 12 || !buf.starts_with(&SPLIT) {
+            return Err("unexpected packet".to_owned());
+        }
+        packet = buf[..len].to_vec();
+    }
+    let payload: Vec<u8> = parts.into_iter().flatten().flatten().collect();
+    payload
+        .strip_prefix(&SINGLE)
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| "broken split reply".to_owned())
+}
+
+/// Сирі пари ключ-значення або текст помилки.
+pub type Rules = Result<Vec<(String, String)>, String>;
+
+/// Правила сервера (A2S_RULES).
+pub fn rules(ip: &str, port: u32) -> Rules {
+    let socket = connect(ip, port)?;
+    // Спершу челендж: -1 у полі челенджу просить сервер його видати
+    let mut request = SINGLE.to_vec();
+    request.push(A2S_RULES);
+    request.extend_from_slice(&SINGLE);
+    socket.send(&request).map_err(|e| e.to_string())?;
+
+    let mut reply = receive(&socket)?;
+    if reply.first() == Some(&S2C_CHALLENGE) && reply.len() >= 5 {
+        request.truncate(5);
+        request.extend_from_slice(&reply[1..5]);
+        socket.send(&request).map_err(|e| e.to_string())?;
+        reply = receive(&socket)?;
+    }
+    if reply.first() != Some(&S2A_RULES) || reply.len() < 3 {
+        return Err("unexpected reply".to_owned());
+    }
+
+    let count = u16::from_le_bytes([reply[1], reply[2]]) as usize;
+    let mut fields = reply[3..].split(|&b| b == 0).map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+    let mut rules = Vec::with_capacity(count);
+    for _ in 0..count {
+        match (fields.next(), fields.next()) {
+            (Some(key), Some(value)) => rules.push((key, value)),
+            _ => break,
+        }
+    }
+    rules.sort();
+    Ok(rules)
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::time::Duration;
+
+// --- ОГОЛОШЕННЯ СПІЛЬНОТИ (стрічка з URL) ---
+//
+// Клан вказує адресу JSON або RSS/Atom; свіжі записи показуються банером над
+// списком. Останній отриманий список зберігається, закриті банери не повертаються.
+//
+// JSON: [{"id": "...", "text": "...", "link": "..."}] або {"items": [...]};
+// замість text підходить title/message, замість link - url.
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+const MAX_ITEMS: usize = 20;
+
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Announcement {
+    pub id: String,
+    pub text: String,
+    pub link: String,
+}
+
+impl Announcement {
+    fn new(id: String, text: String, link: String) -> Option<Self> {
+        if text.is_empty() {
+            return None;
+        }
+        // Без id розрізняємо записи за посиланням або текстом
+        let id = [id, link.clone(), text.clone()].into_iter().find(|s| !s.is_empty()).unwrap_or_default();
+        Some(Self { id, text, link })
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct AnnouncementFeed {
+    pub url: String,
+    pub items: Vec<Announcement>,
+    pub dismissed: HashSet<String>,
+}
+
+impl AnnouncementFeed {
+    /// Перше оголошення, яке ще не закрили.
+    pub fn current(&self) -> Option<&Announcement> {
+        self.items.iter().find(|item| !self.dismissed.contains(&item.id))
+    }
+
+    pub fn dismiss(&mut self, id: &str) {
+        self.dismissed.insert(id.to_owned());
+    }
+
+    pub fn replace(&mut self, items: Vec<Announcement>) {
+        // Зі стрічки зникло - пам'ятати, що закрили, більше не треба
+        self.dismissed.retain(|id| items.iter().any(|item| &item.id == id));
+        self.items = items;
+    }
+}
+
+pub fn fetch(url: &str) -> Result<Vec<Announcement>, String> {
+    let body = reqwest::blocking::get(url)
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.text())
+        .map_err(|e| e.to_string())?;
+    parse(&body)
+}
+
+pub fn parse(body: &str) -> Result<Vec<Announcement>, String> {
+    let mut items = if body.trim_start().starts_with('<') { parse_feed(body) } else { parse_json(body)? };
+    items.truncate(MAX_ITEMS);
+    Ok(items)
+}
+
+fn parse_json(body: &str) -> Result<Vec<Announcement>, String> {
+    let json: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    let list = match &json {
+        Value::Array(list) => list,
+        other => other
+            .get("items")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "expected an array or {\"items\": [...]}".to_owned())?,
+    };
+    let field = |item: &Value, names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| match item.get(name) {
+                Some(Value::String(s)) => Some(s.trim().to_owned()),
+                Some(Value::Number(n)) => Some(n.to_string()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    };
+    Ok(list
+        .iter()
+        .filter_map(|item| {
+            Announcement::new(field(item, &["id", "guid"]), field(item, &["text", "title", "message"]), field(item, &["link", "url"]))
+        })
+        .collect())
+}
+
+// RSS (<item>) і Atom (<entry>) без XML-бібліотеки: нам потрібні лише три поля
+fn parse_feed(body: &str) -> Vec<Announcement> {
+    let mut items = Vec::new();
+    for tag in ["item", "entry"] {
+        let (open, close) = (format!("<{}", tag), format!("</{}>", tag));
+        let mut rest = body;
+        while let Some(start) = rest.find(&open) {
+            let block = &rest[start..];
+            let end = match block.find(&close) {
+                Some(end) => end,
+                None => break,
+            };
+            let item = &block[..end];
+            let link = tag_text(item, "link").filter(|l| !l.is_empty()).or_else(|| attribute(item, "link", "href"));
+            let id = tag_text(item, "guid").or_else(|| tag_text(item, "id"));
+            if let Some(announcement) =
+                Announcement::new(id.unwrap_or_default(), tag_text(item, "title").unwrap_or_default(), link.unwrap_or_default())
+            {
+                items.push(announcement);
+            }
+            rest = &block[end + close.len()..];
+        }
+    }
+    items
+}
+
+fn tag_text(xml: &str, name: &str) -> Option<String> {
+    let open = format!("<{}", name);
+    let mut from = 0;
+    // "<link" не повинен збігатися з "<linkedin>"
+    let start = loop {
+        let at = from + xml[from..].find(&open)?;
+        let after = xml[at + open.len()..].chars().next()?;
+        if after == '>' || after.is_whitespace() {
+            break at;
+        }
+        from = at + open.len();
+    };
+    let content_start = start + xml[start..].find('>')? + 1;
+    if xml[..content_start].ends_with("/>") {
+        return None;
+    }
+    let content_end = content_start + xml[content_start..].find(&format!("</{}>", name))?;
+    let text = xml[content_start..content_end].trim();
+    let text = text.strip_prefix("<![CDATA[").and_then(|t| t.strip_suffix("]]>")).unwrap_or(text);
+    Some(decode_entities(text.trim()))
+}
+
+fn attribute(xml: &str, tag: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{} ", tag))?;
+    let element = &xml[start..start + xml[start..].find('>')?];
+    let value_start = element.find(&format!("{}=\"", name))? + name.len() + 2;
+    let value_end = value_start + element[value_start..].find('"')?;
+    Some(decode_entities(&element[value_start..value_end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
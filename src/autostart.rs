@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// --- ЗАПУСК РАЗОМ ІЗ СИСТЕМОЮ ---
+//
+// Windows: значення в HKCU\...\Run, Linux: ~/.config/autostart/*.desktop.
+// Програма стартує з --minimized, щоб моніторинг і сповіщення працювали
+// з самого входу в систему, не відкриваючи вікно поверх усього.
+// Стан не зберігається в конфігу - джерело правди сама ОС.
+
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+const NAME: &str = "SquadBrowser";
+const DESKTOP_FILE: &str = "squad-browser.desktop";
+
+fn desktop_path() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config.join("autostart").join(DESKTOP_FILE))
+}
+
+pub fn is_enabled() -> bool {
+    if cfg!(target_os = "windows") {
+        run("reg", &["query", RUN_KEY, "/v", NAME]).is_ok()
+    } else if cfg!(target_os = "linux") {
+        desktop_path().is_some_and(|path| path.exists())
+    } else {
+        false
+    }
+}
+
+pub fn enable(exe: &Path) -> Result<(), String> {
+    let exe = exe.to_string_lossy();
+    if cfg!(target_os = "windows") {
+        run("reg", &["add", RUN_KEY, "/v", NAME, "/d", &format!("\"{}\" --minimized", exe), "/f"])
+    } else if cfg!(target_os = "linux") {
+        let path = desktop_path().ok_or_else(|| "neither XDG_CONFIG_HOME nor HOME is set".to_owned())?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName=Squad Browser\nExec=\"{}\" --minimized\nX-GNOME-Autostart-enabled=true\n",
+            exe
+        );
+        std::fs::write(path, entry).map_err(|e| e.to_string())
+    } else {
+        Err("autostart is only supported on Windows and Linux".to_owned())
+    }
+}
+
+pub fn disable() -> Result<(), String> {
+    if cfg!(target_os = "windows") {
+        run("reg", &["delete", RUN_KEY, "/v", NAME, "/f"])
+    } else if cfg!(target_os = "linux") {
+        match desktop_path() {
+            Some(path) if path.exists() => std::fs::remove_file(path).map_err(|e| e.to_string()),
+            _ => Ok(()),
+        }
+    } else {
+        Ok(())
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program).args(args).output().map_err(|e| format!("{}: {}", program, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, output.status))
+    }
+}
@@ -0,0 +1,188 @@
+use chrono::{Datelike, Timelike};
+use std::fs;
+
+// --- РЕЗЕРВНА КОПІЯ (один zip з усіма даними) ---
+//
+// Пишемо звичайний zip без стиснення: відкривається будь-яким архіватором,
+// а для відновлення не потрібна бібліотека. Читаємо лише такі ж архіви
+// (метод "stored"); перепакований зі стисненням архів не приймаємо.
+
+pub const SETTINGS_ENTRY: &str = "settings.ron";
+pub const RULES_ENTRY: &str = "rules.txt";
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+// Біт 11: імена файлів у UTF-8
+const UTF8_NAMES: u16 = 0x0800;
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+// Дата й час у форматі MS-DOS, як їх зберігає zip
+fn dos_timestamp() -> (u16, u16) {
+    let now = chrono::Local::now();
+    let time = ((now.hour() << 11) | (now.minute() << 5) | (now.second() / 2)) as u16;
+    let date = (((now.year().max(1980) - 1980) as u32) << 9) | (now.month() << 5) | now.day();
+    (time, date as u16)
+}
+
+pub fn write_zip(path: &str, entries: &[(&str, Vec<u8>)]) -> Result<(), String> {
+    let (time, date) = dos_timestamp();
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in entries {
+        let offset = u32::try_from(out.len()).map_err(|_| "backup is too large".to_owned())?;
+        let size = u32::try_from(data.len()).map_err(|_| "backup is too large".to_owned())?;
+        let crc = crc32(data);
+        // Спільна частина локального заголовка і запису каталогу
+        let mut common = Vec::new();
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&UTF8_NAMES.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&time.to_le_bytes());
+        common.extend_from_slice(&date.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        out.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+        out.extend_from_slice(&common);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        directory.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes());
+        directory.extend_from_slice(&common);
+        // Коментар, диск, внутрішні й зовнішні атрибути
+        directory.extend_from_slice(&[0u8; 10]);
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+    let directory_offset = out.len() as u32;
+    let count = entries.len() as u16;
+    out.extend_from_slice(&directory);
+    out.extend_from_slice(&END_OF_DIRECTORY.to_le_bytes());
+    out.extend_from_slice(&[0u8; 4]);
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    out.extend_from_slice(&directory_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    fs::write(path, out).map_err(|e| e.to_string())
+}
+
+fn u16_at(data: &[u8], at: usize) -> Result<u16, String> {
+    data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or_else(|| "truncated archive".to_owned())
+}
+
+fn u32_at(data: &[u8], at: usize) -> Result<u32, String> {
+    data.get(at..at + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "truncated archive".to_owned())
+}
+
+/// Усі файли архіву: (ім'я, вміст).
+pub fn read_zip(path: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let end = (0..data.len().saturating_sub(21))
+        .rev()
+        .find(|&at| u32_at(&data, at) == Ok(END_OF_DIRECTORY))
+        .ok_or_else(|| "not a zip archive".to_owned())?;
+    let count = u16_at(&data, end + 10)? as usize;
+    let mut at = u32_at(&data, end + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(&data, at)? != CENTRAL_HEADER {
+            return Err("broken zip directory".to_owned());
+        }
+        let method = u16_at(&data, at + 10)?;
+        let crc = u32_at(&data, at + 16)?;
+        let size = u32_at(&data, at + 20)? as usize;
+        let name_len = u16_at(&data, at + 28)? as usize;
+        let extra_len = u16_at(&data, at + 30)? as usize;
+        let comment_len = u16_at(&data, at + 32)? as usize;
+        let offset = u32_at(&data, at + 42)? as usize;
+        let name = data.get(at + 46..at + 46 + name_len).ok_or_else(|| "truncated archive".to_owned())?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        at += 46 + name_len + extra_len + comment_len;
+
+        if method != 0 {
+            return Err(format!("{}: compressed entries are not supported", name));
+        }
+        let start = offset + 30 + u16_at(&data, offset + 26)? as usize + u16_at(&data, offset + 28)? as usize;
+        let content = data.get(start..start + size).ok_or_else(|| "truncated archive".to_owned())?;
+        if crc32(content) != crc {
+            return Err(format!("{}: checksum mismatch", name));
+        }
+        entries.push((name, content.to_vec()));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_zip(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("squad-backup-{}-{}.zip", name, std::process::id()));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn find(data: &[u8], signature: u32) -> usize {
+        (0..data.len()).find(|&at| u32_at(data, at) == Ok(signature)).unwrap()
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let path = temp_zip("round-trip");
+        let entries = vec![(SETTINGS_ENTRY, b"(language: Ua)".to_vec()), (RULES_ENTRY, "country:RU\nname~вагнер\n".as_bytes().to_vec())];
+        write_zip(&path, &entries).unwrap();
+        let read = read_zip(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        for ((name, data), (read_name, read_data)) in entries.iter().zip(&read) {
+            assert_eq!(name, read_name);
+            assert_eq!(data, read_data);
+        }
+    }
+
+    #[test]
+    fn corrupted_entry_fails_checksum() {
+        let path = temp_zip("crc");
+        write_zip(&path, &[(RULES_ENTRY, b"country:RU".to_vec())]).unwrap();
+        let mut data = fs::read(&path).unwrap();
+        // Перший байт вмісту - одразу після локального заголовка й імені
+        let content = 30 + RULES_ENTRY.len();
+        data[content] ^= 0xFF;
+        fs::write(&path, data).unwrap();
+        let error = read_zip(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(error.contains("checksum mismatch"), "{}", error);
+    }
+
+    #[test]
+    fn compressed_entry_is_rejected() {
+        let path = temp_zip("deflate");
+        write_zip(&path, &[(SETTINGS_ENTRY, b"()".to_vec())]).unwrap();
+        let mut data = fs::read(&path).unwrap();
+        // Метод 8 (deflate) у записі каталогу
+        let method = find(&data, CENTRAL_HEADER) + 10;
+        data[method] = 8;
+        fs::write(&path, data).unwrap();
+        let error = read_zip(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(error.contains("compressed entries are not supported"), "{}", error);
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::ServerItem;
+
+// --- ПАКЕТНІ ЗАПИТИ ЗА ID ---
+//
+// Присутність закріплених серверів і асистент черги питають API про
+// конкретні сервери (filter[ids][whitelist]). Замість окремого запиту для
+// кожного - один спільний: хто вже на черзі або стане протягом COALESCE,
+// їде разом, id без повторів і по MAX_IDS на сторінку. Відповідь містить
+// повні записи (онлайн, черги, карта, версія), тож її вистачає всім.
+
+// Хто стане на черзі за цей час, іде тим самим запитом
+pub const COALESCE: Duration = Duration::from_secs(10);
+// page[size] у provider::first_page_params - більше за раз не прийде
+pub const MAX_IDS: usize = 50;
+
+#[derive(Default, Debug)]
+pub struct Batch {
+    // Закріплені сервери для перевірки присутності
+    pub presence: Vec<String>,
+    // Цілі асистента черги
+    pub queue: Vec<String>,
+}
+
+impl Batch {
+    /// Унікальні id, по MAX_IDS через кому - значення для filter[ids][whitelist].
+    pub fn chunks(&self) -> Vec<String> {
+        let mut ids: Vec<&str> = Vec::new();
+        for id in self.presence.iter().chain(self.queue.iter()) {
+            if !ids.contains(&id.as_str()) {
+                ids.push(id);
+            }
+        }
+        ids.chunks(MAX_IDS).map(|chunk| chunk.join(",")).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Fetched {
+    // Розібрані записи - для асистента черги й оновлення закріплених
+    pub servers: Vec<ServerItem>,
+    // id усіх отриманих записів, навіть зіпсованих - для присутності
+    pub ids: HashSet<String>,
+}
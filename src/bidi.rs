@@ -0,0 +1,163 @@
+use eframe::egui;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+// --- ТЕКСТ І РОЗКЛАДКА СПРАВА НАЛІВО (іврит, арабська) ---
+//
+// egui розкладає гліфи лише зліва направо і не знає Unicode Bidi, тому
+// рядок з RTL-літерами переставляємо у візуальний порядок перед показом.
+// Це спрощений алгоритм (без вкладених рівнів і арабського з'єднання літер),
+// але його досить для назв серверів і перекладів інтерфейсу.
+//
+// Дзеркальна розкладка: для RTL-мови панелі переходять на інший бік, а
+// вертикальні розкладки вирівнюються праворуч - тоді ui.horizontal в egui сам
+// будує ряди справа наліво. Гліфи івриту й арабської ще треба додати
+// шрифтом у FontDefinitions разом із перекладом.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// Вертикальна розкладка панелі; від неї ряди всередині беруть напрямок.
+    pub fn column(self) -> egui::Layout {
+        match self {
+            Direction::Ltr => egui::Layout::top_down(egui::Align::Min),
+            Direction::Rtl => egui::Layout::top_down(egui::Align::Max),
+        }
+    }
+
+    /// Кінець ряду (кнопки праворуч для LTR, ліворуч для RTL).
+    pub fn trailing(self) -> egui::Layout {
+        match self {
+            Direction::Ltr => egui::Layout::right_to_left(egui::Align::Center),
+            Direction::Rtl => egui::Layout::left_to_right(egui::Align::Center),
+        }
+    }
+
+    /// Бічна панель з боку початку рядка.
+    pub fn side_panel(self, id: impl Into<egui::Id>) -> egui::SidePanel {
+        match self {
+            Direction::Ltr => egui::SidePanel::left(id),
+            Direction::Rtl => egui::SidePanel::right(id),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Left,
+    Right,
+    Neutral,
+}
+
+fn class(c: char) -> Class {
+    match c as u32 {
+        0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF => Class::Right,
+        _ if c.is_alphanumeric() => Class::Left,
+        _ => Class::Neutral,
+    }
+}
+
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        other => other,
+    }
+}
+
+/// Логічний порядок -> візуальний. Базовий напрямок - за першою "сильною"
+/// літерою, як у Unicode (P2/P3). Без RTL-літер рядок повертається як є.
+pub fn visual(text: &str) -> Cow<'_, str> {
+    let chars: Vec<char> = text.chars().collect();
+    let classes: Vec<Class> = chars.iter().map(|&c| class(c)).collect();
+    if !classes.contains(&Class::Right) {
+        return Cow::Borrowed(text);
+    }
+    let base = classes.iter().copied().find(|&c| c != Class::Neutral).unwrap_or(Class::Left);
+
+    // Нейтральні символи між літерами одного напрямку беруть його, інші - базовий
+    let mut resolved = classes.clone();
+    for index in 0..resolved.len() {
+        if classes[index] != Class::Neutral {
+            continue;
+        }
+        let before = classes[..index].iter().rev().copied().find(|&c| c != Class::Neutral);
+        let after = classes[index + 1..].iter().copied().find(|&c| c != Class::Neutral);
+        resolved[index] = match (before, after) {
+            (Some(before), Some(after)) if before == after => before,
+            _ => base,
+        };
+    }
+
+    let mut runs: Vec<(Class, Vec<char>)> = Vec::new();
+    for (&c, &direction) in chars.iter().zip(&resolved) {
+        match runs.last_mut() {
+            Some((last, run)) if *last == direction => run.push(c),
+            _ => runs.push((direction, vec![c])),
+        }
+    }
+    if base == Class::Right {
+        runs.reverse();
+    }
+    let mut out = String::with_capacity(text.len());
+    for (direction, run) in runs {
+        if direction == Class::Right {
+            out.extend(run.iter().rev().map(|&c| mirror(c)));
+        } else {
+            out.extend(run);
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Те саме для статичних рядків перекладу: результат кешується, тож пам'ять
+/// обмежена кількістю ключів.
+pub fn visual_static(text: &'static str) -> &'static str {
+    static CACHE: OnceLock<Mutex<HashMap<&'static str, &'static str>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    cache.entry(text).or_insert_with(|| match visual(text) {
+        Cow::Borrowed(text) => text,
+        Cow::Owned(text) => Box::leak(text.into_boxed_str()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ltr_text_is_untouched() {
+        assert!(matches!(visual("Squad EU #1 (24/7)"), Cow::Borrowed("Squad EU #1 (24/7)")));
+    }
+
+    #[test]
+    fn rtl_text_is_reversed_and_mirrored() {
+        assert_eq!(visual("שלום"), "םולש");
+        assert_eq!(visual("(שלום)"), "(םולש)");
+    }
+
+    #[test]
+    fn mixed_runs_keep_their_own_order() {
+        // База - LTR: слово івритом на своєму місці, перевернуте всередині
+        assert_eq!(visual("Server שלום 1"), "Server םולש 1");
+        // База - RTL: англійське слово стоїть ліворуч і не перевертається
+        assert_eq!(visual("שרת EU"), "EU תרש");
+    }
+
+    #[test]
+    fn digits_inside_rtl_stay_left_to_right() {
+        assert_eq!(visual("שרת 24/7"), "24/7 תרש");
+        assert_eq!(visual("خادم 123"), "123 مداخ");
+    }
+}
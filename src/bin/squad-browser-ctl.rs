@@ -0,0 +1,44 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::process::ExitCode;
+use std::time::Duration;
+
+// --- squad-browser-ctl: керування запущеним вікном з командного рядка ---
+//
+// squad-browser-ctl refresh
+// squad-browser-ctl join 1234567
+// squad-browser-ctl set-filter map=narva min=40
+//
+// Порт має збігатися з src/ipc.rs.
+
+const DEFAULT_PORT: u16 = 47815;
+const PORT_ENV: &str = "SQUAD_CTL_PORT";
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        eprintln!("usage: squad-browser-ctl <command> [args...]   (try \"help\")");
+        return ExitCode::FAILURE;
+    }
+    match send(&args.join(" ")) {
+        Ok(reply) => {
+            println!("{}", reply);
+            if reply.starts_with("OK") { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(e) => {
+            eprintln!("[ERR] squad-browser is not reachable: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn send(line: &str) -> std::io::Result<String> {
+    let port = std::env::var(PORT_ENV).ok().and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT);
+    let stream = TcpStream::connect_timeout(&SocketAddr::from(([127, 0, 0, 1], port)), TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    writeln!(&stream, "{}", line)?;
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    Ok(reply.trim_end().to_owned())
+}
@@ -0,0 +1,182 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+use crate::filters::{FilterSet, LegacyFilters};
+use crate::SquadApp;
+
+// --- ВЕРСІЇ КОНФІГУ ТА МІГРАЦІЇ ---
+//
+// Нові поля додаються з #[serde(default)] і міграцій не потребують. Міграція
+// потрібна, коли змінюється зміст або сенс вже збереженого поля.
+
+pub const CONFIG_VERSION: u32 = 2;
+
+// MIGRATIONS[n] переводить конфіг з версії n у n + 1. Окрім розібраного стану
+// отримує й сирий текст - для полів, яких у SquadApp вже немає.
+const MIGRATIONS: [fn(&mut SquadApp, &str); CONFIG_VERSION as usize] = [migrate_v0_to_v1, migrate_v1_to_v2];
+
+// v0 - конфіги без config_version. Тоді повзунки гравців не були зв'язані,
+// і мінімум міг бути збережений більшим за максимум. Ці поля тепер читає лише
+// migrate_v1_to_v2, тож і виправлення - там (From<LegacyFilters>).
+fn migrate_v0_to_v1(_app: &mut SquadApp, _raw: &str) {}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LegacySearch {
+    filters: LegacyFilters,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LegacyTab {
+    filters: LegacyFilters,
+}
+
+// Списки конфігу v1, де фільтри зберігались окремими полями
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LegacyLists {
+    saved_searches: Vec<LegacySearch>,
+    tabs: Vec<LegacyTab>,
+}
+
+// v2: фільтри з окремих полів переїхали у FilterSet - у самому додатку,
+// у збережених пошуках і у вкладках. Старі поля кореня читаємо тим самим
+// текстом як LegacyFilters: зайві поля serde просто пропускає.
+fn migrate_v1_to_v2(app: &mut SquadApp, raw: &str) {
+    let (root, legacy) = match (ron::from_str::<LegacyFilters>(raw), ron::from_str::<LegacyLists>(raw)) {
+        (Ok(root), Ok(legacy)) => (root, legacy),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("[ERR] Failed to read old filter fields: {}", e);
+            return;
+        }
+    };
+    app.filters = FilterSet::from(root);
+    // За порядком, як і вкладки: назви пошуків можуть повторюватись
+    for (search, old) in app.saved_searches.iter_mut().zip(legacy.saved_searches) {
+        search.filters = FilterSet::from(old.filters);
+    }
+    for (tab, old) in app.tabs.iter_mut().zip(legacy.tabs) {
+        tab.filters = FilterSet::from(old.filters);
+    }
+}
+
+/// Доводить конфіг до поточної версії. Повертає true, якщо щось змінилось.
+pub fn migrate(app: &mut SquadApp, raw: &str) -> bool {
+    let from = app.config_version;
+    if from >= CONFIG_VERSION {
+        return false;
+    }
+    for migration in &MIGRATIONS[from as usize..] {
+        migration(app, raw);
+    }
+    app.config_version = CONFIG_VERSION;
+    println!("[LOG] Config migrated from v{} to v{}", from, CONFIG_VERSION);
+    true
+}
+
+/// Розбір збереженого стану. На відміну від unwrap_or_default(), перед міграцією
+/// або при помилці розбору зберігає копію файлу, щоб дані не губились мовчки.
+pub fn load(raw: Option<&str>, config_path: Option<&Path>) -> SquadApp {
+    let raw = match raw {
+        Some(raw) => raw,
+        None => return SquadApp::default(),
+    };
+    match ron::from_str::<SquadApp>(raw) {
+        Ok(mut app) => {
+            if app.config_version > CONFIG_VERSION {
+                println!("[ERR] Config v{} is newer than this build (v{})", app.config_version, CONFIG_VERSION);
+            } else if app.config_version < CONFIG_VERSION {
+                backup(config_path, &format!("v{}", app.config_version));
+                migrate(&mut app, raw);
+            }
+            app
+        }
+        Err(e) => {
+            println!("[ERR] Failed to parse saved config, using defaults: {}", e);
+            backup(config_path, "broken");
+            SquadApp::default()
+        }
+    }
+}
+
+fn backup(config_path: Option<&Path>, suffix: &str) {
+    let path = match config_path {
+        Some(path) if path.exists() => path,
+        _ => return,
+    };
+    let backup = path.with_extension(format!("{}.bak.ron", suffix));
+    match fs::copy(path, &backup) {
+        Ok(_) => println!("[LOG] Config backed up to {}", backup.display()),
+        Err(e) => println!("[ERR] Failed to back up config: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::Filter;
+
+    fn set(filters: &[Filter]) -> FilterSet {
+        let mut set = FilterSet::default();
+        for filter in filters {
+            set.set(filter.clone());
+        }
+        set
+    }
+
+    // Корінь, два пошуки з однаковою назвою і дві вкладки у форматі v1
+    const LISTS: &str = r#"
+        min_players: 90,
+        max_players: 20,
+        banned_countries: ["RU"],
+        filter_map: "Gorodok",
+        saved_searches: [
+            (name: "Seed", filters: (min_players: 10, max_players: 40, filter_mode: "Seed"), monitor: true),
+            (name: "Seed", filters: (min_players: 0, max_players: 100, hide_no_anticheat: true), monitor: false),
+        ],
+        tabs: [
+            (title: "EU", filters: (filter_name: "EU", max_public_queue: Some(5))),
+            (title: "Full", filters: (min_players: 100, max_players: 60)),
+        ],
+    "#;
+
+    fn check(app: &SquadApp) {
+        assert_eq!(app.config_version, CONFIG_VERSION);
+        assert_eq!(
+            app.filters,
+            set(&[
+                Filter::MinPlayers(20),
+                Filter::MaxPlayers(90),
+                Filter::Map("Gorodok".into()),
+                Filter::NoCountry("RU".into()),
+            ])
+        );
+        assert_eq!(app.saved_searches.len(), 2);
+        assert_eq!(
+            app.saved_searches[0].filters,
+            set(&[Filter::MinPlayers(10), Filter::MaxPlayers(40), Filter::Mode("Seed".into())])
+        );
+        assert!(app.saved_searches[0].monitor);
+        assert_eq!(app.saved_searches[1].filters, set(&[Filter::MaxPlayers(100), Filter::AntiCheatOnly]));
+        assert_eq!(app.tabs.len(), 2);
+        assert_eq!(
+            app.tabs[0].filters,
+            set(&[Filter::MaxPlayers(100), Filter::Name("EU".into()), Filter::MaxPublicQueue(5)])
+        );
+        assert_eq!(app.tabs[1].filters, set(&[Filter::MinPlayers(60), Filter::MaxPlayers(100)]));
+    }
+
+    #[test]
+    fn v0_config_is_migrated() {
+        let raw = format!("({})", LISTS);
+        check(&load(Some(&raw), None));
+    }
+
+    #[test]
+    fn v1_config_is_migrated() {
+        let raw = format!("(config_version: 1, {})", LISTS);
+        check(&load(Some(&raw), None));
+    }
+}
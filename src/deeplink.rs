@@ -0,0 +1,211 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::filters::{Filter, FilterSet};
+
+// --- ПОСИЛАННЯ squadbrowser:// ---
+//
+// squadbrowser://server/12345         - відкрити й розгорнути сервер
+// squadbrowser://filter?map=narva&min=40 - застосувати набір фільтрів
+//
+// Ключі фільтрів - ті самі, що в squad-browser-ctl (ipc::parse_filter).
+// Посилання лише показує сервер чи фільтри, але ніколи не заходить у гру:
+// його може надіслати будь-хто в Discord.
+//
+// ОС запускає програму з посиланням аргументом; якщо вікно вже відкрите,
+// посилання передається йому через сокет керування (ipc::send).
+
+pub const SCHEME: &str = "squadbrowser";
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum DeepLink {
+    Server(String),
+    // Пари "key=value" для ipc::parse_filter
+    Filters(Vec<String>),
+}
+
+pub fn parse(url: &str) -> Result<DeepLink, String> {
+    let prefix = format!("{}://", SCHEME);
+    let rest = url
+        .trim()
+        .get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(&prefix))
+        .map(|_| &url.trim()[prefix.len()..])
+        .ok_or_else(|| format!("expected a {} link, got \"{}\"", prefix, url))?;
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let mut parts = path.split('/').filter(|part| !part.is_empty());
+    match parts.next() {
+        Some("server") => {
+            let id = parts.next().map(decode).unwrap_or_default();
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("expected a numeric server id, got \"{}\"", id));
+            }
+            Ok(DeepLink::Server(id))
+        }
+        Some("filter") => {
+            let pairs: Vec<String> = query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| match pair.split_once('=') {
+                    Some((key, value)) => format!("{}={}", decode(key), decode(value)),
+                    None => format!("{}=on", decode(pair)),
+                })
+                .collect();
+            if pairs.is_empty() {
+                return Err("the filter link has no filters".to_owned());
+            }
+            Ok(DeepLink::Filters(pairs))
+        }
+        other => Err(format!("unknown link type \"{}\"", other.unwrap_or_default())),
+    }
+}
+
+pub fn server_link(id: &str) -> String {
+    format!("{}://server/{}", SCHEME, encode(id))
+}
+
+/// Посилання на фільтри, які можна передати; рядки розширеного запиту не передаються.
+pub fn filter_link(filters: &FilterSet) -> String {
+    let pairs: Vec<String> = filters
+        .iter()
+        .filter_map(|filter| {
+            let (key, value) = match filter {
+                Filter::Name(text) => ("name", text.clone()),
+                Filter::Map(text) => ("map", text.clone()),
+                Filter::Mode(text) => ("mode", text.clone()),
+                Filter::MinPlayers(n) => ("min", n.to_string()),
+                Filter::MaxPlayers(n) => ("max", n.to_string()),
+                Filter::MaxPublicQueue(n) => ("queue", n.to_string()),
+                Filter::MaxReservedQueue(n) => ("reserved", n.to_string()),
+                Filter::AntiCheatOnly => ("anticheat", "on".to_owned()),
+                Filter::NoCountry(code) => ("no-country", code.clone()),
+                Filter::Vehicles(class) => ("vehicles", class.key().to_owned()),
+                Filter::Param(..) => return None,
+            };
+            Some(format!("{}={}", key, encode(&value)))
+        })
+        .collect();
+    format!("{}://filter?{}", SCHEME, pairs.join("&"))
+}
+
+fn encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => match text.get(index + 1..index + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                Some(byte) => {
+                    out.push(byte);
+                    index += 3;
+                    continue;
+                }
+                None => out.push(b'%'),
+            },
+            b'+' => out.push(b' '),
+            byte => out.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Реєструє програму як обробник squadbrowser:// для поточного користувача.
+pub fn register(exe: &Path) -> Result<String, String> {
+    let exe = exe.to_string_lossy();
+    if cfg!(target_os = "windows") {
+        let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+        let command = format!("\"{}\" --open \"%1\"", exe);
+        run("reg", &["add", &key, "/ve", "/d", "URL:Squad Browser", "/f"])?;
+        run("reg", &["add", &key, "/v", "URL Protocol", "/d", "", "/f"])?;
+        run("reg", &["add", &format!(r"{}\shell\open\command", key), "/ve", "/d", &command, "/f"])?;
+        Ok(key)
+    } else if cfg!(target_os = "linux") {
+        let home = std::env::var("HOME").map_err(|_| "HOME is not set".to_owned())?;
+        let dir = Path::new(&home).join(".local/share/applications");
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let file = format!("{}.desktop", SCHEME);
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName=Squad Browser\nExec=\"{}\" --open %u\nMimeType=x-scheme-handler/{};\nNoDisplay=true\n",
+            exe, SCHEME
+        );
+        std::fs::write(dir.join(&file), entry).map_err(|e| e.to_string())?;
+        run("xdg-mime", &["default", &file, &format!("x-scheme-handler/{}", SCHEME)])?;
+        Ok(dir.join(file).display().to_string())
+    } else {
+        // macOS бере схеми лише з Info.plist пакета .app (CFBundleURLTypes)
+        Err("declare the scheme in the app bundle's Info.plist (CFBundleURLTypes)".to_owned())
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let status = Command::new(program).args(args).status().map_err(|e| format!("{}: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc;
+    use crate::layerdb::VehicleClass;
+
+    #[test]
+    fn parses_server_links() {
+        assert_eq!(parse(" SquadBrowser://server/12345/ "), Ok(DeepLink::Server("12345".into())));
+        assert_eq!(parse(&server_link("987")), Ok(DeepLink::Server("987".into())));
+        assert!(parse("squadbrowser://server/").is_err());
+        assert!(parse("squadbrowser://server/12;rm").is_err());
+        assert!(parse("steam://connect/1.2.3.4:7787").is_err());
+        assert_eq!(parse("squadbrowser://join/1"), Err("unknown link type \"join\"".into()));
+    }
+
+    #[test]
+    fn parses_filter_queries() {
+        assert_eq!(
+            parse("squadbrowser://filter?name=UA%20Squad+EU&anticheat&bad%zz=1"),
+            Ok(DeepLink::Filters(vec!["name=UA Squad EU".into(), "anticheat=on".into(), "bad%zz=1".into()]))
+        );
+        assert!(parse("squadbrowser://filter?").is_err());
+    }
+
+    #[test]
+    fn filter_link_round_trips() {
+        let mut filters = FilterSet::default();
+        for filter in [
+            Filter::Name("Січ & co=1".into()),
+            Filter::MinPlayers(40),
+            Filter::AntiCheatOnly,
+            Filter::NoCountry("RU".into()),
+            Filter::Vehicles(VehicleClass::HeavyArmor),
+        ] {
+            filters.set(filter);
+        }
+        filters.set_params(vec![("filter[rank]".into(), "1".into())]);
+        let pairs = match parse(&filter_link(&filters)) {
+            Ok(DeepLink::Filters(pairs)) => pairs,
+            other => panic!("{:?}", other),
+        };
+        let mut parsed = FilterSet::default();
+        for pair in pairs {
+            parsed.set(ipc::parse_filter(&pair).unwrap().0);
+        }
+        // Рядки розширеного запиту в посилання не потрапляють
+        filters.set_params(Vec::new());
+        assert_eq!(parsed, filters);
+    }
+}
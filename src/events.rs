@@ -0,0 +1,74 @@
+use chrono::{NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+// --- ЗАПЛАНОВАНІ ПОДІЇ (сід, скрими) ТА ЕКСПОРТ У iCal ---
+//
+// Файл .ics перезаписується після кожної зміни, тож календар, підписаний
+// на нього, бачить нові події без ручного імпорту.
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct PlannedEvent {
+    pub title: String,
+    pub server_id: String,
+    pub server_name: String,
+    // Секунди від UNIX epoch
+    pub start: u64,
+    pub minutes: u32,
+}
+
+/// Дата "2024-05-01" і час "19:00" за місцевим часом -> секунди від UNIX epoch.
+pub fn parse_local(date: &str, time: &str) -> Result<u64, String> {
+    let naive = NaiveDateTime::parse_from_str(&format!("{} {}", date.trim(), time.trim()), "%Y-%m-%d %H:%M")
+        .map_err(|_| "expected YYYY-MM-DD and HH:MM".to_owned())?;
+    let local = chrono::Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| "this time does not exist locally".to_owned())?;
+    u64::try_from(local.timestamp()).map_err(|_| "date before 1970".to_owned())
+}
+
+pub fn format_local(secs: u64) -> String {
+    match chrono::Local.timestamp_opt(secs as i64, 0).single() {
+        Some(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+        None => "?".to_owned(),
+    }
+}
+
+fn ics_time(secs: u64) -> String {
+    match chrono::Utc.timestamp_opt(secs as i64, 0).single() {
+        Some(time) => time.format("%Y%m%dT%H%M%SZ").to_string(),
+        None => "19700101T000000Z".to_owned(),
+    }
+}
+
+// Спецсимволи текстових полів за RFC 5545
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+pub fn to_ics(events: &[PlannedEvent], now: u64) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//squad-browser//events//EN".to_owned(),
+        "CALSCALE:GREGORIAN".to_owned(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_owned());
+        // Стабільний UID: календар оновлює подію, а не дублює її
+        lines.push(format!("UID:{}-{}@squad-browser", event.start, event.server_id));
+        lines.push(format!("DTSTAMP:{}", ics_time(now)));
+        lines.push(format!("DTSTART:{}", ics_time(event.start)));
+        lines.push(format!("DTEND:{}", ics_time(event.start + event.minutes as u64 * 60)));
+        lines.push(format!("SUMMARY:{}", escape(&event.title)));
+        if !event.server_id.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", escape(&event.server_name)));
+            lines.push(format!("URL:https://www.battlemetrics.com/servers/squad/{}", event.server_id));
+        }
+        lines.push("END:VEVENT".to_owned());
+    }
+    lines.push("END:VCALENDAR".to_owned());
+    // iCal вимагає CRLF
+    lines.join("\r\n") + "\r\n"
+}
@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// --- ЕКСПОРТ СПИСКУ СЕРВЕРІВ (CSV / JSON) ---
+//
+// Користувач сам вибирає колонки; набір колонок з форматом можна зберегти
+// як пресет. Значення рахує додаток (там історія, пінг, сесії) - тут лише
+// перелік колонок і запис у формат.
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Column {
+    Id,
+    Name,
+    Players,
+    MaxPlayers,
+    PublicQueue,
+    ReservedQueue,
+    Map,
+    Mode,
+    Country,
+    City,
+    Version,
+    AntiCheat,
+    Address,
+    QueryPort,
+    // Розширені: з історії пінгу, доступності та сесій (лише для серверів, де є дані)
+    PingAverage,
+    PingJitter,
+    Uptime7d,
+    Trend,
+    PlaytimeMinutes,
+}
+
+impl Column {
+    pub const ALL: [Column; 19] = [
+        Column::Id,
+        Column::Name,
+        Column::Players,
+        Column::MaxPlayers,
+        Column::PublicQueue,
+        Column::ReservedQueue,
+        Column::Map,
+        Column::Mode,
+        Column::Country,
+        Column::City,
+        Column::Version,
+        Column::AntiCheat,
+        Column::Address,
+        Column::QueryPort,
+        Column::PingAverage,
+        Column::PingJitter,
+        Column::Uptime7d,
+        Column::Trend,
+        Column::PlaytimeMinutes,
+    ];
+
+    /// Назва у заголовку CSV і ключ у JSON.
+    pub fn key(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Name => "name",
+            Column::Players => "players",
+            Column::MaxPlayers => "max_players",
+            Column::PublicQueue => "queue",
+            Column::ReservedQueue => "reserved_queue",
+            Column::Map => "layer",
+            Column::Mode => "mode",
+            Column::Country => "country",
+            Column::City => "city",
+            Column::Version => "version",
+            Column::AntiCheat => "anticheat",
+            Column::Address => "address",
+            Column::QueryPort => "query_port",
+            Column::PingAverage => "ping_ms",
+            Column::PingJitter => "jitter_ms",
+            Column::Uptime7d => "uptime_7d_percent",
+            Column::Trend => "trend",
+            Column::PlaytimeMinutes => "playtime_minutes",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Preset {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub format: Format,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct ExportSettings {
+    pub columns: Vec<Column>,
+    pub format: Format,
+    // Без розширення: його дає формат
+    pub path: String,
+    pub presets: Vec<Preset>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Name, Column::Players, Column::MaxPlayers, Column::PublicQueue, Column::Map, Column::Address],
+            format: Format::Csv,
+            path: "squad_servers".to_owned(),
+            presets: Vec::new(),
+        }
+    }
+}
+
+impl ExportSettings {
+    pub fn file_name(&self) -> String {
+        format!("{}.{}", self.path, self.format.extension())
+    }
+
+    /// Вмикає або вимикає колонку, зберігаючи порядок з Column::ALL.
+    pub fn toggle(&mut self, column: Column) {
+        if self.columns.contains(&column) {
+            self.columns.retain(|c| *c != column);
+        } else {
+            self.columns.push(column);
+            self.columns.sort_by_key(|c| Column::ALL.iter().position(|all| all == c));
+        }
+    }
+
+    pub fn save_preset(&mut self, name: &str) {
+        let preset = Preset { name: name.to_owned(), columns: self.columns.clone(), format: self.format };
+        match self.presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    pub fn load_preset(&mut self, index: usize) {
+        if let Some(preset) = self.presets.get(index) {
+            self.columns = preset.columns.clone();
+            self.format = preset.format;
+        }
+    }
+
+    /// Рядки - значення в порядку `columns`.
+    pub fn render(&self, rows: &[Vec<Value>]) -> String {
+        match self.format {
+            Format::Csv => to_csv(&self.columns, rows),
+            Format::Json => to_json(&self.columns, rows),
+        }
+    }
+}
+
+fn csv_field(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn to_csv(columns: &[Column], rows: &[Vec<Value>]) -> String {
+    let header: Vec<&str> = columns.iter().map(|c| c.key()).collect();
+    let mut lines = vec![header.join(",")];
+    for row in rows {
+        lines.push(row.iter().map(csv_field).collect::<Vec<_>>().join(","));
+    }
+    // Excel відкриває UTF-8 правильно лише з BOM
+    format!("\u{FEFF}{}\r\n", lines.join("\r\n"))
+}
+
+fn to_json(columns: &[Column], rows: &[Vec<Value>]) -> String {
+    let objects: Vec<Value> = rows
+        .iter()
+        .map(|row| {
+            let object: Map<String, Value> = columns.iter().map(|c| c.key().to_owned()).zip(row.iter().cloned()).collect();
+            Value::Object(object)
+        })
+        .collect();
+    serde_json::to_string_pretty(&objects).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn settings(format: Format) -> ExportSettings {
+        ExportSettings { columns: vec![Column::Name, Column::Players, Column::PingAverage], format, ..Default::default() }
+    }
+
+    fn rows() -> Vec<Vec<Value>> {
+        vec![vec![json!("Squad \"EU\", #1"), json!(80), Value::Null], vec![json!("Січ"), json!(0), json!(42.5)]]
+    }
+
+    #[test]
+    fn csv_quotes_and_bom() {
+        assert_eq!(
+            settings(Format::Csv).render(&rows()),
+            "\u{FEFF}name,players,ping_ms\r\n\"Squad \"\"EU\"\", #1\",80,\r\nСіч,0,42.5\r\n"
+        );
+    }
+
+    #[test]
+    fn json_uses_column_keys() {
+        let parsed: Value = serde_json::from_str(&settings(Format::Json).render(&rows())).unwrap();
+        assert_eq!(
+            parsed,
+            json!([
+                {"name": "Squad \"EU\", #1", "players": 80, "ping_ms": null},
+                {"name": "Січ", "players": 0, "ping_ms": 42.5},
+            ])
+        );
+    }
+
+    #[test]
+    fn toggle_keeps_column_order_and_presets_replace_by_name() {
+        let mut settings = settings(Format::Csv);
+        settings.toggle(Column::Id);
+        settings.toggle(Column::Players);
+        assert_eq!(settings.columns, vec![Column::Id, Column::Name, Column::PingAverage]);
+        settings.save_preset("short");
+        settings.format = Format::Json;
+        settings.save_preset("short");
+        assert_eq!(settings.presets.len(), 1);
+        settings.format = Format::Csv;
+        settings.columns.clear();
+        settings.load_preset(0);
+        assert_eq!(settings.columns.len(), 3);
+        assert_eq!(settings.file_name(), "squad_servers.json");
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{firstseen, primetime};
+use crate::regions::REGIONS;
+use crate::{sessions, ServerItem};
+use serde::{Deserialize, Serialize};
+
+// --- ВИРАЗИ ФІЛЬТРІВ (для розумних списків) ---
+//
+// Умови через кому - усі мають виконуватись; варіанти через | - хоча б один;
+// ! на початку умови - заперечення. Приклад:
+//   mode:invasion, country:DE|country:FR, queue<10, !name:seed
+//
+// Терми: country:UA  region:asia  map:narva  mode:raas  name:текст
+//        players>=60  queue<10  anticheat
+//        new (вперше помічений за останній тиждень)  age<30 (днів від першої появи)
+//        evening (прайм-тайм припадає на мій вечір, 18-23)
+
+pub const SYNTAX_HINT: &str =
+    "mode:invasion, country:DE|country:FR, players>=60, queue<10, anticheat, !name:seed, region:asia, new, age<30, evening";
+// Вираз готового списку "нові сервери за тиждень"
+pub const NEW_SERVERS: &str = "new";
+// Вираз готового списку "активні моїми вечорами"
+pub const MY_EVENINGS: &str = "evening";
+
+#[derive(Clone, Copy, Debug)]
+enum Cmp {
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+}
+
+impl Cmp {
+    fn check(self, value: u32, limit: u32) -> bool {
+        match self {
+            Cmp::Less => value < limit,
+            Cmp::LessEq => value <= limit,
+            Cmp::Greater => value > limit,
+            Cmp::GreaterEq => value >= limit,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Term {
+    Country(String),
+    Region(&'static [&'static str]),
+    Map(String),
+    Mode(String),
+    Name(String),
+    Players(Cmp, u32),
+    Queue(Cmp, u32),
+    AntiCheat,
+    New,
+    Age(Cmp, u32),
+    Evening,
+}
+
+#[derive(Clone, Debug)]
+struct Clause {
+    negated: bool,
+    any_of: Vec<Term>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Expr {
+    clauses: Vec<Clause>,
+}
+
+fn parse_number(field: &str, rest: &str) -> Result<(Cmp, u32), String> {
+    let (cmp, number) = if let Some(n) = rest.strip_prefix(">=") {
+        (Cmp::GreaterEq, n)
+    } else if let Some(n) = rest.strip_prefix("<=") {
+        (Cmp::LessEq, n)
+    } else if let Some(n) = rest.strip_prefix('>') {
+        (Cmp::Greater, n)
+    } else if let Some(n) = rest.strip_prefix('<') {
+        (Cmp::Less, n)
+    } else {
+        return Err(format!("expected <, <=, > or >= after \"{}\"", field));
+    };
+    let limit = number.trim().parse().map_err(|_| format!("invalid number in \"{}{}\"", field, rest))?;
+    Ok((cmp, limit))
+}
+
+fn parse_term(text: &str) -> Result<Term, String> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("anticheat") {
+        return Ok(Term::AntiCheat);
+    }
+    if text.eq_ignore_ascii_case("new") {
+        return Ok(Term::New);
+    }
+    if text.eq_ignore_ascii_case("evening") {
+        return Ok(Term::Evening);
+    }
+    if let Some(rest) = text.strip_prefix("age") {
+        let (cmp, limit) = parse_number("age", rest)?;
+        return Ok(Term::Age(cmp, limit));
+    }
+    if let Some(rest) = text.strip_prefix("players") {
+        let (cmp, limit) = parse_number("players", rest)?;
+        return Ok(Term::Players(cmp, limit));
+    }
+    if let Some(rest) = text.strip_prefix("queue") {
+        let (cmp, limit) = parse_number("queue", rest)?;
+        return Ok(Term::Queue(cmp, limit));
+    }
+
+    let (field, value) = text.split_once(':').ok_or_else(|| format!("unknown term \"{}\"", text))?;
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("empty value in \"{}\"", text));
+    }
+    match field.trim().to_lowercase().as_str() {
+        "country" => Ok(Term::Country(value.to_uppercase())),
+        "map" => Ok(Term::Map(value.to_lowercase())),
+        "mode" => Ok(Term::Mode(value.to_lowercase())),
+        "name" => Ok(Term::Name(value.to_lowercase())),
+        "region" => {
+            let key = format!("region_{}", value.to_lowercase());
+            REGIONS
+                .iter()
+                .find(|region| region.key == key)
+                .map(|region| Term::Region(region.countries))
+                .ok_or_else(|| format!("unknown region \"{}\"", value))
+        }
+        other => Err(format!("unknown field \"{}\"", other)),
+    }
+}
+
+impl Expr {
+    pub fn parse(text: &str) -> Result<Expr, String> {
+        let mut clauses = Vec::new();
+        for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (negated, body) = match part.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, part),
+            };
+            let any_of = body.split('|').map(parse_term).collect::<Result<Vec<_>, _>>()?;
+            clauses.push(Clause { negated, any_of });
+        }
+        Ok(Expr { clauses })
+    }
+
+    pub fn matches(&self, server: &ServerItem) -> bool {
+        self.clauses.iter().all(|clause| {
+            let hit = clause.any_of.iter().any(|term| term_matches(term, server));
+            hit != clause.negated
+        })
+    }
+}
+
+fn term_matches(term: &Term, server: &ServerItem) -> bool {
+    match term {
+        Term::Country(code) => server.country.as_ref() == code.as_str(),
+        Term::Region(countries) => countries.contains(&server.country.as_ref()),
+        Term::Map(map) => server.map.to_lowercase().contains(map.as_str()),
+        Term::Mode(mode) => server.mode.to_lowercase().contains(mode.as_str()),
+        Term::Name(name) => server.full_name.to_lowercase().contains(name.as_str()),
+        Term::Players(cmp, limit) => cmp.check(server.players, *limit),
+        Term::Queue(cmp, limit) => cmp.check(server.public_queue + server.reserved_queue, *limit),
+        Term::AntiCheat => server.anticheat == Some(true),
+        Term::New => firstseen::is_new(server.first_seen, sessions::now_secs()),
+        // Сервери з невідомим віком не підходять ні під age<, ні під age>
+        Term::Age(cmp, limit) => server
+            .first_seen
+            .is_some_and(|seen| cmp.check(firstseen::age_days(seen, sessions::now_secs()) as u32, *limit)),
+        Term::Evening => server.prime_time.is_some_and(|prime| prime.in_evening(primetime::local_offset())),
+    }
+}
+
+// --- РОЗУМНІ СПИСКИ ---
+
+/// Іменований вираз; вміст перераховується з останніх результатів сканування.
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct SmartList {
+    pub name: String,
+    pub expr: String,
+}
+
+impl SmartList {
+    pub fn matching<'a>(&self, servers: &[&'a ServerItem]) -> Result<Vec<&'a ServerItem>, String> {
+        let expr = Expr::parse(&self.expr)?;
+        Ok(servers.iter().copied().filter(|server| expr.matches(server)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(country: &str, map: &str, mode: &str, name: &str, players: u32, queue: u32) -> ServerItem {
+        ServerItem {
+            country: country.into(),
+            map: map.into(),
+            mode: mode.into(),
+            full_name: name.to_owned(),
+            players,
+            public_queue: queue,
+            anticheat: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn clauses_alternatives_and_negation() {
+        let expr = Expr::parse("mode:invasion, country:DE|country:fr, players>=60, queue<10, !name:seed").unwrap();
+        assert!(expr.matches(&server("FR", "Narva", "Invasion", "Best EU", 80, 2)));
+        assert!(!expr.matches(&server("FR", "Narva", "Invasion", "Best EU Seed", 80, 2)));
+        assert!(!expr.matches(&server("PL", "Narva", "Invasion", "Best EU", 80, 2)));
+        assert!(!expr.matches(&server("DE", "Narva", "RAAS", "Best EU", 80, 2)));
+        assert!(!expr.matches(&server("DE", "Narva", "Invasion", "Best EU", 59, 2)));
+        assert!(!expr.matches(&server("DE", "Narva", "Invasion", "Best EU", 60, 10)));
+        assert!(expr.matches(&server("DE", "Narva", "Invasion", "Best EU", 60, 9)));
+    }
+
+    #[test]
+    fn regions_anticheat_and_empty_expr() {
+        let asia = Expr::parse("region:Asia, anticheat").unwrap();
+        assert!(asia.matches(&server("JP", "", "", "", 0, 0)));
+        assert!(!asia.matches(&server("DE", "", "", "", 0, 0)));
+        let mut off = server("JP", "", "", "", 0, 0);
+        off.anticheat = None;
+        assert!(!asia.matches(&off));
+        // Порожній вираз підходить усім
+        assert!(Expr::parse(" , ").unwrap().matches(&server("DE", "", "", "", 0, 0)));
+    }
+
+    #[test]
+    fn unknown_age_matches_neither_side() {
+        let mut fresh = server("DE", "", "", "", 0, 0);
+        fresh.first_seen = Some(sessions::now_secs());
+        assert!(Expr::parse("new").unwrap().matches(&fresh));
+        assert!(Expr::parse("age<1").unwrap().matches(&fresh));
+        let unknown = server("DE", "", "", "", 0, 0);
+        assert!(!Expr::parse("age<1").unwrap().matches(&unknown));
+        assert!(!Expr::parse("age>=1").unwrap().matches(&unknown));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Expr::parse("players=60").unwrap_err(), "expected <, <=, > or >= after \"players\"");
+        assert_eq!(Expr::parse("queue<ten").unwrap_err(), "invalid number in \"queue<ten\"");
+        assert_eq!(Expr::parse("map:").unwrap_err(), "empty value in \"map:\"");
+        assert_eq!(Expr::parse("region:mars").unwrap_err(), "unknown region \"mars\"");
+        assert_eq!(Expr::parse("layer:narva").unwrap_err(), "unknown field \"layer\"");
+        assert_eq!(Expr::parse("full").unwrap_err(), "unknown term \"full\"");
+    }
+}
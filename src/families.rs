@@ -0,0 +1,64 @@
+// --- СІМ'Ї СЕРВЕРІВ (одна спільнота в різних регіонах) ---
+//
+// "XYZ EU #1" і "XYZ US #2" - та сама спільнота. Відкидаємо з кінця назви
+// регіон, номер і роздільники; однаковий залишок = одна сім'я.
+
+const REGION_SUFFIXES: &[&str] = &[
+    "eu", "euw", "eue", "europe", "us", "usa", "use", "usw", "na", "nae", "naw", "sa", "latam", "asia", "sea", "au",
+    "oce", "oceania", "uk", "ru", "cn", "jp", "kr", "east", "west", "central",
+];
+
+fn is_suffix_token(token: &str) -> bool {
+    let word = token.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    // Лише роздільники ("|", "-") або номер ("#1", "2", "No.3")
+    if word.is_empty() || word.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    // "EU2", "No.3"
+    let without_number = word.trim_end_matches(|c: char| c.is_ascii_digit());
+    without_number == "no" || REGION_SUFFIXES.contains(&without_number)
+}
+
+/// Назва без регіону й номера в кінці, у нижньому регістрі - спільний початок
+/// назв сім'ї, з нього ж робиться правило name~. None - відкидати нічого
+/// або від назви нічого не лишилося.
+pub fn key(name: &str) -> Option<String> {
+    let tokens: Vec<&str> = name.split_whitespace().collect();
+    let kept = tokens.iter().rposition(|token| !is_suffix_token(token)).map_or(0, |last| last + 1);
+    if kept == tokens.len() || kept == 0 {
+        return None;
+    }
+    let key = tokens[..kept].join(" ").to_lowercase();
+    let key = key.trim_end_matches(|c: char| !c.is_alphanumeric()).to_owned();
+    if key.chars().count() < 3 {
+        return None;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regional_servers_share_a_key() {
+        let names = ["XYZ Gaming EU #1", "XYZ Gaming | US #2", "xyz gaming EU-2", "XYZ Gaming No.3", "XYZ Gaming - West 4"];
+        for name in names {
+            assert_eq!(key(name).as_deref(), Some("xyz gaming"), "{}", name);
+        }
+        assert_eq!(key("[UA] Січ #1").as_deref(), Some("[ua] січ"));
+        assert_eq!(key("Tactical Gaming, EU").as_deref(), Some("tactical gaming"));
+    }
+
+    #[test]
+    fn nothing_to_drop_or_nothing_left() {
+        // Без регіону чи номера в кінці - не сім'я
+        assert_eq!(key("Squad Official Server"), None);
+        assert_eq!(key("EU #1"), None);
+        assert_eq!(key(""), None);
+        // Залишок коротший за 3 символи
+        assert_eq!(key("AB EU"), None);
+        // Регіон лише в кінці назви, а не всередині
+        assert_eq!(key("EU Vets Club 2").as_deref(), Some("eu vets club"));
+    }
+}
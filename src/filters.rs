@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::mem::discriminant;
+
+use crate::layerdb::{LayerDb, VehicleClass};
+use crate::normalize::Folded;
+use crate::ServerItem;
+
+// --- ФІЛЬТРИ ПОШУКУ ---
+//
+// Кожне обмеження - окремий Filter; у FilterSet лежать лише ті, що справді
+// щось відсікають. Відсутній фільтр = типове значення (0..100 гравців, без черги...).
+
+pub const DEFAULT_MAX_PLAYERS: u32 = 100;
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub enum Filter {
+    MinPlayers(u32),
+    MaxPlayers(u32),
+    Name(String),
+    Map(String),
+    Mode(String),
+    NoCountry(String),
+    MaxPublicQueue(u32),
+    MaxReservedQueue(u32),
+    AntiCheatOnly,
+    // Техніка на поточному шарі - за базою шарів
+    Vehicles(VehicleClass),
+    // Рядок розширеного запиту до API
+    Param(String, String),
+}
+
+impl Filter {
+    pub fn label(&self) -> String {
+        match self {
+            Filter::MinPlayers(min) => format!("min {}", min),
+            Filter::MaxPlayers(max) => format!("max {}", max),
+            Filter::Name(name) => format!("name: {}", name),
+            Filter::Map(map) => format!("map: {}", map),
+            Filter::Mode(mode) => format!("mode: {}", mode),
+            Filter::NoCountry(code) => format!("no {}", code),
+            Filter::MaxPublicQueue(max) => format!("queue ≤ {}", max),
+            Filter::MaxReservedQueue(max) => format!("reserved ≤ {}", max),
+            Filter::AntiCheatOnly => "anti-cheat".to_owned(),
+            Filter::Vehicles(VehicleClass::Helicopters) => "helicopters".to_owned(),
+            Filter::Vehicles(VehicleClass::HeavyArmor) => "armor-heavy".to_owned(),
+            Filter::Param(key, value) => format!("{}={}", key, value),
+        }
+    }
+
+    // Країн, класів техніки і параметрів може бути кілька, решта фільтрів - по одному
+    fn is_repeatable(&self) -> bool {
+        matches!(self, Filter::NoCountry(_) | Filter::Vehicles(_) | Filter::Param(..))
+    }
+
+    // Фільтр з типовим значенням нічого не відсікає - його не зберігаємо
+    fn is_noop(&self) -> bool {
+        match self {
+            Filter::MinPlayers(min) => *min == 0,
+            Filter::MaxPlayers(max) => *max >= DEFAULT_MAX_PLAYERS,
+            Filter::Name(text) | Filter::Map(text) | Filter::Mode(text) => text.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq, Debug)]
+#[serde(default)]
+pub struct FilterSet {
+    items: Vec<Filter>,
+}
+
+impl FilterSet {
+    pub fn iter(&self) -> impl Iterator<Item = &Filter> {
+        self.items.iter()
+    }
+
+    /// Додає фільтр; одиничний фільтр замінює попередній того ж виду.
+    pub fn set(&mut self, filter: Filter) {
+        if filter.is_repeatable() {
+            if !self.items.contains(&filter) {
+                self.items.push(filter);
+            }
+            return;
+        }
+        // Замінюємо на тому ж місці, щоб "крихти" не стрибали під час редагування
+        match self.items.iter().position(|f| discriminant(f) == discriminant(&filter)) {
+            Some(index) if filter.is_noop() => {
+                self.items.remove(index);
+            }
+            Some(index) => self.items[index] = filter,
+            None if filter.is_noop() => {}
+            None => self.items.push(filter),
+        }
+    }
+
+    pub fn remove(&mut self, filter: &Filter) {
+        self.items.retain(|f| f != filter);
+    }
+
+    /// Прибирає всі фільтри того ж виду, що й `filter` (значення не важливе).
+    pub fn clear_like(&mut self, filter: &Filter) {
+        self.items.retain(|f| discriminant(f) != discriminant(filter));
+    }
+
+    pub fn min_players(&self) -> u32 {
+        self.find(|f| match f {
+            Filter::MinPlayers(min) => Some(*min),
+            _ => None,
+        })
+        .unwrap_or(0)
+    }
+
+    pub fn max_players(&self) -> u32 {
+        self.find(|f| match f {
+            Filter::MaxPlayers(max) => Some(*max),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_MAX_PLAYERS)
+    }
+
+    pub fn name(&self) -> &str {
+        self.find(|f| match f {
+            Filter::Name(name) => Some(name.as_str()),
+            _ => None,
+        })
+        .unwrap_or("")
+    }
+
+    pub fn map(&self) -> &str {
+        self.find(|f| match f {
+            Filter::Map(map) => Some(map.as_str()),
+            _ => None,
+        })
+        .unwrap_or("")
+    }
+
+    pub fn mode(&self) -> &str {
+        self.find(|f| match f {
+            Filter::Mode(mode) => Some(mode.as_str()),
+            _ => None,
+        })
+        .unwrap_or("")
+    }
+
+    pub fn max_public_queue(&self) -> Option<u32> {
+        self.find(|f| match f {
+            Filter::MaxPublicQueue(max) => Some(*max),
+            _ => None,
+        })
+    }
+
+    pub fn max_reserved_queue(&self) -> Option<u32> {
+        self.find(|f| match f {
+            Filter::MaxReservedQueue(max) => Some(*max),
+            _ => None,
+        })
+    }
+
+    pub fn anticheat_only(&self) -> bool {
+        self.items.contains(&Filter::AntiCheatOnly)
+    }
+
+    pub fn wants_vehicles(&self, class: VehicleClass) -> bool {
+        self.items.contains(&Filter::Vehicles(class))
+    }
+
+    pub fn is_banned(&self, code: &str) -> bool {
+        self.items.iter().any(|f| matches!(f, Filter::NoCountry(c) if c == code))
+    }
+
+    pub fn banned_countries(&self) -> HashSet<String> {
+        self.items
+            .iter()
+            .filter_map(|f| match f {
+                Filter::NoCountry(code) => Some(code.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn params(&self) -> Vec<(String, String)> {
+        self.items
+            .iter()
+            .filter_map(|f| match f {
+                Filter::Param(key, value) => Some((key.clone(), value.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Замінює всі рядки розширеного запиту (включно з недописаними).
+    pub fn set_params(&mut self, params: Vec<(String, String)>) {
+        self.items.retain(|f| !matches!(f, Filter::Param(..)));
+        self.items.extend(params.into_iter().map(|(key, value)| Filter::Param(key, value)));
+    }
+
+    /// Фільтри, які перевіряються локально після відповіді API. Гравці йдуть
+    /// у запит, країни - разом з чорним списком, параметри - в API.
+    /// Шар, якого немає в базі, фільтр техніки не проходить - підтвердити нічим.
+    pub fn matches(&self, server: &ServerItem, layers: &LayerDb) -> bool {
+        self.items.iter().all(|filter| match filter {
+            Filter::Name(name) => Folded::new(&server.full_name).contains(name),
+            Filter::Map(map) => server.map.to_lowercase().contains(&map.to_lowercase()),
+            Filter::Mode(mode) => server.mode.to_lowercase().contains(&mode.to_lowercase()),
+            Filter::MaxPublicQueue(max) => server.public_queue <= *max,
+            Filter::MaxReservedQueue(max) => server.reserved_queue <= *max,
+            Filter::AntiCheatOnly => server.anticheat != Some(false),
+            Filter::Vehicles(class) => layers.get(&server.map).is_some_and(|info| info.has(*class)),
+            _ => true,
+        })
+    }
+
+    fn find<'a, T>(&'a self, pick: impl Fn(&'a Filter) -> Option<T>) -> Option<T> {
+        self.items.iter().find_map(pick)
+    }
+}
+
+// --- СТАРИЙ ФОРМАТ (до версії конфігу 2) ---
+
+/// Окремі поля фільтрів, як їх зберігали раніше. Лише для міграції.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct LegacyFilters {
+    pub min_players: u32,
+    pub max_players: u32,
+    pub banned_countries: HashSet<String>,
+    pub filter_name: String,
+    pub filter_map: String,
+    pub filter_mode: String,
+    pub max_public_queue: Option<u32>,
+    pub max_reserved_queue: Option<u32>,
+    pub hide_no_anticheat: bool,
+    pub advanced_params: Vec<(String, String)>,
+}
+
+impl Default for LegacyFilters {
+    fn default() -> Self {
+        Self {
+            min_players: 0,
+            max_players: DEFAULT_MAX_PLAYERS,
+            banned_countries: HashSet::new(),
+            filter_name: String::new(),
+            filter_map: String::new(),
+            filter_mode: String::new(),
+            max_public_queue: None,
+            max_reserved_queue: None,
+            hide_no_anticheat: false,
+            advanced_params: Vec::new(),
+        }
+    }
+}
+
+impl From<LegacyFilters> for FilterSet {
+    fn from(old: LegacyFilters) -> Self {
+        let mut set = FilterSet::default();
+        // Конфіги v0 могли зберегти мінімум, більший за максимум
+        set.set(Filter::MinPlayers(old.min_players.min(old.max_players)));
+        set.set(Filter::MaxPlayers(old.min_players.max(old.max_players)));
+        set.set(Filter::Name(old.filter_name));
+        set.set(Filter::Map(old.filter_map));
+        set.set(Filter::Mode(old.filter_mode));
+        let mut countries: Vec<String> = old.banned_countries.into_iter().collect();
+        countries.sort();
+        for code in countries {
+            set.set(Filter::NoCountry(code));
+        }
+        if let Some(max) = old.max_public_queue {
+            set.set(Filter::MaxPublicQueue(max));
+        }
+        if let Some(max) = old.max_reserved_queue {
+            set.set(Filter::MaxReservedQueue(max));
+        }
+        if old.hide_no_anticheat {
+            set.set(Filter::AntiCheatOnly);
+        }
+        set.set_params(old.advanced_params);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> ServerItem {
+        ServerItem {
+            full_name: "[EU] Tactical Gaming".into(),
+            map: "Narva_RAAS_v1".into(),
+            mode: "RAAS".into(),
+            public_queue: 3,
+            reserved_queue: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn set_replaces_in_place_and_drops_defaults() {
+        let mut set = FilterSet::default();
+        set.set(Filter::MinPlayers(20));
+        set.set(Filter::Map("narva".into()));
+        set.set(Filter::MinPlayers(40));
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![Filter::MinPlayers(40), Filter::Map("narva".into())]);
+        set.set(Filter::MinPlayers(0));
+        set.set(Filter::MaxPlayers(DEFAULT_MAX_PLAYERS));
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![Filter::Map("narva".into())]);
+        set.set(Filter::NoCountry("RU".into()));
+        set.set(Filter::NoCountry("CN".into()));
+        set.set(Filter::NoCountry("RU".into()));
+        assert_eq!(set.banned_countries().len(), 2);
+        assert_eq!((set.min_players(), set.max_players()), (0, DEFAULT_MAX_PLAYERS));
+    }
+
+    #[test]
+    fn matches_local_filters() {
+        let layers = LayerDb::default();
+        let check = |filter: Filter| {
+            let mut set = FilterSet::default();
+            set.set(filter);
+            set.matches(&server(), &layers)
+        };
+        assert!(check(Filter::Name("tactical".into())));
+        // Назва порівнюється після нормалізації (normalize.rs)
+        assert!(check(Filter::Name("TACT1CAL".into())));
+        assert!(!check(Filter::Name("seed".into())));
+        assert!(check(Filter::Map("NARVA".into())));
+        assert!(!check(Filter::Map("gorodok".into())));
+        assert!(check(Filter::Mode("raas".into())));
+        assert!(check(Filter::MaxPublicQueue(3)));
+        assert!(!check(Filter::MaxPublicQueue(2)));
+        assert!(!check(Filter::MaxReservedQueue(0)));
+        // Невідомий стан античиту не відсікається, вимкнений - так
+        assert!(check(Filter::AntiCheatOnly));
+        let mut off = server();
+        off.anticheat = Some(false);
+        let mut set = FilterSet::default();
+        set.set(Filter::AntiCheatOnly);
+        assert!(!set.matches(&off, &layers));
+        // Шару немає в базі - фільтр техніки не проходить
+        assert!(!check(Filter::Vehicles(VehicleClass::Helicopters)));
+        // Гравці й країни йдуть у запит до API, тут їх не перевіряємо
+        assert!(check(Filter::MinPlayers(99)));
+        assert!(check(Filter::NoCountry(String::new())));
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// --- КОЛИ СЕРВЕР З'ЯВИВСЯ ВПЕРШЕ ---
+//
+// Час першої появи кожного id у скануваннях. Сервери з першої години
+// спостережень були в списку й до нас, тож їхній вік невідомий (None),
+// інакше після оновлення програми всі сервери разом стали б "новими".
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+const BASELINE_SECS: u64 = 60 * 60;
+// Скільки днів сервер вважається новим (значок і терм `new`)
+pub const NEW_DAYS: u64 = 7;
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct FirstSeenLog {
+    // Початок спостережень, секунди від UNIX epoch; 0 - ще не почались
+    started: u64,
+    servers: HashMap<String, u64>,
+}
+
+impl FirstSeenLog {
+    pub fn record(&mut self, id: &str, now: u64) {
+        if self.started == 0 {
+            self.started = now;
+        }
+        self.servers.entry(id.to_owned()).or_insert(now);
+    }
+
+    pub fn first_seen(&self, id: &str) -> Option<u64> {
+        self.servers.get(id).copied().filter(|&seen| seen >= self.started + BASELINE_SECS)
+    }
+}
+
+/// Повних днів від першої появи.
+pub fn age_days(first_seen: u64, now: u64) -> u64 {
+    now.saturating_sub(first_seen) / DAY_SECS
+}
+
+pub fn is_new(first_seen: Option<u64>, now: u64) -> bool {
+    first_seen.is_some_and(|seen| age_days(seen, now) < NEW_DAYS)
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+// --- ІСТОРІЯ ОНЛАЙНУ (в пам'яті, з кожного оновлення) ---
+
+const KEEP_FOR: Duration = Duration::from_secs(60 * 60);
+const TREND_WINDOW: Duration = Duration::from_secs(10 * 60);
+// Гравців за хвилину, нижче якого вважаємо онлайн стабільним
+const TREND_THRESHOLD: f32 = 0.3;
+// Скільки останніх карт пам'ятати для кожного сервера
+const RECENT_MAPS: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Stable,
+}
+
+struct Sample {
+    at: Instant,
+    players: u32,
+}
+
+#[derive(Default)]
+pub struct History {
+    samples: HashMap<String, VecDeque<Sample>>,
+    // Для всіх серверів, а не лише закріплених (ті мають RotationLog)
+    maps: HashMap<String, VecDeque<String>>,
+}
+
+impl History {
+    pub fn record(&mut self, id: &str, players: u32) {
+        self.record_at(id, players, Instant::now());
+    }
+
+    fn record_at(&mut self, id: &str, players: u32, now: Instant) {
+        let samples = self.samples.entry(id.to_string()).or_default();
+
+        // Та сама сторінка могла прийти двічі поспіль - лишаємо свіжіший семпл
+        if samples.back().is_some_and(|last| now.duration_since(last.at) < Duration::from_secs(5)) {
+            samples.pop_back();
+        }
+        samples.push_back(Sample { at: now, players });
+
+        while samples.front().is_some_and(|s| now.duration_since(s.at) > KEEP_FOR) {
+            samples.pop_front();
+        }
+    }
+
+    pub fn record_map(&mut self, id: &str, map: &str) {
+        let maps = self.maps.entry(id.to_string()).or_default();
+        if maps.back().is_some_and(|last| last == map) {
+            return;
+        }
+        maps.push_back(map.to_string());
+        if maps.len() > RECENT_MAPS {
+            maps.pop_front();
+        }
+    }
+
+    /// Останні карти сервера, від старих до поточної.
+    pub fn recent_maps(&self, id: &str) -> Vec<String> {
+        self.maps.get(id).map(|maps| maps.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Швидкість зміни онлайну (гравців за хвилину) за останні 10 хвилин.
+    /// None, якщо семпли охоплюють менше хвилини.
+    pub fn fill_rate(&self, id: &str) -> Option<f32> {
+        let samples = self.samples.get(id)?;
+        let last = samples.back()?;
+        let first = samples
+            .iter()
+            .find(|s| last.at.duration_since(s.at) <= TREND_WINDOW)?;
+
+        let minutes = last.at.duration_since(first.at).as_secs_f32() / 60.0;
+        if minutes < 1.0 {
+            return None;
+        }
+        Some((last.players as f32 - first.players as f32) / minutes)
+    }
+
+    /// Прогноз, через скільки хвилин сервер дійде до `target` гравців
+    /// при поточній швидкості заповнення.
+    pub fn minutes_until(&self, id: &str, players: u32, target: u32) -> Option<f32> {
+        if players >= target {
+            return None;
+        }
+        let rate = self.fill_rate(id)?;
+        if rate <= 0.0 {
+            return None;
+        }
+        Some((target - players) as f32 / rate)
+    }
+
+    pub fn trend(&self, id: &str) -> Option<Trend> {
+        let rate = self.fill_rate(id)?;
+        Some(if rate > TREND_THRESHOLD {
+            Trend::Rising
+        } else if rate < -TREND_THRESHOLD {
+            Trend::Falling
+        } else {
+            Trend::Stable
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(m: u64) -> Duration {
+        Duration::from_secs(m * 60)
+    }
+
+    #[test]
+    fn rate_trend_and_eta() {
+        let start = Instant::now();
+        let mut history = History::default();
+        history.record_at("1", 40, start);
+        assert_eq!(history.fill_rate("1"), None);
+        history.record_at("1", 50, start + minutes(5));
+        assert_eq!(history.fill_rate("1"), Some(2.0));
+        assert!(history.trend("1") == Some(Trend::Rising));
+        assert_eq!(history.minutes_until("1", 50, 90), Some(20.0));
+        assert_eq!(history.minutes_until("1", 95, 90), None);
+
+        // Старіші за TREND_WINDOW семпли в швидкість не йдуть
+        history.record_at("1", 50, start + minutes(20));
+        assert_eq!(history.fill_rate("1"), None);
+        history.record_at("1", 40, start + minutes(25));
+        assert_eq!(history.fill_rate("1"), Some(-2.0));
+        assert!(history.trend("1") == Some(Trend::Falling));
+        assert_eq!(history.minutes_until("1", 40, 90), None);
+    }
+
+    #[test]
+    fn repeated_page_replaces_last_sample() {
+        let start = Instant::now();
+        let mut history = History::default();
+        history.record_at("1", 40, start);
+        history.record_at("1", 41, start + minutes(2));
+        history.record_at("1", 60, start + minutes(2) + Duration::from_secs(1));
+        assert_eq!(history.samples["1"].len(), 2);
+        assert_eq!(history.samples["1"].back().map(|s| s.players), Some(60));
+    }
+
+    #[test]
+    fn keeps_last_distinct_maps() {
+        let mut history = History::default();
+        for map in ["Narva", "Narva", "Gorodok", "Yehorivka", "Mutaha"] {
+            history.record_map("1", map);
+        }
+        assert_eq!(history.recent_maps("1"), vec!["Gorodok", "Yehorivka", "Mutaha"]);
+        assert!(history.recent_maps("2").is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Deserializer};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// --- ІНТЕРНУВАННЯ РЯДКІВ ---
+//
+// Карт, режимів і країн - десятки, а серверів у глибокому скані - тисячі.
+// Кожне унікальне значення зберігається один раз, сервери тримають Arc на нього.
+
+static STRINGS: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+
+pub fn intern(value: &str) -> Arc<str> {
+    let strings = STRINGS.get_or_init(Default::default);
+    let mut strings = match strings.lock() {
+        Ok(strings) => strings,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some(existing) = strings.get(value) {
+        return existing.clone();
+    }
+    let interned: Arc<str> = Arc::from(value);
+    strings.insert(interned.clone());
+    interned
+}
+
+/// Для `#[serde(deserialize_with)]`: збережені сервери теж ділять спільні рядки.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<str>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    Ok(intern(&value))
+}
@@ -0,0 +1,140 @@
+use eframe::egui;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::filters::Filter;
+use crate::layerdb::VehicleClass;
+
+// --- КЕРУВАННЯ ЗАПУЩЕНИМ ДОДАТКОМ (squad-browser-ctl) ---
+//
+// Локальний TCP-сокет лише на 127.0.0.1: однаково працює на Windows і Linux,
+// тож кнопки Stream Deck і скрипти можуть керувати вікном. Протокол - один
+// рядок команди на з'єднання, у відповідь один рядок "OK ..." або "ERR ...".
+// Порт має збігатися з src/bin/squad-browser-ctl.rs.
+
+const DEFAULT_PORT: u16 = 47815;
+const PORT_ENV: &str = "SQUAD_CTL_PORT";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const HELP: &str = "commands: refresh | refresh-all | join <id> | set-filter <key=value>... | \
+clear-filter <key|all> | tab <n> | search <name> | open <squadbrowser://...> | status; \
+filter keys: name, map, mode, min, max, queue, reserved, anticheat=on|off, no-country=XX, vehicles=helicopters|armor";
+
+pub fn port() -> u16 {
+    std::env::var(PORT_ENV).ok().and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT)
+}
+
+/// Один рядок уже запущеному вікну - так друга копія передає посилання першій.
+pub fn send(line: &str) -> std::io::Result<String> {
+    let stream = TcpStream::connect_timeout(&(std::net::Ipv4Addr::LOCALHOST, port()).into(), TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    writeln!(&stream, "{}", line)?;
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    Ok(reply.trim_end().to_owned())
+}
+
+/// Команда з CLI; відповідь треба надіслати через `reply`.
+pub struct Request {
+    pub command: String,
+    pub args: Vec<String>,
+    reply: Sender<String>,
+}
+
+impl Request {
+    pub fn reply(self, text: String) {
+        let _ = self.reply.send(text);
+    }
+}
+
+pub struct ControlServer {
+    rx: Receiver<Request>,
+}
+
+impl ControlServer {
+    pub fn start(ctx: egui::Context) -> Option<Self> {
+        let listener = match TcpListener::bind(("127.0.0.1", port())) {
+            Ok(listener) => listener,
+            Err(e) => {
+                // Найчастіше порт зайнятий іншою копією програми
+                println!("[ERR] Control socket unavailable on port {}: {}", port(), e);
+                return None;
+            }
+        };
+        println!("[LOG] Listening for squad-browser-ctl on 127.0.0.1:{}", port());
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let tx = tx.clone();
+                let ctx = ctx.clone();
+                thread::spawn(move || {
+                    if let Err(e) = serve(stream, &tx, &ctx) {
+                        println!("[ERR] Control connection failed: {}", e);
+                    }
+                });
+            }
+        });
+        Some(Self { rx })
+    }
+
+    pub fn try_recv(&self) -> Option<Request> {
+        self.rx.try_recv().ok()
+    }
+}
+
+fn serve(stream: TcpStream, tx: &Sender<Request>, ctx: &egui::Context) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let mut words = line.split_whitespace().map(str::to_owned);
+    let command = words.next().unwrap_or_default();
+
+    let (reply_tx, reply_rx) = channel();
+    let answer = if tx.send(Request { command, args: words.collect(), reply: reply_tx }).is_ok() {
+        ctx.request_repaint();
+        reply_rx.recv_timeout(TIMEOUT).unwrap_or_else(|_| "ERR the app did not answer".to_owned())
+    } else {
+        "ERR the app is shutting down".to_owned()
+    };
+    writeln!(&stream, "{}", answer)
+}
+
+/// Слова без "=" належать значенню попередньої пари: "name=Foo Bar".
+pub fn filter_pairs(args: &[String]) -> Vec<String> {
+    let mut pairs: Vec<String> = Vec::new();
+    for word in args {
+        match pairs.last_mut() {
+            Some(last) if !word.contains('=') => {
+                last.push(' ');
+                last.push_str(word);
+            }
+            _ => pairs.push(word.clone()),
+        }
+    }
+    pairs
+}
+
+/// "map=narva" -> (фільтр, чи вмикати). Порожнє значення теж прибирає фільтр (FilterSet::set).
+pub fn parse_filter(pair: &str) -> Result<(Filter, bool), String> {
+    let (key, value) = pair.split_once('=').ok_or_else(|| format!("expected key=value, got \"{}\"", pair))?;
+    let number = || value.parse::<u32>().map_err(|_| format!("{}: expected a number", key));
+    let filter = match key {
+        "name" => Filter::Name(value.to_owned()),
+        "map" => Filter::Map(value.to_owned()),
+        "mode" => Filter::Mode(value.to_owned()),
+        "min" => Filter::MinPlayers(number()?),
+        "max" => Filter::MaxPlayers(number()?),
+        "queue" => Filter::MaxPublicQueue(number()?),
+        "reserved" => Filter::MaxReservedQueue(number()?),
+        "anticheat" => Filter::AntiCheatOnly,
+        "no-country" => Filter::NoCountry(value.to_uppercase()),
+        "vehicles" => Filter::Vehicles(
+            VehicleClass::parse(value).ok_or_else(|| format!("vehicles: expected helicopters or armor, got \"{}\"", value))?,
+        ),
+        other => return Err(format!("unknown filter \"{}\"", other)),
+    };
+    Ok((filter, key != "anticheat" || !matches!(value, "off" | "false" | "0")))
+}
@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+// --- РЕЖИМ КІОСКУ (--kiosk) ---
+//
+// Інфоекран для LAN-вечірки: на весь екран, сам оновлюється, налаштувань
+// немає і нічого не зберігається. Поки ніхто не чіпає мишу й клавіатуру,
+// по черзі показуємо закріплені сервери великими картками.
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const IDLE_AFTER: Duration = Duration::from_secs(30);
+const SLIDE_DURATION: Duration = Duration::from_secs(8);
+
+pub struct Kiosk {
+    last_refresh: Option<Instant>,
+    last_input: Instant,
+    slide: usize,
+    slide_started: Instant,
+}
+
+impl Default for Kiosk {
+    fn default() -> Self {
+        Self { last_refresh: None, last_input: Instant::now(), slide: 0, slide_started: Instant::now() }
+    }
+}
+
+impl Kiosk {
+    pub fn refresh_due(&self) -> bool {
+        self.last_refresh.is_none_or(|at| at.elapsed() >= REFRESH_INTERVAL)
+    }
+
+    pub fn refreshed(&mut self) {
+        self.last_refresh = Some(Instant::now());
+    }
+
+    /// Будь-який ввід повертає зі слайдшоу до списку.
+    pub fn touched(&mut self) {
+        self.last_input = Instant::now();
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.last_input.elapsed() >= IDLE_AFTER
+    }
+
+    /// Номер поточного слайда з `count`; перемикається кожні SLIDE_DURATION.
+    pub fn slide(&mut self, count: usize) -> usize {
+        if self.slide_started.elapsed() >= SLIDE_DURATION {
+            self.slide += 1;
+            self.slide_started = Instant::now();
+        }
+        if count == 0 { 0 } else { self.slide % count }
+    }
+}
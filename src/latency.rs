@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+// --- ІСТОРІЯ ПІНГУ ОБРАНИХ СЕРВЕРІВ ---
+//
+// Останні WINDOW замірів A2S на кожен закріплений сервер (None - відповідь
+// не прийшла). Джитер - середня різниця між сусідніми замірами: він
+// показує нестабільність каналу краще, ніж один середній пінг.
+
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+pub const WINDOW: usize = 60;
+
+pub struct PingStats {
+    pub average: u32,
+    pub min: u32,
+    pub max: u32,
+    pub jitter: u32,
+    pub loss_percent: f32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct PingLog {
+    // ID сервера -> заміри в мілісекундах, від старих до нових
+    servers: HashMap<String, VecDeque<Option<u32>>>,
+}
+
+impl PingLog {
+    pub fn record(&mut self, id: &str, ms: Option<u32>) {
+        let samples = self.servers.entry(id.to_owned()).or_default();
+        samples.push_back(ms);
+        while samples.len() > WINDOW {
+            samples.pop_front();
+        }
+    }
+
+    /// Історію відкріплених серверів не тримаємо.
+    pub fn retain(&mut self, ids: &[String]) {
+        self.servers.retain(|id, _| ids.contains(id));
+    }
+
+    pub fn samples(&self, id: &str) -> Vec<Option<u32>> {
+        self.servers.get(id).map(|samples| samples.iter().copied().collect()).unwrap_or_default()
+    }
+
+    pub fn stats(&self, id: &str) -> Option<PingStats> {
+        let samples = self.servers.get(id)?;
+        let answered: Vec<u32> = samples.iter().flatten().copied().collect();
+        if answered.is_empty() {
+            return None;
+        }
+        let jitter = if answered.len() > 1 {
+            let total: u32 = answered.windows(2).map(|pair| pair[0].abs_diff(pair[1])).sum();
+            total / (answered.len() as u32 - 1)
+        } else {
+            0
+        };
+        Some(PingStats {
+            average: answered.iter().sum::<u32>() / answered.len() as u32,
+            min: answered.iter().copied().min().unwrap_or(0),
+            max: answered.iter().copied().max().unwrap_or(0),
+            jitter,
+            loss_percent: (samples.len() - answered.len()) as f32 * 100.0 / samples.len() as f32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_and_loss() {
+        let mut log = PingLog::default();
+        for ms in [Some(40), None, Some(60), Some(50), None] {
+            log.record("1", ms);
+        }
+        let stats = log.stats("1").unwrap();
+        assert_eq!((stats.average, stats.min, stats.max), (50, 40, 60));
+        // Різниці між відповідями, що прийшли: |40-60| і |60-50|
+        assert_eq!(stats.jitter, 15);
+        assert_eq!(stats.loss_percent, 40.0);
+    }
+
+    #[test]
+    fn no_answers_or_single_answer() {
+        let mut log = PingLog::default();
+        assert!(log.stats("1").is_none());
+        log.record("1", None);
+        assert!(log.stats("1").is_none());
+        log.record("1", Some(30));
+        let stats = log.stats("1").unwrap();
+        assert_eq!((stats.jitter, stats.loss_percent), (0, 50.0));
+    }
+
+    #[test]
+    fn window_and_retain() {
+        let mut log = PingLog::default();
+        for ms in 0..WINDOW as u32 + 5 {
+            log.record("1", Some(ms));
+        }
+        log.record("2", Some(10));
+        let samples = log.samples("1");
+        assert_eq!(samples.len(), WINDOW);
+        assert_eq!(samples[0], Some(5));
+        log.retain(&["2".to_owned()]);
+        assert!(log.samples("1").is_empty());
+        assert_eq!(log.samples("2"), vec![Some(10)]);
+    }
+}
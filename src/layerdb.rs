@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// --- БАЗА ШАРІВ (розмір карти, техніка, розташування прапорів) ---
+//
+// Вбудований assets/layers.json - стартовий набір. Оновлення з URL
+// зберігається поруч із конфігом (layers.json) і має перевагу над вбудованим.
+// Ключ - назва шару, як її віддає BattleMetrics ("Narva_RAAS_v2").
+
+const EMBEDDED: &str = include_str!("../assets/layers.json");
+pub const FILE_NAME: &str = "layers.json";
+
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Flag {
+    pub name: String,
+    // Положення на карті, 0..1 від лівого верхнього кута
+    pub x: f32,
+    pub y: f32,
+}
+
+// --- КЛАСИ ТЕХНІКИ ---
+//
+// У базі техніка - вільний текст ("2x M1A2 Abrams"), тож клас визначаємо за
+// назвою. Важка броня - танки й БМП; БТР і легкі машини не рахуються.
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum VehicleClass {
+    Helicopters,
+    HeavyArmor,
+}
+
+// Шар "бронетанковий", якщо важкої броні в обох командах разом не менше
+pub const HEAVY_ARMOR_MIN: u32 = 4;
+
+const HELICOPTERS: [&str; 14] =
+    ["heli", "uh-60", "mh-60", "uh-1", "mi-8", "mi-17", "ch-146", "mrh-90", "mrh90", "z-8", "z-9", "sa330", "loach", "mh-6"];
+const HEAVY_ARMOR: [&str; 16] = [
+    "abrams", "t-62", "t-72", "t-90", "leopard", "challenger", "ztz", "bradley", "bmp", "bmd", "warrior", "lav-6", "ascod",
+    "zbd", "fv510", "fv4034",
+];
+
+impl VehicleClass {
+    pub const ALL: [VehicleClass; 2] = [VehicleClass::Helicopters, VehicleClass::HeavyArmor];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            VehicleClass::Helicopters => "helicopters",
+            VehicleClass::HeavyArmor => "armor",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| class.key().eq_ignore_ascii_case(text.trim()))
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            VehicleClass::Helicopters => &HELICOPTERS,
+            VehicleClass::HeavyArmor => &HEAVY_ARMOR,
+        }
+    }
+
+    fn includes(self, vehicle: &str) -> bool {
+        let vehicle = vehicle.to_lowercase();
+        self.keywords().iter().any(|keyword| vehicle.contains(keyword))
+    }
+}
+
+/// "2x M1A2 Abrams" -> (2, "M1A2 Abrams"); без префікса - одна машина.
+fn split_count(vehicle: &str) -> (u32, &str) {
+    let vehicle = vehicle.trim();
+    match vehicle.split_once(' ') {
+        Some((count, name)) if count.len() > 1 && count.ends_with(['x', 'X']) => {
+            match count[..count.len() - 1].parse() {
+                Ok(count) => (count, name.trim()),
+                Err(_) => (1, vehicle),
+            }
+        }
+        _ => (1, vehicle),
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct LayerInfo {
+    pub layer: String,
+    pub map: String,
+    pub mode: String,
+    pub size_km: f32,
+    pub team1: Vec<String>,
+    pub team2: Vec<String>,
+    pub flags: Vec<Flag>,
+}
+
+impl LayerInfo {
+    /// Кількість машин класу в обох командах.
+    pub fn vehicles(&self, class: VehicleClass) -> u32 {
+        self.team1
+            .iter()
+            .chain(&self.team2)
+            .map(|vehicle| split_count(vehicle))
+            .filter(|(_, name)| class.includes(name))
+            .map(|(count, _)| count)
+            .sum()
+    }
+
+    pub fn has(&self, class: VehicleClass) -> bool {
+        match class {
+            VehicleClass::Helicopters => self.vehicles(class) > 0,
+            VehicleClass::HeavyArmor => self.vehicles(class) >= HEAVY_ARMOR_MIN,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct File {
+    version: u32,
+    layers: Vec<LayerInfo>,
+}
+
+#[derive(Default, Debug)]
+pub struct LayerDb {
+    pub version: u32,
+    // true - завантажена копія, false - вбудована
+    pub downloaded: bool,
+    layers: HashMap<String, LayerInfo>,
+}
+
+impl LayerDb {
+    pub fn embedded() -> Self {
+        parse(EMBEDDED).unwrap_or_default()
+    }
+
+    /// Завантажена копія, якщо вона є і читається, інакше вбудована.
+    pub fn load(path: Option<&Path>) -> Self {
+        let downloaded = path.and_then(|path| fs::read_to_string(path).ok()).map(|raw| parse(&raw));
+        match downloaded {
+            Some(Ok(mut db)) => {
+                db.downloaded = true;
+                db
+            }
+            Some(Err(e)) => {
+                println!("[ERR] Ignoring downloaded layer database: {}", e);
+                Self::embedded()
+            }
+            None => Self::embedded(),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn get(&self, layer: &str) -> Option<&LayerInfo> {
+        self.layers.get(&layer.trim().to_lowercase())
+    }
+}
+
+fn parse(raw: &str) -> Result<LayerDb, String> {
+    let file: File = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+    let layers: HashMap<String, LayerInfo> = file
+        .layers
+        .into_iter()
+        .filter(|info| !info.layer.trim().is_empty())
+        .map(|info| (info.layer.trim().to_lowercase(), info))
+        .collect();
+    if layers.is_empty() {
+        return Err("no layers in the file".to_owned());
+    }
+    Ok(LayerDb { version: file.version, downloaded: false, layers })
+}
+
+/// Завантажує базу, перевіряє її і лише тоді зберігає у `path`.
+pub fn update(url: &str, path: &Path) -> Result<LayerDb, String> {
+    let raw = reqwest::blocking::get(url)
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.text())
+        .map_err(|e| e.to_string())?;
+    let mut db = parse(&raw)?;
+    fs::write(path, raw).map_err(|e| e.to_string())?;
+    db.downloaded = true;
+    Ok(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(team1: &[&str], team2: &[&str]) -> LayerInfo {
+        LayerInfo {
+            team1: team1.iter().map(|v| v.to_string()).collect(),
+            team2: team2.iter().map(|v| v.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counts_vehicles_by_class() {
+        assert_eq!(split_count("2x M1A2 Abrams"), (2, "M1A2 Abrams"));
+        assert_eq!(split_count("MRAP"), (1, "MRAP"));
+        assert_eq!(split_count("xx Heli"), (1, "xx Heli"));
+        let layer = info(&["2x M1A2 Abrams", "1x UH-60M", "4x Logistics truck"], &["1x T-72B3", "2x BMP-2"]);
+        assert_eq!(layer.vehicles(VehicleClass::HeavyArmor), 5);
+        assert_eq!(layer.vehicles(VehicleClass::Helicopters), 1);
+        assert!(layer.has(VehicleClass::HeavyArmor));
+        // Менше HEAVY_ARMOR_MIN машин - шар не бронетанковий
+        assert!(!info(&["1x M1A2 Abrams"], &["2x BTR-82A", "1x BMP-1"]).has(VehicleClass::HeavyArmor));
+        assert!(!info(&["2x MRAP"], &[]).has(VehicleClass::Helicopters));
+    }
+
+    #[test]
+    fn parses_and_looks_up_case_insensitively() {
+        let db = parse(r#"{"version": 3, "layers": [{"layer": " Narva_RAAS_v1 ", "size_km": 3.0}, {"layer": ""}]}"#)
+            .unwrap();
+        assert_eq!((db.version, db.count()), (3, 1));
+        assert!(db.get("narva_raas_v1").is_some());
+        assert!(db.get("Gorodok_RAAS_v1").is_none());
+        assert_eq!(parse(r#"{"layers": []}"#).unwrap_err(), "no layers in the file");
+        assert!(parse("not json").is_err());
+        assert!(LayerDb::embedded().count() > 0);
+        assert_eq!(VehicleClass::parse(" Armor "), Some(VehicleClass::HeavyArmor));
+    }
+}
@@ -0,0 +1,107 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+// --- ВИГЛЯД ВІКНА І ЗБЕРЕЖЕНІ МАКЕТИ ---
+//
+// Щільність списку і видимі частини рядка сервера. Самі макети (розділ,
+// ширина бокової панелі, відкриті панелі) зберігає SquadApp, бо розділи
+// оголошені там.
+
+pub const SIDEBAR_WIDTH: f32 = 160.0;
+pub const SIDEBAR_RANGE: std::ops::RangeInclusive<f32> = 120.0..=320.0;
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl Density {
+    /// Comfortable - стандартні відступи egui.
+    pub fn apply(self, style: &mut egui::Style) {
+        let (item_spacing, button_padding) = match self {
+            Density::Comfortable => (egui::vec2(8.0, 3.0), egui::vec2(4.0, 1.0)),
+            Density::Compact => (egui::vec2(6.0, 1.0), egui::vec2(3.0, 0.0)),
+        };
+        style.spacing.item_spacing = item_spacing;
+        style.spacing.button_padding = button_padding;
+    }
+}
+
+/// Що показувати в рядку сервера, крім назви і кількості гравців.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(default)]
+pub struct RowColumns {
+    pub country: bool,
+    // Місто поруч із кодом країни ("Frankfurt, DE"), якщо відоме
+    pub city: bool,
+    pub map_mode: bool,
+    pub queue: bool,
+    pub trend: bool,
+    pub delta: bool,
+    pub seed_eta: bool,
+    // Значок "топ N% за онлайном" (ranking)
+    pub rank: bool,
+}
+
+impl Default for RowColumns {
+    fn default() -> Self {
+        Self { country: true, city: true, map_mode: true, queue: true, trend: true, delta: true, seed_eta: true, rank: true }
+    }
+}
+
+impl RowColumns {
+    pub fn toggles(&mut self) -> [(&'static str, &mut bool); 8] {
+        [
+            ("column_country", &mut self.country),
+            ("column_city", &mut self.city),
+            ("column_map_mode", &mut self.map_mode),
+            ("column_queue", &mut self.queue),
+            ("column_trend", &mut self.trend),
+            ("column_delta", &mut self.delta),
+            ("column_seed_eta", &mut self.seed_eta),
+            ("column_rank", &mut self.rank),
+        ]
+    }
+}
+
+/// Кнопки швидких дій праворуч у рядку сервера; набір і порядок - у налаштуваннях.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum QuickAction {
+    Join,
+    Favorite,
+    Hide,
+    CopyAddress,
+    CopyName,
+}
+
+impl QuickAction {
+    pub const ALL: [QuickAction; 5] =
+        [QuickAction::Join, QuickAction::Favorite, QuickAction::Hide, QuickAction::CopyAddress, QuickAction::CopyName];
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            QuickAction::Join => "▶",
+            QuickAction::Favorite => "📌",
+            QuickAction::Hide => "🚫",
+            QuickAction::CopyAddress => "📋",
+            QuickAction::CopyName => "🔤",
+        }
+    }
+
+    /// Ключ перекладу для підказки і налаштувань.
+    pub fn key(self) -> &'static str {
+        match self {
+            QuickAction::Join => "quick_join",
+            QuickAction::Favorite => "quick_favorite",
+            QuickAction::Hide => "quick_hide",
+            QuickAction::CopyAddress => "quick_copy_address",
+            QuickAction::CopyName => "quick_copy_name",
+        }
+    }
+}
+
+pub fn default_quick_actions() -> Vec<QuickAction> {
+    vec![QuickAction::Join, QuickAction::Favorite, QuickAction::Hide, QuickAction::CopyAddress]
+}
@@ -0,0 +1,134 @@
+// --- МІСТО СЕРВЕРА І ВІДСТАНЬ ---
+//
+// BattleMetrics віддає location = [довгота, широта] і зрідка назву міста.
+// Без міста беремо найближче відоме місто з таблиці дата-центрів нижче;
+// без координат - координати названого міста. Відстань - по великому колу
+// від міста, яке гравець вибрав у налаштуваннях; для пінгу це лише оцінка.
+
+pub struct City {
+    pub name: &'static str,
+    pub country: &'static str,
+    pub lat: f32,
+    pub lon: f32,
+}
+
+const fn city(name: &'static str, country: &'static str, lat: f32, lon: f32) -> City {
+    City { name, country, lat, lon }
+}
+
+// Міста, де зазвичай стоять ігрові сервери, плюс кілька столиць для вибору "свого" міста
+pub const CITIES: [City; 49] = [
+    city("Amsterdam", "NL", 52.37, 4.90),
+    city("Ashburn", "US", 39.04, -77.49),
+    city("Atlanta", "US", 33.75, -84.39),
+    city("Auckland", "NZ", -36.85, 174.76),
+    city("Beauharnois", "CA", 45.31, -73.87),
+    city("Beijing", "CN", 39.90, 116.40),
+    city("Berlin", "DE", 52.52, 13.40),
+    city("Chicago", "US", 41.88, -87.63),
+    city("Copenhagen", "DK", 55.68, 12.57),
+    city("Dallas", "US", 32.78, -96.80),
+    city("Denver", "US", 39.74, -104.99),
+    city("Dubai", "AE", 25.20, 55.27),
+    city("Falkenstein", "DE", 50.48, 12.37),
+    city("Frankfurt", "DE", 50.11, 8.68),
+    city("Gravelines", "FR", 50.99, 2.13),
+    city("Helsinki", "FI", 60.17, 24.94),
+    city("Hong Kong", "HK", 22.32, 114.17),
+    city("Istanbul", "TR", 41.01, 28.98),
+    city("Johannesburg", "ZA", -26.20, 28.05),
+    city("Kharkiv", "UA", 49.99, 36.23),
+    city("Kyiv", "UA", 50.45, 30.52),
+    city("London", "GB", 51.51, -0.13),
+    city("Los Angeles", "US", 34.05, -118.24),
+    city("Lviv", "UA", 49.84, 24.03),
+    city("Madrid", "ES", 40.42, -3.70),
+    city("Melbourne", "AU", -37.81, 144.96),
+    city("Miami", "US", 25.76, -80.19),
+    city("Milan", "IT", 45.46, 9.19),
+    city("Montreal", "CA", 45.50, -73.57),
+    city("Moscow", "RU", 55.76, 37.62),
+    city("Mumbai", "IN", 19.08, 72.88),
+    city("New York", "US", 40.71, -74.01),
+    city("Nuremberg", "DE", 49.45, 11.08),
+    city("Odesa", "UA", 46.48, 30.72),
+    city("Oslo", "NO", 59.91, 10.75),
+    city("Paris", "FR", 48.86, 2.35),
+    city("Prague", "CZ", 50.08, 14.44),
+    city("Roubaix", "FR", 50.69, 3.18),
+    city("Santiago", "CL", -33.45, -70.67),
+    city("Sao Paulo", "BR", -23.55, -46.63),
+    city("Seattle", "US", 47.61, -122.33),
+    city("Seoul", "KR", 37.57, 126.98),
+    city("Singapore", "SG", 1.35, 103.82),
+    city("Stockholm", "SE", 59.33, 18.07),
+    city("Sydney", "AU", -33.87, 151.21),
+    city("Tokyo", "JP", 35.68, 139.69),
+    city("Toronto", "CA", 43.65, -79.38),
+    city("Vienna", "AT", 48.21, 16.37),
+    city("Warsaw", "PL", 52.23, 21.01),
+];
+
+// Далі за цю відстань найближче місто з таблиці вже не вважаємо "тим самим"
+const NEAREST_MAX_KM: f32 = 150.0;
+
+pub fn find(name: &str) -> Option<&'static City> {
+    CITIES.iter().find(|city| city.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// (широта, довгота) з API або, якщо їх немає, названого міста.
+pub fn coords(location: Option<(f32, f32)>, city: &str) -> Option<(f32, f32)> {
+    location.or_else(|| find(city).map(|city| (city.lat, city.lon)))
+}
+
+/// Найближче місто з таблиці в тій самій країні - коли API не назвало міста.
+pub fn nearest(country: &str, (lat, lon): (f32, f32)) -> Option<&'static str> {
+    CITIES
+        .iter()
+        .filter(|city| city.country == country)
+        .map(|city| (city, distance_km((lat, lon), (city.lat, city.lon))))
+        .filter(|(_, km)| *km <= NEAREST_MAX_KM)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(city, _)| city.name)
+}
+
+/// Відстань по великому колу (формула гаверсинусів), км.
+pub fn distance_km(a: (f32, f32), b: (f32, f32)) -> f32 {
+    const EARTH_RADIUS_KM: f32 = 6371.0;
+    let (lat1, lon1) = (a.0.to_radians(), a.1.to_radians());
+    let (lat2, lon2) = (b.0.to_radians(), b.1.to_radians());
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn great_circle_distance() {
+        let kyiv = (50.45, 30.52);
+        let london = (51.51, -0.13);
+        assert!((distance_km(kyiv, london) - 2135.0).abs() < 20.0);
+        assert_eq!(distance_km(kyiv, kyiv), 0.0);
+        // Через антимеридіан
+        assert!((distance_km((0.0, 179.5), (0.0, -179.5)) - 111.2).abs() < 1.0);
+    }
+
+    #[test]
+    fn nearest_city_in_same_country() {
+        // Дата-центр поблизу Франкфурта
+        assert_eq!(nearest("DE", (50.10, 8.63)), Some("Frankfurt"));
+        // Місто іншої країни не підходить, навіть якщо ближче
+        assert_eq!(nearest("NL", (50.10, 8.63)), None);
+        // Далі NEAREST_MAX_KM - невідоме
+        assert_eq!(nearest("UA", (48.0, 37.8)), None);
+    }
+
+    #[test]
+    fn coords_fall_back_to_named_city() {
+        assert_eq!(coords(Some((1.0, 2.0)), "Kyiv"), Some((1.0, 2.0)));
+        assert_eq!(coords(None, " kyiv "), Some((50.45, 30.52)));
+        assert_eq!(coords(None, "Atlantis"), None);
+    }
+}
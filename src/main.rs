@@ -2,9 +2,118 @@
 
 use eframe::egui;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+mod a2s;
+mod announcements;
+mod autostart;
+mod backup;
+mod batch;
+mod bidi;
+mod config;
+mod deeplink;
+mod events;
+mod export;
+mod expr;
+mod families;
+mod filters;
+mod firstseen;
+mod history;
+mod interner;
+mod ipc;
+mod kiosk;
+mod latency;
+mod layerdb;
+mod layouts;
+mod location;
+mod normalize;
+mod notifications;
+mod overlay;
+mod patchday;
+mod palette;
+mod population;
+mod presence;
+mod primetime;
+mod provider;
+mod queueing;
+mod ranking;
+mod recording;
+mod regions;
+mod report;
+mod rotation;
+mod rules;
+mod scheduler;
+mod searches;
+mod secrets;
+mod seeding;
+mod sessions;
+mod skin;
+mod streamdeck;
+mod sync;
+mod traceroute;
+mod translations;
+mod uptime;
+mod validation;
+mod version;
+mod veto;
+mod watcher;
+mod worldmap;
+
+use announcements::{Announcement, AnnouncementFeed};
+use batch::{Batch, Fetched};
+use bidi::Direction;
+use deeplink::DeepLink;
+use events::PlannedEvent;
+use export::{Column, ExportSettings, Format};
+use expr::SmartList;
+use filters::{Filter, FilterSet};
+use firstseen::FirstSeenLog;
+use history::{History, Trend};
+use ipc::ControlServer;
+use kiosk::Kiosk;
+use latency::PingLog;
+use layerdb::{LayerDb, VehicleClass};
+use layouts::{Density, QuickAction, RowColumns};
+use normalize::Folded;
+use notifications::{Notification, NotificationAction, QuietHours};
+use overlay::{OverlayFormat, OverlaySettings};
+use palette::Palette;
+use patchday::{PatchDaySettings, PatchNote};
+use population::PopulationLog;
+use presence::PresenceTracker;
+use primetime::{PrimeTime, PrimeTimeLog};
+use provider::{BattleMetricsProvider, DemoProvider, PageRequest, ServerProvider, BATTLEMETRICS_URL};
+use queueing::QueueAssistant;
+use ranking::Badge;
+use recording::{RecordingProvider, ReplayProvider};
+use report::WeeklySummary;
+use rotation::RotationLog;
+use rules::RuleSet;
+use scheduler::{AdaptivePolls, RefreshScheduler};
+use searches::{MonitorState, SavedSearch, MONITOR_INTERVAL};
+use seeding::{SeedCall, SeedingSettings};
+use sessions::{PlaySession, SessionTracker};
+use skin::OverlaySkin;
+use streamdeck::DeckServer;
+use sync::{Decision, Profile, SyncResult, SyncSettings};
+use traceroute::Trace;
+use translations::Translations;
+use uptime::UptimeLog;
+use version::VersionCensus;
+use veto::{Veto, VetoSettings};
+use validation::Issue;
+use watcher::FileWatcher;
+
+const APP_NAME: &str = "Squad Browser";
+const MAX_PINNED: usize = 5;
 
 // --- СТРУКТУРИ ДАНИХ (API) ---
 
@@ -16,6 +125,13 @@ struct ApiAttributes {
     max_players: u32,
     details: ApiDetails,
     country: Option<String>,
+    // [довгота, широта]; тип не перевіряємо жорстко, як і прапорці античіту
+    location: Option<serde_json::Value>,
+    city: Option<String>,
+    ip: Option<String>,
+    port: Option<u32>,
+    #[serde(rename = "portQuery")]
+    port_query: Option<u32>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -23,10 +139,36 @@ struct ApiDetails {
     map: Option<String>,
     #[serde(rename = "gameMode")]
     game_mode: Option<String>,
+    #[serde(rename = "squad_publicQueue")]
+    public_queue: Option<u32>,
+    #[serde(rename = "squad_reservedQueue")]
+    reserved_queue: Option<u32>,
+    version: Option<String>,
+    // Не всі сервери звітують античіт; тип поля теж буває різним (bool/0/1)
+    eac: Option<serde_json::Value>,
+    secure: Option<serde_json::Value>,
+}
+
+fn flag_value(value: &Option<serde_json::Value>) -> Option<bool> {
+    match value {
+        Some(serde_json::Value::Bool(b)) => Some(*b),
+        Some(serde_json::Value::Number(n)) => n.as_u64().map(|n| n != 0),
+        _ => None,
+    }
+}
+
+// BattleMetrics: [довгота, широта] -> (широта, довгота)
+fn location_value(value: &Option<serde_json::Value>) -> Option<(f32, f32)> {
+    let pair = value.as_ref()?.as_array()?;
+    match (pair.first()?.as_f64(), pair.get(1)?.as_f64()) {
+        (Some(lon), Some(lat)) if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) => Some((lat as f32, lon as f32)),
+        _ => None,
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
 struct ApiServerData {
+    id: String,
     attributes: ApiAttributes,
 }
 
@@ -37,32 +179,333 @@ struct ApiLinks {
 
 #[derive(Deserialize, Debug, Clone)]
 struct ApiResponse {
-    data: Vec<ApiServerData>,
+    // Записи розбираються поодинці (parse_servers): один зіпсований не губить сторінку
+    data: Vec<serde_json::Value>,
     links: Option<ApiLinks>,
 }
 
+fn parse_servers(data: Vec<serde_json::Value>) -> Vec<ApiServerData> {
+    let mut servers = Vec::with_capacity(data.len());
+    for (index, item) in data.into_iter().enumerate() {
+        let id = item.get("id").and_then(serde_json::Value::as_str).unwrap_or("?").to_owned();
+        match serde_json::from_value::<ApiServerData>(item) {
+            Ok(server) => servers.push(server),
+            Err(e) => println!("[ERR] Skipped malformed server entry #{} (id {}): {}", index, id, e),
+        }
+    }
+    servers
+}
+
 // --- СТРУКТУРИ ДЛЯ GUI ---
 
-#[derive(Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(default)]
 struct ServerItem {
+    id: String,
     name: String,
+    full_name: String,
     players: u32,
     max_players: u32,
-    map: String,
-    mode: String,
+    #[serde(deserialize_with = "interner::deserialize")]
+    map: Arc<str>,
+    #[serde(deserialize_with = "interner::deserialize")]
+    mode: Arc<str>,
+    #[serde(deserialize_with = "interner::deserialize")]
+    country: Arc<str>,
+    // З API або найближче відоме (location::nearest); порожнє - невідоме
+    city: String,
+    // (широта, довгота)
+    location: Option<(f32, f32)>,
+    public_queue: u32,
+    reserved_queue: u32,
+    version: String,
+    anticheat: Option<bool>,
+    ip: String,
+    // Ігровий порт (адреса для гри) і query-порт (Steam A2S)
+    game_port: u32,
+    query_port: u32,
+    // Назва з літерами-двійниками або знайдена пошуком лише після нормалізації
+    obfuscated: bool,
+    // Коли програма вперше побачила сервер (firstseen.rs); None - невідомо
+    #[serde(skip)]
+    first_seen: Option<u64>,
+    // Коли сервер зазвичай заповнений (primetime.rs); None - невідомо
+    #[serde(skip)]
+    prime_time: Option<PrimeTime>,
+    #[serde(skip)]
+    text: RowText,
+}
+
+// Готові рядки для списку: рахуються при отриманні даних, а не кожен кадр
+#[derive(Clone, Debug, Default)]
+struct RowText {
+    // У візуальному порядку (bidi::visual) - назви бувають івритом чи арабською
+    name: String,
     country: String,
+    // "[Frankfurt, DE]" або, без міста, те саме, що country
+    place: String,
+    map_mode: String,
+    players: String,
+}
+
+// Дорожчі частини деталей (пошук сім'ї по всіх вкладках, історія карт):
+// рахуються раз на оновлення даних і спільні для підказки й панелі деталей
+struct ServerSummary {
+    // Ключ сім'ї і назви всіх її серверів (разом із цим)
+    family: Option<(String, Vec<String>)>,
+    recent_maps: Vec<String>,
+}
+
+impl ServerItem {
+    // Squad приймає steam://connect з query-портом сервера; без нього - ігровий
+    fn join_port(&self) -> u32 {
+        if self.query_port != 0 { self.query_port } else { self.game_port }
+    }
+
+    fn can_join(&self) -> bool {
+        !self.ip.is_empty() && self.join_port() != 0
+    }
+
+    // Червоний лічильник гравців; max_players буває 0, якщо API його не віддав
+    fn is_nearly_full(&self) -> bool {
+        self.players >= self.max_players.saturating_sub(2)
+    }
+
+    // A2S відповідає лише на query-порт
+    fn can_query(&self) -> bool {
+        !self.ip.is_empty() && self.query_port != 0
+    }
+
+    fn address(&self) -> String {
+        format!("{}:{}", self.ip, self.game_port)
+    }
+
+    // "Frankfurt, DE" або лише код країни
+    fn place(&self) -> String {
+        if self.city.is_empty() { self.country.to_string() } else { format!("{}, {}", self.city, self.country) }
+    }
+
+    fn coords(&self) -> Option<(f32, f32)> {
+        location::coords(self.location, &self.city)
+    }
+
+    fn with_text(mut self) -> Self {
+        self.text = RowText {
+            name: bidi::visual(&self.name).into_owned(),
+            country: format!("[{}]", self.country),
+            place: format!("[{}]", self.place()),
+            map_mode: format!("{} | {}", self.map, self.mode),
+            players: format!("{}/{}", self.players, self.max_players),
+        };
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ScanFilters {
+    filters: FilterSet,
+    extra_params: Vec<(String, String)>,
+    rules: RuleSet,
+    pinned_ids: HashSet<String>,
+    layers: Arc<LayerDb>,
 }
 
 #[derive(Clone, Debug)]
 struct ScanResult {
     servers: Vec<ServerItem>,
+    // Закріплені сервери, які не пройшли фільтри (щоб оновити їх дані)
+    unmatched_pinned: Vec<ServerItem>,
+    next_url: String,
+    // Курсор сторінки, що не завантажилась (порожній = перша сторінка)
+    failed_cursor: String,
+    error: Option<String>,
+    // id усіх записів сторінки, навіть тих, що не розібрались (для присутності)
+    raw_ids: Vec<String>,
+}
+
+// Стан одного списку результатів (своя пагінація і завантаження).
+// Між запусками зберігаються результати і курсори сторінок.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+struct ScanView {
+    servers: Vec<ServerItem>,
+    first_load_done: bool,
     next_url: String,
+    // Сторінка, яку почали, але не отримали (вихід з програми, обрив мережі).
+    // Порожній - нічого дочитувати.
+    resume_cursor: String,
+
+    #[serde(skip)]
+    rx: Option<Receiver<ScanResult>>,
+    #[serde(skip)]
+    is_loading: bool,
+    #[serde(skip)]
+    scan_error: Option<String>,
+    #[serde(skip)]
+    failed_cursor: String,
+    #[serde(skip)]
+    refreshed_ids: HashSet<String>,
+    // Значки "топ N%" у межах фільтрів цієї вкладки
+    #[serde(skip)]
+    badges: HashMap<String, Badge>,
+}
+
+impl ScanView {
+    fn update_badges(&mut self) {
+        self.badges = ranking::compute(self.servers.iter().map(|s| (s.id.as_str(), s.players, &*s.country)));
+    }
+
+    // Режим "заморожений порядок": оновлюємо значення на місці, нові сервери - в кінець
+    fn merge_servers(&mut self, incoming: Vec<ServerItem>) {
+        let mut index: HashMap<String, usize> =
+            self.servers.iter().enumerate().map(|(i, s)| (s.id.clone(), i)).collect();
+        for server in incoming {
+            self.refreshed_ids.insert(server.id.clone());
+            match index.get(&server.id) {
+                Some(&i) => self.servers[i] = server,
+                None => {
+                    index.insert(server.id.clone(), self.servers.len());
+                    self.servers.push(server);
+                }
+            }
+        }
+    }
+
+    // Оновлення дійшло до останньої сторінки: чого в ньому не було, того вже
+    // немає в результатах (або воно не проходить фільтри). Порядок не чіпаємо.
+    fn drop_stale(&mut self) {
+        if !self.refreshed_ids.is_empty() {
+            self.servers.retain(|s| self.refreshed_ids.contains(&s.id));
+        }
+    }
+
+    // Рядок ще не оновлено поточним оновленням (воно могло не дійти до його сторінки)
+    fn is_stale(&self, id: &str) -> bool {
+        !self.refreshed_ids.is_empty() && !self.refreshed_ids.contains(id)
+    }
+
+    fn resort_servers(&mut self) {
+        if !self.refreshed_ids.is_empty() {
+            self.servers.retain(|s| self.refreshed_ids.contains(&s.id));
+        }
+        self.servers.sort_by_key(|s| std::cmp::Reverse(s.players));
+    }
+}
+
+// Вкладка робочого простору. Фільтри активної вкладки живуть у SquadApp::filters,
+// а її результати - у SquadApp::view.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+struct WorkspaceTab {
+    title: String,
+    filters: FilterSet,
+    view: ScanView,
+}
+
+// Збережений макет вікна ("browsing", "admin", "overlay"...)
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+struct WindowLayout {
+    name: String,
+    section: Section,
+    sidebar_width: f32,
+    density: Density,
+    columns: RowColumns,
+    // Макети, збережені до появи кнопок, отримують типовий набір
+    #[serde(default = "layouts::default_quick_actions")]
+    quick_actions: Vec<QuickAction>,
+    notifications_open: bool,
+    compact_overlay: bool,
+}
+
+#[derive(Default, Clone)]
+struct LaunchOptions {
+    demo: bool,
+    api_url: Option<String>,
+    record: Option<String>,
+    replay: Option<String>,
+    kiosk: bool,
+    // Дзеркальна розкладка без RTL-перекладу - перевірити верстку
+    rtl: bool,
+    // squadbrowser://..., з яким ОС запустила програму; відкривається в першому кадрі
+    open: Option<String>,
+    // Запуск разом із системою - вікно згортається в першому кадрі
+    minimized: bool,
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, PartialEq, Clone, Default)]
 enum Language {
+    #[default]
     En,
     Ua,
+    // Код мови = ім'я файлу в lang/ (translations.rs)
+    Custom(String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ResetScope {
+    Filters,
+    Blacklist,
+    Appearance,
+    Everything,
+}
+
+// Розділи бічної навігації
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+enum Section {
+    #[default]
+    AllServers,
+    Favorites,
+    History,
+    Events,
+    SmartLists,
+    Stats,
+    Settings,
+}
+
+// Дії палітри команд (Ctrl+K)
+#[derive(Clone)]
+enum Command {
+    Refresh,
+    RefreshAll,
+    NewTab,
+    ShowSection(Section),
+    SwitchTab(usize),
+    LoadSearch(usize),
+    ShowSmartList(usize),
+    OpenFavorite(usize),
+    JoinFavorite(usize),
+    RemoveFilter(Filter),
+    ToggleAntiCheat,
+    ToggleFreeze,
+    ToggleCompact,
+    ToggleLanguage,
+    ToggleNotifications,
+    ExportRules,
+    ExportCalendar,
+    // true - HTML, false - Markdown
+    ExportWeekly(bool),
+    ExportServers,
+    MapVeto,
+    TogglePatchDay,
+    ApplyLayout(usize),
+    BackupAll,
+    SyncNow,
+}
+
+#[derive(Default)]
+struct RowActions {
+    clicked: Option<String>,
+    toggle_pin: Option<String>,
+    join: Option<String>,
+    query_rules: Option<String>,
+    plan_event: Option<String>,
+    // Ключ сім'ї серверів (families::key)
+    pin_family: Option<String>,
+    ban_family: Option<String>,
+    toggle_target: Option<String>,
+    traceroute: Option<String>,
+    hide: Option<String>,
 }
 
 // --- ЛОГІКА ДОДАТКУ ---
@@ -70,107 +513,451 @@ enum Language {
 #[derive(Deserialize, Serialize)]
 #[serde(default)]
 struct SquadApp {
-    min_players: u32,
-    max_players: u32,
-    banned_countries: HashSet<String>,
-    filter_name: String,
-    filter_map: String,
-    filter_mode: String,
+    // Відсутнє поле = конфіг, збережений до появи версій (v0)
+    #[serde(default)]
+    config_version: u32,
+    filters: FilterSet,
     language: Language,
+    overlay: OverlaySettings,
+    block_rules: String,
+    rules_file: String,
+    watch_rules_file: bool,
+    freeze_order: bool,
+    single_expand: bool,
+    pinned: Vec<ServerItem>,
+    seed_threshold: u32,
+    expected_version: String,
+    saved_searches: Vec<SavedSearch>,
+    sessions: Vec<PlaySession>,
+    rotations: RotationLog,
+    quiet_hours: QuietHours,
+    announcements: AnnouncementFeed,
+    seeding: SeedingSettings,
+    patch_day: PatchDaySettings,
+    veto: VetoSettings,
+    layer_db_url: String,
+    notifications: Vec<Notification>,
+    skin: OverlaySkin,
+    smart_lists: Vec<SmartList>,
+    events: Vec<PlannedEvent>,
+    ics_path: String,
+    backup_path: String,
+    export: ExportSettings,
+    sync: SyncSettings,
+    population: PopulationLog,
+    uptime: UptimeLog,
+    first_seen: FirstSeenLog,
+    prime_times: PrimeTimeLog,
+    ping_history: PingLog,
+    density: Density,
+    row_columns: RowColumns,
+    quick_actions: Vec<QuickAction>,
+    // Для оцінки відстані до серверів (location::CITIES); порожнє - не показувати
+    home_city: String,
+    sidebar_width: f32,
+    layouts: Vec<WindowLayout>,
+    tabs: Vec<WorkspaceTab>,
+    active_tab: usize,
+    view: ScanView,
 
     #[serde(skip)]
-    servers: Vec<ServerItem>,
+    section: Section,
     #[serde(skip)]
-    next_url: String,
+    selected_id: Option<String>,
     #[serde(skip)]
-    show_settings: bool,
+    expanded_ids: HashSet<String>,
     #[serde(skip)]
-    rx: Option<Receiver<ScanResult>>,
+    previous_players: HashMap<String, u32>,
     #[serde(skip)]
-    is_loading: bool,
+    history: History,
     #[serde(skip)]
-    first_load_done: bool,
+    versions: VersionCensus,
+    #[serde(skip)]
+    pinned_matching: HashSet<String>,
+    #[serde(skip)]
+    pinned_unmatched: HashSet<String>,
+    #[serde(skip)]
+    rules_file_mtime: Option<SystemTime>,
+    #[serde(skip)]
+    provider: Option<Arc<dyn ServerProvider>>,
+    #[serde(skip)]
+    launch: LaunchOptions,
+    #[serde(skip)]
+    watcher: Option<FileWatcher>,
+    #[serde(skip)]
+    config_path: Option<PathBuf>,
+    #[serde(skip)]
+    toast: Option<(String, Instant)>,
+    #[serde(skip)]
+    rules_status: String,
+    #[serde(skip)]
+    new_search_name: String,
+    #[serde(skip)]
+    monitors: HashMap<String, MonitorState>,
+    #[serde(skip)]
+    monitor_tx: Sender<(String, Option<Vec<String>>)>,
+    #[serde(skip)]
+    monitor_rx: Receiver<(String, Option<Vec<String>>)>,
+    #[serde(skip)]
+    scheduler: RefreshScheduler,
+    // Коли опитувати кожен закріплений сервер (poll_presence)
+    #[serde(skip)]
+    pin_polls: AdaptivePolls,
+    #[serde(skip)]
+    egui_ctx: egui::Context,
+    #[serde(skip)]
+    pending_reset: Option<ResetScope>,
+    #[serde(skip)]
+    session_tracker: SessionTracker,
+    #[serde(skip)]
+    show_notifications: bool,
+    #[serde(skip)]
+    applied_skin: Option<OverlaySkin>,
+    #[serde(skip)]
+    presence: PresenceTracker,
+    #[serde(skip)]
+    batch_tx: Sender<(Batch, Option<Fetched>)>,
+    #[serde(skip)]
+    batch_rx: Receiver<(Batch, Option<Fetched>)>,
+    #[serde(skip)]
+    active_smart_list: Option<usize>,
+    #[serde(skip)]
+    new_smart_name: String,
+    #[serde(skip)]
+    new_smart_expr: String,
+    // Форма нової події; дата й час - як їх ввели
+    #[serde(skip)]
+    new_event: PlannedEvent,
+    #[serde(skip)]
+    new_event_date: String,
+    #[serde(skip)]
+    new_event_time: String,
+    #[serde(skip)]
+    events_status: String,
+    // Розібрана копія чекає вибору: замінити все чи об'єднати
+    #[serde(skip)]
+    pending_restore: Option<Box<SquadApp>>,
+    #[serde(skip)]
+    pending_restore_rules: Option<String>,
+    #[serde(skip)]
+    backup_status: String,
+    #[serde(skip)]
+    show_export: bool,
+    #[serde(skip)]
+    export_preset_name: String,
+    #[serde(skip)]
+    palette: Palette,
+    #[serde(skip)]
+    applied_density: Option<Density>,
+    // Ширину панелі з макета примусово ставимо на один кадр, далі її знову можна тягнути
+    #[serde(skip)]
+    resize_sidebar: bool,
+    #[serde(skip)]
+    new_layout_name: String,
+    #[serde(skip)]
+    translations: Translations,
+    // ID сервера -> зведення; скидається з кожною порцією нових даних
+    #[serde(skip)]
+    summaries: RefCell<HashMap<String, Rc<ServerSummary>>>,
+    #[serde(skip)]
+    control: Option<ControlServer>,
+    #[serde(skip)]
+    deck: Option<DeckServer>,
+    // Обрані з вільним місцем на момент останнього оновлення
+    #[serde(skip)]
+    deck_free: HashSet<String>,
+    #[serde(skip)]
+    sync_checked: Option<Instant>,
+    #[serde(skip)]
+    sync_busy: bool,
+    #[serde(skip)]
+    sync_status: String,
+    // Секрет змінено в налаштуваннях, але ще не записано в сховище ОС
+    #[serde(skip)]
+    sync_secret_dirty: bool,
+    // Змінились обидві сторони: чекаємо, що вибере користувач
+    #[serde(skip)]
+    sync_conflict: Option<Box<Profile>>,
+    #[serde(skip)]
+    sync_tx: Sender<SyncResult>,
+    #[serde(skip)]
+    sync_rx: Receiver<SyncResult>,
+    #[serde(skip)]
+    population_range: population::Range,
+    #[serde(skip)]
+    population_series: String,
+    // Регіон -> (сервер, результат; None - ще чекаємо)
+    #[serde(skip)]
+    ping_results: HashMap<&'static str, (String, Option<a2s::Ping>)>,
+    #[serde(skip)]
+    ping_tx: Sender<(&'static str, a2s::Ping)>,
+    #[serde(skip)]
+    ping_rx: Receiver<(&'static str, a2s::Ping)>,
+    // Фонові заміри пінгу обраних: (ID, мс; None - без відповіді)
+    // Одне трасування за раз: нове замінює попереднє
+    #[serde(skip)]
+    trace: Option<Trace>,
+    #[serde(skip)]
+    ping_history_checked: Option<Instant>,
+    #[serde(skip)]
+    ping_history_tx: Sender<Vec<(String, Option<u32>)>>,
+    #[serde(skip)]
+    ping_history_rx: Receiver<Vec<(String, Option<u32>)>>,
+    // ID сервера -> правила A2S; None - запит ще йде
+    #[serde(skip)]
+    server_rules: HashMap<String, Option<a2s::Rules>>,
+    #[serde(skip)]
+    rules_tx: Sender<(String, a2s::Rules)>,
+    #[serde(skip)]
+    rules_rx: Receiver<(String, a2s::Rules)>,
+    #[serde(skip)]
+    announcements_checked: Option<Instant>,
+    #[serde(skip)]
+    announcements_tx: Sender<Result<Vec<Announcement>, String>>,
+    #[serde(skip)]
+    announcements_rx: Receiver<Result<Vec<Announcement>, String>>,
+    // Сервери, які спільноти саме зараз сідують
+    #[serde(skip)]
+    seed_calls: Vec<SeedCall>,
+    #[serde(skip)]
+    seed_alerted: HashSet<String>,
+    #[serde(skip)]
+    seeding_checked: Option<Instant>,
+    #[serde(skip)]
+    seeding_tx: Sender<Result<Vec<SeedCall>, String>>,
+    #[serde(skip)]
+    seeding_rx: Receiver<Result<Vec<SeedCall>, String>>,
+    #[serde(skip)]
+    links_status: String,
+    // Читається з ОС при запуску (autostart::is_enabled)
+    #[serde(skip)]
+    autostart: bool,
+    #[serde(skip)]
+    autostart_status: String,
+    #[serde(skip)]
+    patch_note: Option<PatchNote>,
+    #[serde(skip)]
+    patch_checked: Option<Instant>,
+    #[serde(skip)]
+    patch_tx: Sender<Result<Option<PatchNote>, String>>,
+    #[serde(skip)]
+    patch_rx: Receiver<Result<Option<PatchNote>, String>>,
+    // Arc - щоб фільтр техніки міг читати базу у потоці сканування
+    #[serde(skip)]
+    layer_db: Arc<LayerDb>,
+    #[serde(skip)]
+    layer_db_busy: bool,
+    #[serde(skip)]
+    layer_db_status: String,
+    #[serde(skip)]
+    layer_db_tx: Sender<Result<LayerDb, String>>,
+    #[serde(skip)]
+    layer_db_rx: Receiver<Result<LayerDb, String>>,
+    #[serde(skip)]
+    show_veto: bool,
+    // Вето, що йде зараз; None - форма налаштування
+    #[serde(skip)]
+    veto_session: Option<Veto>,
+    #[serde(skip)]
+    kiosk: Kiosk,
+    #[serde(skip)]
+    queue_assistant: QueueAssistant,
 }
 
 impl Default for SquadApp {
     fn default() -> Self {
-        let mut banned = HashSet::new();
+        let mut filters = FilterSet::default();
         for code in ["RU", "BY", "CN", "BR"] {
-            banned.insert(code.to_string());
+            filters.set(Filter::NoCountry(code.to_string()));
         }
+        let (monitor_tx, monitor_rx) = channel();
+        let (batch_tx, batch_rx) = channel();
+        let (ping_tx, ping_rx) = channel();
+        let (ping_history_tx, ping_history_rx) = channel();
+        let (rules_tx, rules_rx) = channel();
+        let (announcements_tx, announcements_rx) = channel();
+        let (seeding_tx, seeding_rx) = channel();
+        let (patch_tx, patch_rx) = channel();
+        let (layer_db_tx, layer_db_rx) = channel();
+        let (sync_tx, sync_rx) = channel();
 
         Self {
-            servers: Vec::new(),
-            min_players: 0,
-            max_players: 100,
-            banned_countries: banned,
-            filter_name: String::new(),
-            filter_map: String::new(),
-            filter_mode: String::new(),
+            config_version: config::CONFIG_VERSION,
+            filters,
             language: Language::En,
-            
-            next_url: String::new(),
-            show_settings: false,
-            rx: None,
-            is_loading: false,
-            first_load_done: false,
+            overlay: OverlaySettings::default(),
+            block_rules: String::new(),
+            rules_file: "squad_rules.txt".to_owned(),
+            watch_rules_file: false,
+            freeze_order: false,
+            single_expand: true,
+            pinned: Vec::new(),
+            seed_threshold: 50,
+            expected_version: String::new(),
+            saved_searches: Vec::new(),
+            sessions: Vec::new(),
+            rotations: RotationLog::default(),
+            quiet_hours: QuietHours::default(),
+            announcements: AnnouncementFeed::default(),
+            seeding: SeedingSettings::default(),
+            patch_day: PatchDaySettings::default(),
+            veto: VetoSettings::default(),
+            layer_db_url: String::new(),
+            notifications: Vec::new(),
+            skin: OverlaySkin::default(),
+            smart_lists: Vec::new(),
+            events: Vec::new(),
+            ics_path: "squad_events.ics".to_owned(),
+            backup_path: "squad_backup.zip".to_owned(),
+            export: ExportSettings::default(),
+            sync: SyncSettings::default(),
+            population: PopulationLog::default(),
+            uptime: UptimeLog::default(),
+            first_seen: FirstSeenLog::default(),
+            prime_times: PrimeTimeLog::default(),
+            ping_history: PingLog::default(),
+            density: Density::default(),
+            row_columns: RowColumns::default(),
+            quick_actions: layouts::default_quick_actions(),
+            home_city: String::new(),
+            sidebar_width: layouts::SIDEBAR_WIDTH,
+            layouts: Vec::new(),
+            tabs: vec![WorkspaceTab::default()],
+            active_tab: 0,
+            view: ScanView::default(),
+
+            section: Section::AllServers,
+            selected_id: None,
+            expanded_ids: HashSet::new(),
+            previous_players: HashMap::new(),
+            history: History::default(),
+            versions: VersionCensus::default(),
+            pinned_matching: HashSet::new(),
+            pinned_unmatched: HashSet::new(),
+            rules_file_mtime: None,
+            provider: None,
+            launch: LaunchOptions::default(),
+            watcher: None,
+            config_path: None,
+            toast: None,
+            rules_status: String::new(),
+            new_search_name: String::new(),
+            monitors: HashMap::new(),
+            monitor_tx,
+            monitor_rx,
+            scheduler: RefreshScheduler::default(),
+            pin_polls: AdaptivePolls::default(),
+            egui_ctx: egui::Context::default(),
+            pending_reset: None,
+            session_tracker: SessionTracker::default(),
+            show_notifications: false,
+            applied_skin: None,
+            presence: PresenceTracker::default(),
+            batch_tx,
+            batch_rx,
+            active_smart_list: None,
+            new_smart_name: String::new(),
+            new_smart_expr: String::new(),
+            new_event: PlannedEvent { minutes: 60, ..PlannedEvent::default() },
+            new_event_date: String::new(),
+            new_event_time: "19:00".to_owned(),
+            events_status: String::new(),
+            pending_restore: None,
+            pending_restore_rules: None,
+            backup_status: String::new(),
+            show_export: false,
+            export_preset_name: String::new(),
+            palette: Palette::default(),
+            applied_density: None,
+            resize_sidebar: true,
+            new_layout_name: String::new(),
+            translations: Translations::default(),
+            summaries: RefCell::new(HashMap::new()),
+            control: None,
+            deck: None,
+            deck_free: HashSet::new(),
+            sync_checked: None,
+            sync_busy: false,
+            sync_status: String::new(),
+            sync_secret_dirty: false,
+            sync_conflict: None,
+            sync_tx,
+            sync_rx,
+            population_range: population::Range::Day,
+            population_series: String::new(),
+            ping_results: HashMap::new(),
+            ping_tx,
+            ping_rx,
+            trace: None,
+            ping_history_checked: None,
+            ping_history_tx,
+            ping_history_rx,
+            server_rules: HashMap::new(),
+            rules_tx,
+            rules_rx,
+            announcements_checked: None,
+            announcements_tx,
+            announcements_rx,
+            seed_calls: Vec::new(),
+            seed_alerted: HashSet::new(),
+            seeding_checked: None,
+            seeding_tx,
+            seeding_rx,
+            links_status: String::new(),
+            autostart: false,
+            autostart_status: String::new(),
+            patch_note: None,
+            patch_checked: None,
+            patch_tx,
+            patch_rx,
+            layer_db: Arc::default(),
+            layer_db_busy: false,
+            layer_db_status: String::new(),
+            layer_db_tx,
+            layer_db_rx,
+            show_veto: false,
+            veto_session: None,
+            kiosk: Kiosk::default(),
+            queue_assistant: QueueAssistant::default(),
         }
     }
 }
 
-fn fetch_servers(
-    min_p: u32, 
-    max_p: u32, 
-    banned: HashSet<String>, 
-    f_name: String,
-    f_map: String, 
-    f_mode: String, 
-    override_url: String
-) -> ScanResult {
-    
+fn fetch_servers(provider: &dyn ServerProvider, filters: ScanFilters, override_url: String) -> ScanResult {
+    let ScanFilters { filters, extra_params, rules, pinned_ids, layers } = filters;
+    let banned = filters.banned_countries();
+
     // ЛОГ В КОНСОЛЬ
     println!("[LOG] Starting fetch request...");
 
-    let client = reqwest::blocking::Client::new();
     let mut final_servers = Vec::new();
+    let mut unmatched_pinned = Vec::new();
     let mut next_link = String::new();
+    let mut error = None;
+    let mut raw_ids = Vec::new();
     
-    let ban_words_ru = ["RUSSIA", "MOSCOW", "SPB", "USSR", "ZOV", "WAGNER", "[RU]"];
+    // Порівнюються після нормалізації, тож "M0SCOW" і кирилиця теж ловляться
+    let ban_words_ru = ["RUSSIA", "ROSSIYA", "MOSCOW", "MOSKVA", "SPB", "USSR", "SSSR", "ZOV", "WAGNER", "[RU]"];
     let ban_words_cn = ["CHINESE", "ASIA", "[CN]", "QQ", "DOUYU"];
 
     let is_infinite_scroll = !override_url.is_empty();
-    
-    let base_url = "https://api.battlemetrics.com/servers";
 
-    let current_url = if is_infinite_scroll {
-        override_url.clone()
+    let request = if is_infinite_scroll {
+        PageRequest::Next(override_url.clone())
     } else {
-        base_url.to_string()
+        PageRequest::First { min_players: filters.min_players(), max_players: filters.max_players(), extra_params }
     };
 
-    let mut request = client.get(&current_url);
-
-    if !is_infinite_scroll {
-        println!("[LOG] New search initiated. Filters applied.");
-        request = request
-            .query(&[("filter[game]", "squad")])
-            .query(&[("filter[status]", "online")])
-            .query(&[("page[size]", "50")])
-            .query(&[("sort", "-players")])
-            .query(&[("filter[players][min]", min_p.to_string())])
-            .query(&[("filter[players][max]", max_p.to_string())]);
-    } else {
-        println!("[LOG] Loading next page from API...");
-    }
-
-    match request.send() {
-        Ok(resp) => {
-            if let Ok(json) = resp.json::<ApiResponse>() {
+    match provider.fetch_page(&request) {
+        Ok(body) => match serde_json::from_str::<ApiResponse>(&body) {
+            Ok(json) => {
                 if let Some(links) = json.links {
                     next_link = links.next.unwrap_or_default();
                 }
 
-                for server_data in json.data {
+                raw_ids = json.data.iter().filter_map(|s| s.get("id")?.as_str().map(str::to_owned)).collect();
+                for server_data in parse_servers(json.data) {
+                    let id = server_data.id;
                     let attr = server_data.attributes;
                     let country = attr.country.unwrap_or("??".to_string());
                     let name = attr.name;
@@ -178,321 +965,5843 @@ fn fetch_servers(
                     let max_players = attr.max_players;
                     let map = attr.details.map.unwrap_or("Unknown".to_string());
                     let mode = attr.details.game_mode.unwrap_or("Unknown".to_string());
+                    let public_queue = attr.details.public_queue.unwrap_or(0);
+                    let reserved_queue = attr.details.reserved_queue.unwrap_or(0);
+                    let version = attr.details.version.unwrap_or_default();
+                    let anticheat = flag_value(&attr.details.eac).or(flag_value(&attr.details.secure));
+                    let ip = attr.ip.unwrap_or_default();
+                    let game_port = attr.port.unwrap_or(0);
+                    let query_port = attr.port_query.unwrap_or(0);
+                    let location = location_value(&attr.location);
+                    let city = match attr.city.as_deref().map(str::trim) {
+                        Some(city) if !city.is_empty() => city.to_owned(),
+                        _ => location.and_then(|at| location::nearest(&country, at)).unwrap_or_default().to_owned(),
+                    };
                     
                     let mut skip = false;
                     if country != "UA" {
                         if banned.contains(&country) { skip = true; }
-                        let name_upper = name.to_uppercase();
+                        let folded = Folded::new(&name);
                         if banned.contains("RU") {
-                            for w in ban_words_ru { if name_upper.contains(w) { skip = true; break; } }
+                            for w in ban_words_ru { if folded.contains(w) { skip = true; break; } }
                         }
                         if banned.contains("CN") {
-                            for w in ban_words_cn { if name_upper.contains(w) { skip = true; break; } }
+                            for w in ban_words_cn { if folded.contains(w) { skip = true; break; } }
+                        }
+                        if skip && ban_words_ru.iter().chain(ban_words_cn.iter()).any(|w| folded.evades(w)) {
+                            println!("[LOG] Hid obfuscated name: {}", name);
                         }
                     }
-                    if skip { continue; }
-
-                    if !f_name.is_empty() && !name.to_lowercase().contains(&f_name.to_lowercase()) { continue; }
-                    if !f_map.is_empty() && !map.to_lowercase().contains(&f_map.to_lowercase()) { continue; }
-                    if !f_mode.is_empty() && !mode.to_lowercase().contains(&f_mode.to_lowercase()) { continue; }
+                    if rules.is_blocked(&id, &name, &country) { skip = true; }
+                    if rules.is_exception(&id, &name, &country) { skip = false; }
 
                     // --- ФІКС ТУТ (Безпечна обрізка рядка) ---
                     let clean_name = if name.chars().count() > 48 {
                         let truncated: String = name.chars().take(45).collect();
                         format!("{}...", truncated)
                     } else {
-                        name
+                        name.clone()
                     };
 
-                    final_servers.push(ServerItem {
+                    let obfuscated = normalize::has_homoglyphs(&name) || Folded::new(&name).evades(filters.name());
+                    let item = ServerItem {
+                        id,
                         name: clean_name,
+                        full_name: name,
                         players,
                         max_players,
-                        map,
-                        mode,
-                        country,
-                    });
+                        map: interner::intern(&map),
+                        mode: interner::intern(&mode),
+                        country: interner::intern(&country),
+                        city,
+                        location,
+                        public_queue,
+                        reserved_queue,
+                        version,
+                        anticheat,
+                        ip,
+                        game_port,
+                        query_port,
+                        obfuscated,
+                        first_seen: None,
+                        prime_time: None,
+                        text: RowText::default(),
+                    };
+                    let matches_filters = !skip && filters.matches(&item, &layers);
+                    if !matches_filters && !pinned_ids.contains(&item.id) { continue; }
+
+                    let item = item.with_text();
+                    if matches_filters {
+                        final_servers.push(item);
+                    } else {
+                        unmatched_pinned.push(item);
+                    }
                 }
-            } else {
-                println!("[ERR] Failed to parse JSON");
+            }
+            Err(e) => {
+                println!("[ERR] Failed to parse JSON: {}", e);
+                error = Some(e.to_string());
             }
         },
-        Err(e) => println!("[ERR] Network error: {}", e),
+        Err(e) => {
+            println!("[ERR] Network error: {}", e);
+            error = Some(e);
+        }
     }
 
     println!("[LOG] Fetched {} servers. Next URL present: {}", final_servers.len(), !next_link.is_empty());
 
     ScanResult {
         servers: final_servers,
+        unmatched_pinned,
         next_url: next_link,
+        failed_cursor: if error.is_some() { override_url } else { String::new() },
+        error,
+        raw_ids,
     }
 }
 
 // --- GUI ---
 
 impl SquadApp {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-        }
-        Default::default()
-    }
-
-    fn tr(&self, key: &str) -> String {
-        match (key, &self.language) {
-            ("app_title", Language::En) => "Squad Browser".to_owned(),
-            ("app_title", Language::Ua) => "Пошук Серверів Squad".to_owned(),
-            ("settings", Language::En) => "Settings".to_owned(),
-            ("settings", Language::Ua) => "Налаштування".to_owned(),
-            ("start", Language::En) => "START SCAN".to_owned(),
-            ("start", Language::Ua) => "ПОЧАТИ ПОШУК".to_owned(),
-            ("refresh", Language::En) => "REFRESH".to_owned(),
-            ("refresh", Language::Ua) => "ОНОВИТИ".to_owned(),
-            ("found", Language::En) => "Servers:".to_owned(),
-            ("found", Language::Ua) => "Серверів:".to_owned(),
-            ("no_servers", Language::En) => "No servers found.".to_owned(),
-            ("no_servers", Language::Ua) => "Серверів не знайдено.".to_owned(),
-            ("conf_title", Language::En) => "Configuration".to_owned(),
-            ("conf_title", Language::Ua) => "Конфігурація".to_owned(),
-            ("min_p", Language::En) => "Min Players:".to_owned(),
-            ("min_p", Language::Ua) => "Мін. Гравців:".to_owned(),
-            ("max_p", Language::En) => "Max Players:".to_owned(),
-            ("max_p", Language::Ua) => "Макс. Гравців:".to_owned(),
-            ("search_name", Language::En) => "Server Name:".to_owned(),
-            ("search_name", Language::Ua) => "Назва Сервера:".to_owned(),
-            ("map", Language::En) => "Map Name:".to_owned(),
-            ("map", Language::Ua) => "Назва Карти:".to_owned(),
-            ("mode", Language::En) => "Game Mode:".to_owned(),
-            ("mode", Language::Ua) => "Режим Гри:".to_owned(),
-            ("close", Language::En) => "Close & Save".to_owned(),
-            ("close", Language::Ua) => "Зберегти і Закрити".to_owned(),
-            ("lang", Language::En) => "Language:".to_owned(),
-            ("lang", Language::Ua) => "Мова:".to_owned(),
-            ("bl_title", Language::En) => "🚫 Disabled Countries".to_owned(),
-            ("bl_title", Language::Ua) => "🚫 Заблоковані Країни".to_owned(),
-            ("scanning", Language::En) => "Scanning...".to_owned(),
-            ("scanning", Language::Ua) => "Пошук...".to_owned(),
-            ("loading_more", Language::En) => "Loading more...".to_owned(),
-            ("loading_more", Language::Ua) => "Підвантажую ще...".to_owned(),
-            ("ready", Language::En) => "Ready".to_owned(),
-            ("ready", Language::Ua) => "Готовий".to_owned(),
-            _ => key.to_owned(),
+    fn new(cc: &eframe::CreationContext<'_>, launch: LaunchOptions, provider: Box<dyn ServerProvider>) -> Self {
+        let config_path = eframe::storage_dir(APP_NAME).map(|dir| dir.join("app.ron"));
+        let raw = cc.storage.and_then(|storage| storage.get_string(eframe::APP_KEY));
+        let mut app = config::load(raw.as_deref(), config_path.as_deref());
+        if app.tabs.is_empty() {
+            app.tabs.push(WorkspaceTab::default());
+        }
+        app.active_tab = app.active_tab.min(app.tabs.len() - 1);
+        app.load_sync_secret();
+        // Рядки для відображення не зберігаються - перераховуємо для відновлених серверів
+        let views = std::iter::once(&mut app.view).chain(app.tabs.iter_mut().map(|tab| &mut tab.view));
+        for view in views {
+            for server in view.servers.iter_mut() {
+                *server = std::mem::take(server).with_text();
+                server.first_seen = app.first_seen.first_seen(&server.id);
+                server.prime_time = app.prime_times.prime_time(&server.id, &server.country);
+            }
+            view.update_badges();
+        }
+        for server in app.pinned.iter_mut() {
+            *server = std::mem::take(server).with_text();
+            server.first_seen = app.first_seen.first_seen(&server.id);
+            server.prime_time = app.prime_times.prime_time(&server.id, &server.country);
         }
+        // Відновлені результати минулої сесії: версія потрібна для позначки "застаріла"
+        let restored = app.view.servers.iter().chain(app.tabs.iter().flat_map(|tab| tab.view.servers.iter()));
+        for server in restored {
+            app.versions.record(&server.id, &server.version);
+        }
+        app.versions.update();
+        app.provider = Some(Arc::from(provider));
+        app.translations = Translations::load();
+        app.launch = launch;
+        app.autostart = autostart::is_enabled();
+        app.watcher = FileWatcher::new(cc.egui_ctx.clone());
+        app.control = ControlServer::start(cc.egui_ctx.clone());
+        app.deck = DeckServer::start(cc.egui_ctx.clone());
+        app.egui_ctx = cc.egui_ctx.clone();
+        app.config_path = config_path;
+        app.layer_db = Arc::new(LayerDb::load(app.layer_db_path().as_deref()));
+        app
     }
 
-    fn run_scan(&mut self, next_page_url: Option<String>) {
-        if self.is_loading { return; }
-
-        self.is_loading = true;
-        
-        if next_page_url.is_none() {
-            self.servers.clear();
+    fn tr(&self, key: &'static str) -> &'static str {
+        let text = match &self.language {
+            // Чого немає у файлі перекладу - англійською
+            Language::Custom(code) => {
+                self.translations.get(code).and_then(|t| t.text(key)).unwrap_or_else(|| Self::builtin_tr(key, &Language::En))
+            }
+            language => Self::builtin_tr(key, language),
+        };
+        match self.direction() {
+            Direction::Ltr => text,
+            Direction::Rtl => bidi::visual_static(text),
         }
-        
-        let (tx, rx): (Sender<ScanResult>, Receiver<ScanResult>) = channel();
-        self.rx = Some(rx);
-
-        let min_p = self.min_players;
-        let max_p = self.max_players;
-        let banned = self.banned_countries.clone();
-        let f_name = self.filter_name.clone();
-        let f_map = self.filter_map.clone();
-        let f_mode = self.filter_mode.clone();
-        let url_arg = next_page_url.unwrap_or_default();
+    }
 
-        thread::spawn(move || {
-            let result = fetch_servers(min_p, max_p, banned, f_name, f_map, f_mode, url_arg);
-            let _ = tx.send(result);
-        });
+    fn direction(&self) -> Direction {
+        if self.launch.rtl {
+            return Direction::Rtl;
+        }
+        match &self.language {
+            // Нова вбудована мова має явно вказати напрямок письма
+            Language::En | Language::Ua => Direction::Ltr,
+            Language::Custom(code) => self.translations.get(code).map_or(Direction::Ltr, |t| t.direction),
+        }
     }
-}
 
-impl eframe::App for SquadApp {
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+    fn builtin_tr(key: &'static str, language: &Language) -> &'static str {
+        match (key, language) {
+            ("app_title", Language::En) => "Squad Browser",
+            ("app_title", Language::Ua) => "Пошук Серверів Squad",
+            ("settings", Language::En) => "Settings",
+            ("settings", Language::Ua) => "Налаштування",
+            ("start", Language::En) => "START SCAN",
+            ("start", Language::Ua) => "ПОЧАТИ ПОШУК",
+            ("refresh", Language::En) => "REFRESH",
+            ("refresh", Language::Ua) => "ОНОВИТИ",
+            ("found", Language::En) => "Servers:",
+            ("found", Language::Ua) => "Серверів:",
+            ("no_servers", Language::En) => "No servers found.",
+            ("no_servers", Language::Ua) => "Серверів не знайдено.",
+            ("conf_title", Language::En) => "Configuration",
+            ("conf_title", Language::Ua) => "Конфігурація",
+            ("min_p", Language::En) => "Min Players:",
+            ("min_p", Language::Ua) => "Мін. Гравців:",
+            ("max_p", Language::En) => "Max Players:",
+            ("max_p", Language::Ua) => "Макс. Гравців:",
+            ("join", Language::En) => "Join",
+            ("join", Language::Ua) => "Приєднатись",
+            ("playtime", Language::En) => "Playtime",
+            ("playtime", Language::Ua) => "Час гри",
+            ("playing_on", Language::En) => "Playing on",
+            ("playing_on", Language::Ua) => "Граєте на",
+            ("no_sessions", Language::En) => "No sessions yet. Join a server from its details to start tracking.",
+            ("no_sessions", Language::Ua) => "Сесій ще немає. Приєднайтесь до сервера з його деталей, щоб почати облік.",
+            ("weekly_summary", Language::En) => "Last 7 days",
+            ("weekly_summary", Language::Ua) => "Останні 7 днів",
+            ("sessions", Language::En) => "sessions",
+            ("sessions", Language::Ua) => "сесій",
+            ("exported_to", Language::En) => "Saved to",
+            ("exported_to", Language::Ua) => "Збережено у",
+            ("rotation", Language::En) => "Rotation:",
+            ("rotation", Language::Ua) => "Ротація:",
+            ("rotation_seen", Language::En) => "Recent maps:",
+            ("rotation_seen", Language::Ua) => "Останні карти:",
+            ("went_offline", Language::En) => "went offline",
+            ("went_offline", Language::Ua) => "вимкнувся",
+            ("came_online", Language::En) => "is back online",
+            ("came_online", Language::Ua) => "знову онлайн",
+            ("pin_offline", Language::En) => "Server is offline",
+            ("pin_offline", Language::Ua) => "Сервер офлайн",
+            ("region_asia", Language::En) => "Asia",
+            ("region_asia", Language::Ua) => "Азія",
+            ("region_south_america", Language::En) => "South America",
+            ("region_south_america", Language::Ua) => "Південна Америка",
+            ("region_oceania", Language::En) => "Oceania",
+            ("region_oceania", Language::Ua) => "Океанія",
+            ("region_europe", Language::En) => "Europe",
+            ("region_europe", Language::Ua) => "Європа",
+            ("region_north_america", Language::En) => "North America",
+            ("region_north_america", Language::Ua) => "Північна Америка",
+            ("region_ban", Language::En) => "Ban every country of the region",
+            ("region_ban", Language::Ua) => "Заблокувати всі країни регіону",
+            ("region_unban", Language::En) => "Whole region is banned, click to unban",
+            ("region_unban", Language::Ua) => "Регіон заблоковано повністю, натисніть, щоб розблокувати",
+            ("region_partial", Language::En) => "Partially banned, click to ban the rest",
+            ("region_partial", Language::Ua) => "Заблоковано частково, натисніть, щоб заблокувати решту",
+            ("seeding_title", Language::En) => "🌱 Seeding signals",
+            ("seeding_title", Language::Ua) => "🌱 Сигнали сідування",
+            ("seeding_url", Language::En) => "Coordination URL:",
+            ("seeding_url", Language::Ua) => "Адреса координації:",
+            ("seeding_alert", Language::En) => "Notify when a community starts seeding",
+            ("seeding_alert", Language::Ua) => "Сповіщати, коли спільнота починає сідування",
+            ("seeding_hint", Language::En) => "JSON list of {\"server_id\", \"community\", \"message\", \"started\", \"until\"}; servers in it get a badge",
+            ("seeding_hint", Language::Ua) => "JSON-список {\"server_id\", \"community\", \"message\", \"started\", \"until\"}; сервери з нього отримують значок",
+            ("seeding_now", Language::En) => "Seeding now:",
+            ("seeding_now", Language::Ua) => "Зараз сідують:",
+            ("seeding_started", Language::En) => "started seeding",
+            ("seeding_started", Language::Ua) => "почали сідувати",
+            ("seeding_someone", Language::En) => "A community",
+            ("seeding_someone", Language::Ua) => "Спільнота",
+            ("announcements_title", Language::En) => "📣 Announcements",
+            ("announcements_title", Language::Ua) => "📣 Оголошення",
+            ("announcements_url", Language::En) => "Feed URL:",
+            ("announcements_url", Language::Ua) => "Адреса стрічки:",
+            ("announcements_reload", Language::En) => "Reload",
+            ("announcements_reload", Language::Ua) => "Оновити",
+            ("announcements_hint", Language::En) => "JSON or RSS/Atom from your community, checked every 30 minutes. New items show as a banner above the list.",
+            ("announcements_hint", Language::Ua) => "JSON або RSS/Atom вашої спільноти, перевіряється кожні 30 хвилин. Нові записи з'являються банером над списком.",
+            ("announcements_dismiss", Language::En) => "Dismiss",
+            ("announcements_dismiss", Language::Ua) => "Закрити",
+            ("quiet_title", Language::En) => "🔕 Quiet Hours",
+            ("quiet_title", Language::Ua) => "🔕 Тихі Години",
+            ("quiet_enabled", Language::En) => "Suppress notifications",
+            ("quiet_enabled", Language::Ua) => "Не показувати сповіщення",
+            ("quiet_hint", Language::En) => "Local time. Alerts are still recorded in the 🔔 notification center.",
+            ("quiet_hint", Language::Ua) => "Місцевий час. Сповіщення все одно записуються в центр сповіщень 🔔.",
+            ("notifications", Language::En) => "Notifications",
+            ("notifications", Language::Ua) => "Сповіщення",
+            ("no_notifications", Language::En) => "No notifications yet.",
+            ("no_notifications", Language::Ua) => "Сповіщень ще немає.",
+            ("clear", Language::En) => "Clear",
+            ("clear", Language::Ua) => "Очистити",
+            ("map_changed", Language::En) => "map changed to",
+            ("map_changed", Language::Ua) => "карта змінилась на",
+            ("skin_title", Language::En) => "🪟 Compact Overlay",
+            ("skin_title", Language::Ua) => "🪟 Компактний Режим",
+            ("skin_enabled", Language::En) => "Semi-transparent window on top of the game",
+            ("skin_enabled", Language::Ua) => "Напівпрозоре вікно поверх гри",
+            ("skin_opacity", Language::En) => "Opacity:",
+            ("skin_opacity", Language::Ua) => "Непрозорість:",
+            ("skin_click_through", Language::En) => "Click-through",
+            ("skin_click_through", Language::Ua) => "Пропускати кліки крізь вікно",
+            ("skin_hint", Language::En) => "F9 toggles the compact mode. With click-through on, Alt+Tab to the window and press F9 to leave it.",
+            ("skin_hint", Language::Ua) => "F9 вмикає і вимикає режим. З пропуском кліків перейдіть у вікно через Alt+Tab і натисніть F9.",
+            ("skin_exit", Language::En) => "Back to the full window (F9)",
+            ("skin_exit", Language::Ua) => "Повернутись до повного вікна (F9)",
+            ("reset", Language::En) => "Reset",
+            ("reset", Language::Ua) => "Скинути",
+            ("reset_filters", Language::En) => "Filters",
+            ("reset_filters", Language::Ua) => "Фільтри",
+            ("reset_blacklist", Language::En) => "Blacklist",
+            ("reset_blacklist", Language::Ua) => "Чорний список",
+            ("reset_appearance", Language::En) => "Appearance",
+            ("reset_appearance", Language::Ua) => "Вигляд",
+            ("reset_everything", Language::En) => "Everything",
+            ("reset_everything", Language::Ua) => "Усе",
+            ("layouts_title", Language::En) => "🗔 Layout",
+            ("layouts_title", Language::Ua) => "🗔 Макет вікна",
+            ("density", Language::En) => "Density:",
+            ("density", Language::Ua) => "Щільність:",
+            ("density_comfortable", Language::En) => "Comfortable",
+            ("density_comfortable", Language::Ua) => "Звичайна",
+            ("density_compact", Language::En) => "Compact",
+            ("density_compact", Language::Ua) => "Щільна",
+            ("quick_actions", Language::En) => "Quick action buttons in server rows:",
+            ("quick_actions", Language::Ua) => "Кнопки швидких дій у рядках серверів:",
+            ("quick_join", Language::En) => "Join",
+            ("quick_join", Language::Ua) => "Зайти",
+            ("quick_favorite", Language::En) => "Favorite",
+            ("quick_favorite", Language::Ua) => "В обране",
+            ("quick_hide", Language::En) => "Hide this server (adds a block rule)",
+            ("quick_hide", Language::Ua) => "Сховати сервер (додає правило блокування)",
+            ("quick_copy_address", Language::En) => "Copy address",
+            ("quick_copy_address", Language::Ua) => "Копіювати адресу",
+            ("quick_copy_name", Language::En) => "Copy name",
+            ("quick_copy_name", Language::Ua) => "Копіювати назву",
+            ("row_columns", Language::En) => "Show in server rows:",
+            ("row_columns", Language::Ua) => "Показувати в рядках серверів:",
+            ("column_city", Language::En) => "City",
+            ("column_city", Language::Ua) => "Місто",
+            ("home_city", Language::En) => "Your city (for distance):",
+            ("home_city", Language::Ua) => "Ваше місто (для відстані):",
+            ("home_city_none", Language::En) => "Not set",
+            ("home_city_none", Language::Ua) => "Не вибрано",
+            ("distance", Language::En) => "Distance:",
+            ("distance", Language::Ua) => "Відстань:",
+            ("column_rank", Language::En) => "Top N% badge",
+            ("column_rank", Language::Ua) => "Значок топ N%",
+            ("rank_country", Language::En) => "Top {p}% most active {c} server",
+            ("rank_country", Language::Ua) => "Топ-{p}% найактивніших серверів {c}",
+            ("rank_overall", Language::En) => "Top {p}% most active server",
+            ("rank_overall", Language::Ua) => "Топ-{p}% найактивніших серверів",
+            ("column_country", Language::En) => "Country",
+            ("column_country", Language::Ua) => "Країна",
+            ("column_map_mode", Language::En) => "Map and mode",
+            ("column_map_mode", Language::Ua) => "Карта і режим",
+            ("column_queue", Language::En) => "Queue",
+            ("column_queue", Language::Ua) => "Черга",
+            ("column_trend", Language::En) => "Trend",
+            ("column_trend", Language::Ua) => "Тренд",
+            ("column_delta", Language::En) => "Player change",
+            ("column_delta", Language::Ua) => "Зміна гравців",
+            ("column_seed_eta", Language::En) => "Seeding ETA",
+            ("column_seed_eta", Language::Ua) => "Час до заповнення",
+            ("layouts_saved", Language::En) => "Saved layouts:",
+            ("layouts_saved", Language::Ua) => "Збережені макети:",
+            ("layouts_hint", Language::En) => "A layout stores the open section, sidebar width, density, row columns, the notification panel and compact overlay mode",
+            ("layouts_hint", Language::Ua) => "Макет зберігає відкритий розділ, ширину бокової панелі, щільність, колонки рядків, панель сповіщень і компактний оверлей",
+            ("layout_name", Language::En) => "Layout name",
+            ("layout_name", Language::Ua) => "Назва макета",
+            ("layout_save", Language::En) => "Save current layout",
+            ("layout_save", Language::Ua) => "Зберегти поточний макет",
+            ("cmd_apply_layout", Language::En) => "Switch layout",
+            ("cmd_apply_layout", Language::Ua) => "Перемкнути макет",
+            ("backup_title", Language::En) => "💾 Backup",
+            ("backup_title", Language::Ua) => "💾 Резервна копія",
+            ("backup_path", Language::En) => "Backup file:",
+            ("backup_path", Language::Ua) => "Файл копії:",
+            ("export_servers", Language::En) => "Export list",
+            ("export_servers", Language::Ua) => "Експорт списку",
+            ("export_columns", Language::En) => "Columns:",
+            ("export_columns", Language::Ua) => "Колонки:",
+            ("export_extended_hint", Language::En) => "Ping, uptime, trend and playtime are empty for servers without collected data",
+            ("export_extended_hint", Language::Ua) => "Пінг, доступність, тренд і час гри порожні для серверів без зібраних даних",
+            ("export_format", Language::En) => "Format:",
+            ("export_format", Language::Ua) => "Формат:",
+            ("export_file", Language::En) => "File (without extension):",
+            ("export_file", Language::Ua) => "Файл (без розширення):",
+            ("export_presets", Language::En) => "Presets:",
+            ("export_presets", Language::Ua) => "Пресети:",
+            ("export_preset_name", Language::En) => "Preset name",
+            ("export_preset_name", Language::Ua) => "Назва пресету",
+            ("export_save_preset", Language::En) => "Save preset",
+            ("export_save_preset", Language::Ua) => "Зберегти пресет",
+            ("export_run", Language::En) => "Export",
+            ("export_run", Language::Ua) => "Експортувати",
+            ("backup_create", Language::En) => "Backup all data",
+            ("backup_create", Language::Ua) => "Зберегти всі дані",
+            ("backup_restore", Language::En) => "Restore…",
+            ("backup_restore", Language::Ua) => "Відновити…",
+            ("backup_hint", Language::En) => "One zip with settings, favorites, history, rules and events.",
+            ("backup_hint", Language::Ua) => "Один zip з налаштуваннями, обраним, історією, правилами й подіями.",
+            ("restore_title", Language::En) => "Restore backup",
+            ("restore_title", Language::Ua) => "Відновлення копії",
+            ("restore_new", Language::En) => "new",
+            ("restore_new", Language::Ua) => "нових",
+            ("restore_searches", Language::En) => "Saved searches",
+            ("restore_searches", Language::Ua) => "Збережені пошуки",
+            ("restore_smart_lists", Language::En) => "Smart lists",
+            ("restore_smart_lists", Language::Ua) => "Розумні списки",
+            ("restore_sessions", Language::En) => "Play sessions",
+            ("restore_sessions", Language::Ua) => "Ігрові сесії",
+            ("restore_hint", Language::En) => "Replace: everything from the backup. Merge: adds only new entries, on conflicts the current ones stay.",
+            ("restore_hint", Language::Ua) => "Замінити: усе з копії. Об'єднати: додає лише нові записи, при конфліктах лишаються поточні.",
+            ("restore_replace", Language::En) => "Replace all",
+            ("restore_replace", Language::Ua) => "Замінити все",
+            ("restore_merge", Language::En) => "Merge",
+            ("restore_merge", Language::Ua) => "Об'єднати",
+            ("restore_done", Language::En) => "Backup restored.",
+            ("restore_done", Language::Ua) => "Копію відновлено.",
+            ("cmd_hint", Language::En) => "Type a command…",
+            ("cmd_hint", Language::Ua) => "Введіть команду…",
+            ("cmd_refresh", Language::En) => "Refresh",
+            ("cmd_refresh", Language::Ua) => "Оновити",
+            ("cmd_refresh_all", Language::En) => "Refresh all tabs",
+            ("cmd_refresh_all", Language::Ua) => "Оновити всі вкладки",
+            ("cmd_go_to", Language::En) => "Go to",
+            ("cmd_go_to", Language::Ua) => "Перейти",
+            ("cmd_switch_tab", Language::En) => "Switch to tab",
+            ("cmd_switch_tab", Language::Ua) => "Перейти на вкладку",
+            ("cmd_load_search", Language::En) => "Load search",
+            ("cmd_load_search", Language::Ua) => "Завантажити пошук",
+            ("cmd_open_favorite", Language::En) => "Open favorite",
+            ("cmd_open_favorite", Language::Ua) => "Відкрити обраний",
+            ("cmd_join_favorite", Language::En) => "Join favorite",
+            ("cmd_join_favorite", Language::Ua) => "Приєднатися до обраного",
+            ("cmd_remove_filter", Language::En) => "Remove filter",
+            ("cmd_remove_filter", Language::Ua) => "Прибрати фільтр",
+            ("cmd_toggle_anti_cheat", Language::En) => "Toggle filter: anti-cheat only",
+            ("cmd_toggle_anti_cheat", Language::Ua) => "Перемкнути фільтр: лише з античитом",
+            ("cmd_toggle_freeze", Language::En) => "Toggle frozen order",
+            ("cmd_toggle_freeze", Language::Ua) => "Перемкнути фіксований порядок",
+            ("cmd_toggle_compact", Language::En) => "Toggle compact overlay (F9)",
+            ("cmd_toggle_compact", Language::Ua) => "Перемкнути компактний режим (F9)",
+            ("cmd_toggle_language", Language::En) => "Switch language",
+            ("cmd_toggle_language", Language::Ua) => "Змінити мову",
+            ("cmd_export_rules", Language::En) => "Export rules",
+            ("cmd_export_rules", Language::Ua) => "Експортувати правила",
+            ("cmd_export_calendar", Language::En) => "Export events calendar",
+            ("cmd_export_calendar", Language::Ua) => "Експортувати календар подій",
+            ("cmd_export_weekly", Language::En) => "Export weekly summary",
+            ("cmd_export_weekly", Language::Ua) => "Експортувати тижневий підсумок",
+            ("sync_title", Language::En) => "☁ Sync",
+            ("sync_title", Language::Ua) => "☁ Синхронізація",
+            ("sync_enabled", Language::En) => "Sync profile between devices",
+            ("sync_enabled", Language::Ua) => "Синхронізувати профіль між пристроями",
+            ("sync_file_url", Language::En) => "File URL:",
+            ("sync_file_url", Language::Ua) => "URL файлу:",
+            ("sync_gist_id", Language::En) => "Gist ID:",
+            ("sync_gist_id", Language::Ua) => "ID гіста:",
+            ("sync_username", Language::En) => "Username:",
+            ("sync_username", Language::Ua) => "Логін:",
+            ("sync_password", Language::En) => "Password:",
+            ("sync_password", Language::Ua) => "Пароль:",
+            ("sync_access_key", Language::En) => "Access key:",
+            ("sync_access_key", Language::Ua) => "Access key:",
+            ("sync_secret_key", Language::En) => "Secret key:",
+            ("sync_secret_key", Language::Ua) => "Secret key:",
+            ("sync_token", Language::En) => "Token:",
+            ("sync_token", Language::Ua) => "Токен:",
+            ("sync_region", Language::En) => "Region:",
+            ("sync_region", Language::Ua) => "Регіон:",
+            ("sync_device", Language::En) => "Device name:",
+            ("sync_device", Language::Ua) => "Назва пристрою:",
+            ("sync_ask", Language::En) => "Ask when both devices changed (otherwise the newest wins)",
+            ("sync_ask", Language::Ua) => "Питати, коли змінились обидва пристрої (інакше перемагає новіший)",
+            ("sync_now", Language::En) => "Sync now",
+            ("sync_now", Language::Ua) => "Синхронізувати зараз",
+            ("sync_hint", Language::En) => "Syncs filters, favorites, saved searches, smart lists, rules and events. The password or token is kept in the system credential store, not in the settings file or backups.",
+            ("sync_hint", Language::Ua) => "Синхронізуються фільтри, обране, збережені пошуки, розумні списки, правила й події. Пароль чи токен зберігається в системному сховищі облікових даних, а не у файлі налаштувань чи бекапах.",
+            ("sync_secret_failed", Language::En) => "Could not save the password to the system credential store; it is kept for this session only:",
+            ("sync_secret_failed", Language::Ua) => "Не вдалося зберегти пароль у системному сховищі; він діятиме лише до закриття програми:",
+            ("sync_done", Language::En) => "Synced at",
+            ("sync_done", Language::Ua) => "Синхронізовано о",
+            ("sync_conflict_title", Language::En) => "Sync conflict",
+            ("sync_conflict_title", Language::Ua) => "Конфлікт синхронізації",
+            ("sync_conflict", Language::En) => "The profile changed both here and on another device.",
+            ("sync_conflict", Language::Ua) => "Профіль змінився і тут, і на іншому пристрої.",
+            ("sync_this_device", Language::En) => "This device",
+            ("sync_this_device", Language::Ua) => "Цей пристрій",
+            ("sync_remote", Language::En) => "Remote from",
+            ("sync_remote", Language::Ua) => "Віддалений з",
+            ("sync_keep_local", Language::En) => "Keep this device's",
+            ("sync_keep_local", Language::Ua) => "Лишити з цього пристрою",
+            ("sync_use_remote", Language::En) => "Use remote",
+            ("sync_use_remote", Language::Ua) => "Взяти віддалений",
+            ("reset_confirm_title", Language::En) => "Reset settings",
+            ("reset_confirm_title", Language::Ua) => "Скидання налаштувань",
+            ("reset_confirm", Language::En) => "Restore defaults for",
+            ("reset_confirm", Language::Ua) => "Повернути типові значення для",
+            ("cancel", Language::En) => "Cancel",
+            ("cancel", Language::Ua) => "Скасувати",
+            ("warn_min_max", Language::En) => "Min players is above max players: no server can match.",
+            ("warn_min_max", Language::Ua) => "Мінімум гравців більший за максимум: жоден сервер не підійде.",
+            ("warn_country_allowed", Language::En) => "is disabled, but a !country rule allows it: the rule wins and its servers are shown.",
+            ("warn_country_allowed", Language::Ua) => "заблоковано, але правило !country дозволяє її: діє правило, сервери показуються.",
+            ("warn_name_blocked", Language::En) => "is blocked by a name rule, so the name search will find nothing.",
+            ("warn_name_blocked", Language::Ua) => "блокується правилом назви, тому пошук за назвою нічого не знайде.",
+            ("search_name", Language::En) => "Server Name:",
+            ("search_name", Language::Ua) => "Назва Сервера:",
+            ("map", Language::En) => "Map Name:",
+            ("map", Language::Ua) => "Назва Карти:",
+            ("mode", Language::En) => "Game Mode:",
+            ("mode", Language::Ua) => "Режим Гри:",
+            ("lang", Language::En) => "Language:",
+            ("lang", Language::Ua) => "Мова:",
+            ("bl_title", Language::En) => "🚫 Disabled Countries",
+            ("bl_title", Language::Ua) => "🚫 Заблоковані Країни",
+            ("scanning", Language::En) => "Scanning...",
+            ("scanning", Language::Ua) => "Пошук...",
+            ("loading_more", Language::En) => "Loading more...",
+            ("loading_more", Language::Ua) => "Підвантажую ще...",
+            ("ready", Language::En) => "Ready",
+            ("ready", Language::Ua) => "Готовий",
+            ("overlay_title", Language::En) => "📺 Stream Overlay",
+            ("overlay_title", Language::Ua) => "📺 Оверлей для Стріму",
+            ("overlay_enabled", Language::En) => "Write overlay file",
+            ("overlay_enabled", Language::Ua) => "Записувати файл оверлею",
+            ("overlay_path", Language::En) => "File:",
+            ("overlay_path", Language::Ua) => "Файл:",
+            ("overlay_format", Language::En) => "Format:",
+            ("overlay_format", Language::Ua) => "Формат:",
+            ("overlay_template", Language::En) => "Template:",
+            ("overlay_template", Language::Ua) => "Шаблон:",
+            ("overlay_hint", Language::En) => "Click a server to show it on stream.",
+            ("overlay_hint", Language::Ua) => "Клікніть на сервер, щоб показати його на стрімі.",
+            ("rules_title", Language::En) => "📜 Block Rules",
+            ("rules_title", Language::Ua) => "📜 Правила Блокування",
+            ("rules_active", Language::En) => "Active rules:",
+            ("rules_active", Language::Ua) => "Активних правил:",
+            ("rules_file", Language::En) => "Rules file:",
+            ("rules_file", Language::Ua) => "Файл правил:",
+            ("rules_watch", Language::En) => "Auto-reload",
+            ("rules_watch", Language::Ua) => "Автооновлення",
+            ("rules_loaded", Language::En) => "Rules loaded:",
+            ("rules_loaded", Language::Ua) => "Завантажено правил:",
+            ("rules_saved", Language::En) => "Rules saved.",
+            ("rules_saved", Language::Ua) => "Правила збережено.",
+            ("scan_failed", Language::En) => "Scan failed.",
+            ("scan_failed", Language::Ua) => "Помилка пошуку.",
+            ("scan_partial", Language::En) => "Partial results: a page failed to load.",
+            ("scan_partial", Language::Ua) => "Неповні результати: сторінка не завантажилась.",
+            ("retry", Language::En) => "Retry missing pages",
+            ("retry", Language::Ua) => "Довантажити решту",
+            ("row_stale", Language::En) => "not updated by this refresh yet",
+            ("row_stale", Language::Ua) => "ще не оновлено цим оновленням",
+            ("freeze_order", Language::En) => "Freeze order",
+            ("freeze_order", Language::Ua) => "Зафіксувати порядок",
+            ("resort", Language::En) => "Re-sort",
+            ("resort", Language::Ua) => "Пересортувати",
+            ("country", Language::En) => "Country:",
+            ("country", Language::Ua) => "Країна:",
+            ("copy_address", Language::En) => "Copy address",
+            ("copy_address", Language::Ua) => "Копіювати адресу",
+            ("address", Language::En) => "Address:",
+            ("address", Language::Ua) => "Адреса:",
+            ("query_port", Language::En) => "Query port:",
+            ("query_port", Language::Ua) => "Query-порт:",
+            ("copy_name", Language::En) => "Copy name",
+            ("copy_name", Language::Ua) => "Копіювати назву",
+            ("single_expand", Language::En) => "Expand one server at a time",
+            ("single_expand", Language::Ua) => "Розгортати лише один сервер",
+            ("pinned", Language::En) => "Pinned",
+            ("pinned", Language::Ua) => "Закріплені",
+            ("pin", Language::En) => "Pin",
+            ("pin", Language::Ua) => "Закріпити",
+            ("unpin", Language::En) => "Unpin",
+            ("unpin", Language::Ua) => "Відкріпити",
+            ("pin_unmatched", Language::En) => "Doesn't match filters",
+            ("pin_unmatched", Language::Ua) => "Не відповідає фільтрам",
+            ("pin_missing", Language::En) => "Not in current results",
+            ("pin_missing", Language::Ua) => "Немає в поточних результатах",
+            ("delta_hint", Language::En) => "Change in players since the previous refresh",
+            ("delta_hint", Language::Ua) => "Зміна кількості гравців з минулого оновлення",
+            ("trend_hint", Language::En) => "Player trend over the last 10 minutes",
+            ("trend_hint", Language::Ua) => "Тенденція онлайну за останні 10 хвилин",
+            ("seed_eta", Language::En) => "players in",
+            ("seed_eta", Language::Ua) => "гравців через",
+            ("minutes", Language::En) => "min",
+            ("minutes", Language::Ua) => "хв",
+            ("seed_threshold", Language::En) => "Seeded at:",
+            ("seed_threshold", Language::Ua) => "Сід завершено при:",
+            ("queue", Language::En) => "queue",
+            ("queue", Language::Ua) => "черга",
+            ("queue_hint", Language::En) => "Public + reserved queue",
+            ("queue_hint", Language::Ua) => "Загальна + резервна черга",
+            ("queue_public", Language::En) => "Public queue:",
+            ("queue_public", Language::Ua) => "Загальна черга:",
+            ("queue_reserved", Language::En) => "Reserved queue:",
+            ("queue_reserved", Language::Ua) => "Резервна черга:",
+            ("version", Language::En) => "Version:",
+            ("version", Language::Ua) => "Версія:",
+            ("target", Language::En) => "Target",
+            ("target", Language::Ua) => "Ціль",
+            ("untarget", Language::En) => "Targeted",
+            ("untarget", Language::Ua) => "У цілях",
+            ("target_hint", Language::En) => "Add to the \"join any of these\" list",
+            ("target_hint", Language::Ua) => "Додати до списку \"зайти на будь-який з цих\"",
+            ("target_any", Language::En) => "Join any of these servers:",
+            ("target_any", Language::Ua) => "Зайти на будь-який з серверів:",
+            ("target_start", Language::En) => "Wait for a slot",
+            ("target_start", Language::Ua) => "Чекати на місце",
+            ("target_stop", Language::En) => "Stop",
+            ("target_stop", Language::Ua) => "Зупинити",
+            ("target_clear", Language::En) => "Clear",
+            ("target_clear", Language::Ua) => "Очистити",
+            ("target_auto_join", Language::En) => "Join automatically",
+            ("target_auto_join", Language::Ua) => "Заходити автоматично",
+            ("target_checked", Language::En) => "checked",
+            ("target_checked", Language::Ua) => "перевірено",
+            ("target_free", Language::En) => "Free slot",
+            ("target_free", Language::Ua) => "Є місце",
+            ("first_seen", Language::En) => "First seen:",
+            ("first_seen", Language::Ua) => "Вперше помічено:",
+            ("new_server", Language::En) => "New server",
+            ("new_server", Language::Ua) => "Новий сервер",
+            ("today", Language::En) => "today",
+            ("today", Language::Ua) => "сьогодні",
+            ("days_ago", Language::En) => "{d} days ago",
+            ("days_ago", Language::Ua) => "{d} дн. тому",
+            ("smart_new_week", Language::En) => "New servers this week",
+            ("smart_new_week", Language::Ua) => "Нові сервери за тиждень",
+            ("prime_time", Language::En) => "Prime time:",
+            ("prime_time", Language::Ua) => "Прайм-тайм:",
+            ("prime_now", Language::En) => "Prime time",
+            ("prime_now", Language::Ua) => "Прайм-тайм",
+            ("prime_history", Language::En) => "your time, from player history",
+            ("prime_history", Language::Ua) => "ваш час, за історією онлайну",
+            ("prime_region", Language::En) => "your time, estimated from the server's country",
+            ("prime_region", Language::Ua) => "ваш час, оцінка за країною сервера",
+            ("smart_evenings", Language::En) => "Active during my evenings",
+            ("smart_evenings", Language::Ua) => "Активні моїми вечорами",
+            ("uptime", Language::En) => "Uptime",
+            ("uptime", Language::Ua) => "Доступність",
+            ("uptime_checks", Language::En) => "checks",
+            ("uptime_checks", Language::Ua) => "перевірок",
+            ("layer_info", Language::En) => "Layer info",
+            ("layer_info", Language::Ua) => "Про шар",
+            ("layer_team1", Language::En) => "Team 1 vehicles",
+            ("layer_team1", Language::Ua) => "Техніка команди 1",
+            ("layer_team2", Language::En) => "Team 2 vehicles",
+            ("layer_team2", Language::Ua) => "Техніка команди 2",
+            ("layer_no_vehicles", Language::En) => "none",
+            ("layer_no_vehicles", Language::Ua) => "немає",
+            ("patch_title", Language::En) => "🛠 Patch day",
+            ("patch_title", Language::Ua) => "🛠 День патча",
+            ("patch_detected", Language::En) => "Squad update shipped:",
+            ("patch_detected", Language::Ua) => "Вийшло оновлення Squad:",
+            ("patch_mode_on", Language::En) => "Patch-day mode",
+            ("patch_mode_on", Language::Ua) => "Режим дня патча",
+            ("patch_mode_enter", Language::En) => "Switch to patch-day mode",
+            ("patch_mode_enter", Language::Ua) => "Увімкнути режим дня патча",
+            ("patch_mode_exit", Language::En) => "Back to normal",
+            ("patch_mode_exit", Language::Ua) => "Звичайний режим",
+            ("patch_mode_hint", Language::En) => "Shows each server's version, hides servers that haven't updated yet and ignores player and queue filters. Ends 24 hours after the update.",
+            ("patch_mode_hint", Language::Ua) => "Показує версію кожного сервера, ховає ще не оновлені сервери і не застосовує фільтри гравців і черги. Закінчується через 24 години після оновлення.",
+            ("patch_auto", Language::En) => "Switch on automatically when Steam news announces an update",
+            ("patch_auto", Language::Ua) => "Вмикати самостійно, коли в новинах Steam з'являється оновлення",
+            ("patch_last", Language::En) => "Last update:",
+            ("patch_last", Language::Ua) => "Останнє оновлення:",
+            ("patch_none", Language::En) => "No update found in Steam news yet",
+            ("patch_none", Language::Ua) => "У новинах Steam оновлень поки не знайдено",
+            ("cmd_toggle_patch_day", Language::En) => "Toggle patch-day mode",
+            ("cmd_toggle_patch_day", Language::Ua) => "Перемкнути режим дня патча",
+            ("autostart", Language::En) => "Start minimized when I log in",
+            ("autostart", Language::Ua) => "Запускати згорнутою при вході в систему",
+            ("autostart_hint", Language::En) => "Keeps favorites monitoring and alerts running from login (Windows Run key, XDG autostart on Linux)",
+            ("autostart_hint", Language::Ua) => "Моніторинг обраного і сповіщення працюють одразу після входу (ключ Run у Windows, XDG autostart у Linux)",
+            ("links_title", Language::En) => "🔗 squadbrowser:// links",
+            ("links_title", Language::Ua) => "🔗 Посилання squadbrowser://",
+            ("links_hint", Language::En) => "Links like squadbrowser://server/12345 or squadbrowser://filter?map=narva open this app on that server or filter set. They never join a game.",
+            ("links_hint", Language::Ua) => "Посилання на кшталт squadbrowser://server/12345 чи squadbrowser://filter?map=narva відкривають програму на цьому сервері чи наборі фільтрів. У гру вони ніколи не заходять.",
+            ("links_register", Language::En) => "Open these links with this app",
+            ("links_register", Language::Ua) => "Відкривати такі посилання цією програмою",
+            ("links_registered", Language::En) => "Registered:",
+            ("links_registered", Language::Ua) => "Зареєстровано:",
+            ("links_copy_filters", Language::En) => "Copy link to current filters",
+            ("links_copy_filters", Language::Ua) => "Копіювати посилання на поточні фільтри",
+            ("links_copy_server", Language::En) => "Copy a squadbrowser:// link to this server",
+            ("links_copy_server", Language::Ua) => "Копіювати посилання squadbrowser:// на цей сервер",
+            ("layer_db_title", Language::En) => "🗺 Layer database",
+            ("layer_db_title", Language::Ua) => "🗺 База шарів",
+            ("layer_db_layers", Language::En) => "layers",
+            ("layer_db_layers", Language::Ua) => "шарів",
+            ("layer_db_embedded", Language::En) => "built-in",
+            ("layer_db_embedded", Language::Ua) => "вбудована",
+            ("layer_db_downloaded", Language::En) => "downloaded",
+            ("layer_db_downloaded", Language::Ua) => "завантажена",
+            ("layer_db_url", Language::En) => "Update URL:",
+            ("layer_db_url", Language::Ua) => "Адреса оновлення:",
+            ("layer_db_update", Language::En) => "Update",
+            ("layer_db_update", Language::Ua) => "Оновити",
+            ("layer_db_hint", Language::En) => "The downloaded file replaces the built-in starter set and is kept next to the settings file",
+            ("layer_db_hint", Language::Ua) => "Завантажений файл замінює вбудований стартовий набір і зберігається поруч із файлом налаштувань",
+            ("veto_title", Language::En) => "Map veto",
+            ("veto_title", Language::Ua) => "Вето карт",
+            ("veto_teams", Language::En) => "Teams:",
+            ("veto_teams", Language::Ua) => "Команди:",
+            ("veto_format", Language::En) => "Format:",
+            ("veto_format", Language::Ua) => "Формат:",
+            ("veto_bo1_hint", Language::En) => "Teams take turns banning until one map is left",
+            ("veto_bo1_hint", Language::Ua) => "Команди по черзі банять, доки не лишиться одна карта",
+            ("veto_bo3_hint", Language::En) => "Ban, ban, pick, pick, then bans until the decider is left",
+            ("veto_bo3_hint", Language::Ua) => "Бан, бан, вибір, вибір, далі бани до вирішальної карти",
+            ("veto_pool", Language::En) => "Map pool (one per line):",
+            ("veto_pool", Language::Ua) => "Пул карт (по одній на рядок):",
+            ("veto_start", Language::En) => "Start veto",
+            ("veto_start", Language::Ua) => "Почати вето",
+            ("veto_default_pool", Language::En) => "Default pool",
+            ("veto_default_pool", Language::Ua) => "Стандартний пул",
+            ("veto_maps", Language::En) => "maps",
+            ("veto_maps", Language::Ua) => "карт",
+            ("veto_bans", Language::En) => "bans",
+            ("veto_bans", Language::Ua) => "банить",
+            ("veto_picks", Language::En) => "picks",
+            ("veto_picks", Language::Ua) => "вибирає",
+            ("veto_done", Language::En) => "Maps:",
+            ("veto_done", Language::Ua) => "Карти:",
+            ("veto_undo", Language::En) => "Undo",
+            ("veto_undo", Language::Ua) => "Скасувати крок",
+            ("veto_new", Language::En) => "New veto",
+            ("veto_new", Language::Ua) => "Нове вето",
+            ("copy_text", Language::En) => "Copy as text",
+            ("copy_text", Language::Ua) => "Копіювати текстом",
+            ("traceroute", Language::En) => "Network diagnostics",
+            ("traceroute", Language::Ua) => "Діагностика мережі",
+            ("traceroute_run", Language::En) => "Traceroute",
+            ("traceroute_run", Language::Ua) => "Трасування",
+            ("traceroute_hint", Language::En) => "Shows every router on the way to the server with its latency and loss - useful to show your ISP where packet loss starts.",
+            ("traceroute_hint", Language::Ua) => "Показує кожен маршрутизатор на шляху до сервера з затримкою і втратами - зручно показати провайдеру, де починаються втрати.",
+            ("traceroute_host", Language::En) => "Host",
+            ("traceroute_host", Language::Ua) => "Вузол",
+            ("traceroute_loss_from", Language::En) => "Packet loss starts at hop",
+            ("traceroute_loss_from", Language::Ua) => "Втрати пакетів починаються з вузла",
+            ("traceroute_copy", Language::En) => "Copy as text",
+            ("traceroute_copy", Language::Ua) => "Копіювати текстом",
+            ("ping_avg", Language::En) => "Ping",
+            ("ping_avg", Language::Ua) => "Пінг",
+            ("ping_jitter", Language::En) => "jitter",
+            ("ping_jitter", Language::Ua) => "джитер",
+            ("ping_loss", Language::En) => "loss",
+            ("ping_loss", Language::Ua) => "втрати",
+            ("ping_history_hint", Language::En) => "Last hour, one A2S ping per minute. High jitter on every favorite points at your connection; on one server only, at that server.",
+            ("ping_history_hint", Language::Ua) => "Остання година, один A2S-пінг на хвилину. Високий джитер на всіх обраних - проблема у вашому з'єднанні; лише на одному - у сервері.",
+            ("uptime_hint", Language::En) => "Share of availability checks (every 30 s to 4 min while the app is running) that found the server online",
+            ("uptime_hint", Language::Ua) => "Частка перевірок (від 30 с до 4 хв, поки програма запущена), коли сервер був онлайн",
+            ("poll_every", Language::En) => "Checked every {s} s",
+            ("poll_every", Language::Ua) => "Перевіряється кожні {s} с",
+            ("poll_hint", Language::En) => "Servers whose player count changes quickly are checked more often, stable ones less often",
+            ("poll_hint", Language::Ua) => "Сервери, де онлайн швидко змінюється, перевіряються частіше, стабільні - рідше",
+            ("days_short", Language::En) => "d",
+            ("days_short", Language::Ua) => "д",
+            ("tooltip_players", Language::En) => "Players:",
+            ("tooltip_players", Language::Ua) => "Гравців:",
+            ("tooltip_ping", Language::En) => "Average ping:",
+            ("tooltip_ping", Language::Ua) => "Середній пінг:",
+            ("recent_maps", Language::En) => "Last maps:",
+            ("recent_maps", Language::Ua) => "Останні карти:",
+            ("tooltip_hint", Language::En) => "Click for details",
+            ("tooltip_hint", Language::Ua) => "Клікніть для деталей",
+            ("family", Language::En) => "Same community:",
+            ("family", Language::Ua) => "Та сама спільнота:",
+            ("family_pin", Language::En) => "Pin all",
+            ("family_pin", Language::Ua) => "Закріпити всі",
+            ("family_ban", Language::En) => "Ban all",
+            ("family_ban", Language::Ua) => "Забанити всі",
+            ("server_rules", Language::En) => "Server rules (A2S)",
+            ("server_rules", Language::Ua) => "Правила сервера (A2S)",
+            ("server_rules_query", Language::En) => "Query",
+            ("server_rules_query", Language::Ua) => "Запитати",
+            ("server_rules_empty", Language::En) => "The server returned no rules",
+            ("server_rules_empty", Language::Ua) => "Сервер не повернув жодного правила",
+            ("outdated", Language::En) => "Outdated version",
+            ("outdated", Language::Ua) => "Застаріла версія",
+            ("expected_version", Language::En) => "Game version:",
+            ("expected_version", Language::Ua) => "Версія гри:",
+            ("anticheat", Language::En) => "Anti-cheat:",
+            ("anticheat", Language::Ua) => "Античіт:",
+            ("obfuscated", Language::En) => "Obfuscated name",
+            ("obfuscated", Language::Ua) => "Замаскована назва",
+            ("obfuscated_hint", Language::En) => "Uses look-alike characters from other alphabets. Reads as:",
+            ("obfuscated_hint", Language::Ua) => "Містить схожі літери з інших абеток. Читається як:",
+            ("anticheat_off", Language::En) => "Anti-cheat disabled",
+            ("anticheat_off", Language::Ua) => "Античіт вимкнено",
+            ("filter_helicopters", Language::En) => "Layers with helicopters",
+            ("filter_helicopters", Language::Ua) => "Шари з гелікоптерами",
+            ("filter_armor", Language::En) => "Armor-heavy layers",
+            ("filter_armor", Language::Ua) => "Шари з великою кількістю броні",
+            ("filter_vehicles_hint", Language::En) => "Checked against the layer database; servers on layers it doesn't know are hidden",
+            ("filter_vehicles_hint", Language::Ua) => "Перевіряється за базою шарів; сервери на невідомих їй шарах ховаються",
+            ("hide_no_anticheat", Language::En) => "Hide servers without anti-cheat",
+            ("hide_no_anticheat", Language::Ua) => "Ховати сервери без античіту",
+            ("advanced_title", Language::En) => "🔧 Advanced Query",
+            ("advanced_title", Language::Ua) => "🔧 Розширений Запит",
+            ("advanced_hint", Language::En) => "Extra BattleMetrics API parameters, sent with every new search.",
+            ("advanced_hint", Language::Ua) => "Додаткові параметри BattleMetrics API для кожного нового пошуку.",
+            ("add_param", Language::En) => "Add parameter",
+            ("add_param", Language::Ua) => "Додати параметр",
+            ("url_preview", Language::En) => "Request URL:",
+            ("url_preview", Language::Ua) => "URL запиту:",
+            ("config_reloaded", Language::En) => "Settings reloaded from disk",
+            ("config_reloaded", Language::Ua) => "Налаштування оновлено з диску",
+            ("saved_searches", Language::En) => "Saved Searches",
+            ("saved_searches", Language::Ua) => "Збережені Пошуки",
+            ("save_search", Language::En) => "Save current",
+            ("save_search", Language::Ua) => "Зберегти поточний",
+            ("search_title", Language::En) => "Search name",
+            ("search_title", Language::Ua) => "Назва пошуку",
+            ("monitor_hint", Language::En) => "Check in the background every 5 minutes and notify about new servers",
+            ("monitor_hint", Language::Ua) => "Перевіряти у фоні кожні 5 хвилин і повідомляти про нові сервери",
+            ("monitor_new", Language::En) => "new",
+            ("monitor_new", Language::Ua) => "нових",
+            ("tab", Language::En) => "Tab",
+            ("tab", Language::Ua) => "Вкладка",
+            ("refresh_tab", Language::En) => "Refresh this tab",
+            ("refresh_tab", Language::Ua) => "Оновити цю вкладку",
+            ("refresh_all", Language::En) => "REFRESH ALL",
+            ("refresh_all", Language::Ua) => "ОНОВИТИ ВСЕ",
+            ("new_tab", Language::En) => "New tab",
+            ("new_tab", Language::Ua) => "Нова вкладка",
+            ("import", Language::En) => "Import",
+            ("import", Language::Ua) => "Імпорт",
+            ("export", Language::En) => "Export",
+            ("export", Language::Ua) => "Експорт",
+            ("smart_lists", Language::En) => "Smart Lists",
+            ("smart_lists", Language::Ua) => "Розумні Списки",
+            ("smart_name", Language::En) => "List name",
+            ("smart_name", Language::Ua) => "Назва списку",
+            ("smart_expr", Language::En) => "Expression",
+            ("smart_expr", Language::Ua) => "Вираз",
+            ("smart_add", Language::En) => "Add list",
+            ("smart_add", Language::Ua) => "Додати список",
+            ("smart_empty", Language::En) => "No servers in the latest scans match this list",
+            ("smart_empty", Language::Ua) => "Жоден сервер з останніх сканувань не підходить",
+            ("nav_all", Language::En) => "All servers",
+            ("nav_all", Language::Ua) => "Усі сервери",
+            ("nav_favorites", Language::En) => "Favorites",
+            ("nav_favorites", Language::Ua) => "Обране",
+            ("nav_events", Language::En) => "Events",
+            ("nav_events", Language::Ua) => "Події",
+            ("event_plan", Language::En) => "Plan an event",
+            ("event_plan", Language::Ua) => "Запланувати подію",
+            ("event_title", Language::En) => "Title:",
+            ("event_title", Language::Ua) => "Назва:",
+            ("event_default_title", Language::En) => "Squad",
+            ("event_default_title", Language::Ua) => "Squad",
+            ("event_server", Language::En) => "Server:",
+            ("event_server", Language::Ua) => "Сервер:",
+            ("event_no_server", Language::En) => "(none)",
+            ("event_no_server", Language::Ua) => "(без сервера)",
+            ("event_when", Language::En) => "Starts:",
+            ("event_when", Language::Ua) => "Початок:",
+            ("event_duration", Language::En) => "Duration:",
+            ("event_duration", Language::Ua) => "Тривалість:",
+            ("event_add", Language::En) => "Add",
+            ("event_add", Language::Ua) => "Додати",
+            ("event_file", Language::En) => "Calendar file:",
+            ("event_file", Language::Ua) => "Файл календаря:",
+            ("event_export", Language::En) => "Export .ics",
+            ("event_export", Language::Ua) => "Експорт .ics",
+            ("event_none", Language::En) => "No planned events.",
+            ("event_none", Language::Ua) => "Запланованих подій немає.",
+            ("event_hint", Language::En) => "The .ics file is rewritten after every change, so a calendar subscribed to it stays up to date.",
+            ("event_hint", Language::Ua) => "Файл .ics перезаписується після кожної зміни, тож підписаний на нього календар завжди актуальний.",
+            ("nav_history", Language::En) => "History",
+            ("nav_history", Language::Ua) => "Історія",
+            ("nav_stats", Language::En) => "Stats",
+            ("nav_stats", Language::Ua) => "Статистика",
+            ("no_favorites", Language::En) => "Pin servers with 📌 to keep them here",
+            ("no_favorites", Language::Ua) => "Закріпіть сервери кнопкою 📌, щоб вони були тут",
+            ("chip_remove", Language::En) => "Remove this filter and rescan",
+            ("chip_remove", Language::Ua) => "Зняти цей фільтр і оновити список",
+            ("resume_scan", Language::En) => "Resume scan",
+            ("resume_scan", Language::Ua) => "Продовжити сканування",
+            ("country_map", Language::En) => "Servers by country",
+            ("country_map", Language::Ua) => "Сервери за країнами",
+            ("map_servers", Language::En) => "servers in this scan",
+            ("map_servers", Language::Ua) => "серверів у цьому скані",
+            ("map_ban", Language::En) => "Click to ban this country",
+            ("map_ban", Language::Ua) => "Натисніть, щоб заблокувати країну",
+            ("map_unban", Language::En) => "Banned, click to unban",
+            ("map_unban", Language::Ua) => "Заблоковано, натисніть, щоб розблокувати",
+            ("map_other", Language::En) => "Other:",
+            ("map_other", Language::Ua) => "Інші:",
+            ("population", Language::En) => "Players by hour of day",
+            ("population", Language::Ua) => "Гравці за годинами доби",
+            ("population_empty", Language::En) => "No data yet: totals are recorded after every scan",
+            ("population_empty", Language::Ua) => "Даних ще немає: сума гравців записується після кожного сканування",
+            ("range_day", Language::En) => "24 h",
+            ("range_day", Language::Ua) => "24 год",
+            ("range_week", Language::En) => "7 days",
+            ("range_week", Language::Ua) => "7 днів",
+            ("range_month", Language::En) => "30 days",
+            ("range_month", Language::Ua) => "30 днів",
+            ("players_avg", Language::En) => "players on average",
+            ("players_avg", Language::Ua) => "гравців у середньому",
+            ("no_data", Language::En) => "no data",
+            ("no_data", Language::Ua) => "немає даних",
+            ("ping_title", Language::En) => "Ping test",
+            ("ping_title", Language::Ua) => "Тест пінгу",
+            ("ping_hint", Language::En) => "Measures the response time of the most populated server of each region from the latest scans",
+            ("ping_hint", Language::Ua) => "Вимірює час відповіді найбільш заповненого сервера кожного регіону з останніх сканувань",
+            ("ping_run", Language::En) => "Run",
+            ("ping_run", Language::Ua) => "Запустити",
+            ("ping_timeout", Language::En) => "no reply",
+            ("ping_timeout", Language::Ua) => "немає відповіді",
+            ("ping_no_server", Language::En) => "no servers in the latest scans",
+            ("ping_no_server", Language::Ua) => "немає серверів в останніх сканах",
+            _ => key,
+        }
     }
 
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if let Some(rx) = &self.rx {
-            if let Ok(response) = rx.try_recv() {
-                self.servers.extend(response.servers);
-                self.next_url = response.next_url;
-                self.is_loading = false;
-                self.first_load_done = true;
-                self.rx = None;
+    fn update_overlay(&self) {
+        if let Some(id) = &self.selected_id {
+            if let Some(server) = self.view.servers.iter().chain(self.pinned.iter()).find(|s| &s.id == id) {
+                overlay::write(&self.overlay, server);
             }
         }
+    }
 
-        let mut trigger_load_more_url: Option<String> = None;
-        let mut trigger_new_scan = false;
+    // Застосовуємо нові бани до вже завантажених списків усіх вкладок
+    fn apply_rules_to_views(&mut self, set: &RuleSet) {
+        let views = std::iter::once(&mut self.view).chain(self.tabs.iter_mut().map(|tab| &mut tab.view));
+        for view in views {
+            view.servers.retain(|s| !set.hides(&s.id, &s.full_name, &s.country));
+            view.update_badges();
+        }
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.heading(self.tr("app_title"));
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button(format!("⚙ {}", self.tr("settings"))).clicked() {
-                        self.show_settings = !self.show_settings;
-                    }
-                });
-            });
-            
-            ui.add_space(10.0);
+    fn import_rules(&mut self) {
+        match fs::read_to_string(&self.rules_file) {
+            Ok(text) => {
+                let (set, errors) = RuleSet::parse(&text);
+                println!("[LOG] Imported {} rules ({} invalid lines)", set.rules.len(), errors.len());
+                self.block_rules = text;
+                self.rules_file_mtime = fs::metadata(&self.rules_file).and_then(|m| m.modified()).ok();
+                self.rules_status = format!("{} {}", self.tr("rules_loaded"), set.rules.len());
 
-            ui.horizontal(|ui| {
-                let btn_size = [140.0, 40.0];
-                if !self.first_load_done {
-                     if ui.add_sized(btn_size, egui::Button::new(self.tr("start"))).clicked() {
-                        trigger_new_scan = true;
-                    }
-                } else {
-                    if ui.add_sized(btn_size, egui::Button::new(self.tr("refresh"))).clicked() {
-                        trigger_new_scan = true;
-                    }
-                }
-                
-                if self.is_loading {
-                    ui.spinner();
-                }
+                self.apply_rules_to_views(&set);
+            }
+            Err(e) => {
+                println!("[ERR] Failed to read rules file: {}", e);
+                self.rules_status = e.to_string();
+            }
+        }
+    }
 
-                let status_msg = if self.is_loading {
-                    if self.servers.is_empty() { self.tr("scanning") } else { self.tr("loading_more") }
-                } else if self.first_load_done {
-                    format!("{} {}", self.tr("found"), self.servers.len())
-                } else {
-                    "".to_owned()
+    fn export_rules(&mut self) {
+        match fs::write(&self.rules_file, &self.block_rules) {
+            Ok(()) => {
+                self.rules_file_mtime = fs::metadata(&self.rules_file).and_then(|m| m.modified()).ok();
+                self.rules_status = self.tr("rules_saved").to_owned();
+            }
+            Err(e) => {
+                println!("[ERR] Failed to write rules file: {}", e);
+                self.rules_status = e.to_string();
+            }
+        }
+    }
+
+    fn show_toast(&mut self, text: String) {
+        self.toast = Some((text, Instant::now()));
+    }
+
+    // Сповіщення завжди записуються; у тихі години тост не показуємо
+    fn notify(&mut self, text: String, action: NotificationAction) {
+        let suppressed = self.quiet_hours.is_active();
+        println!("[LOG] Notification{}: {}", if suppressed { " (quiet hours)" } else { "" }, text);
+        if !suppressed {
+            self.show_toast(text.clone());
+        }
+        self.notifications.push(Notification {
+            at: sessions::now_secs(),
+            text,
+            suppressed,
+            read: false,
+            action,
+        });
+        if self.notifications.len() > notifications::MAX_NOTIFICATIONS {
+            let excess = self.notifications.len() - notifications::MAX_NOTIFICATIONS;
+            self.notifications.drain(..excess);
+        }
+    }
+
+    fn open_notification(&mut self, action: NotificationAction) {
+        match action {
+            NotificationAction::None => {}
+            NotificationAction::Server(id) => {
+                self.section = Section::AllServers;
+                if self.single_expand {
+                    self.expanded_ids.clear();
+                }
+                self.expanded_ids.insert(id.clone());
+                self.selected_id = Some(id);
+                self.update_overlay();
+            }
+            NotificationAction::SavedSearch(name) => {
+                self.section = Section::AllServers;
+                if let Some(index) = self.saved_searches.iter().position(|s| s.name == name) {
+                    self.load_search(index);
+                }
+            }
+        }
+    }
+
+    fn show_notification_center(&mut self, ctx: &egui::Context) {
+        if !self.show_notifications {
+            return;
+        }
+        let mut open = true;
+        let mut clicked = None;
+        let mut clear = false;
+        egui::Window::new(format!("🔔 {}", self.tr("notifications")))
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if self.notifications.is_empty() {
+                    ui.label(self.tr("no_notifications"));
+                    return;
+                }
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    for (index, notification) in self.notifications.iter().enumerate().rev() {
+                        ui.horizontal(|ui| {
+                            ui.small(notifications::time_label(notification.at));
+                            if notification.suppressed {
+                                ui.small("🔕").on_hover_text(self.tr("quiet_title"));
+                            }
+                            if notification.action == NotificationAction::None {
+                                ui.label(notification.text.as_str());
+                            } else if ui.link(notification.text.as_str()).clicked() {
+                                clicked = Some(index);
+                            }
+                        });
+                    }
+                });
+                ui.separator();
+                clear = ui.button(self.tr("clear")).clicked();
+            });
+        // Панель відкрита - все прочитано
+        for notification in self.notifications.iter_mut() {
+            notification.read = true;
+        }
+        if let Some(index) = clicked {
+            let action = self.notifications[index].action.clone();
+            self.open_notification(action);
+        }
+        if clear {
+            self.notifications.clear();
+        }
+        self.show_notifications = open;
+    }
+
+    // Копіюємо лише збережувані поля, стан сканування лишається як є
+    fn apply_settings(&mut self, loaded: SquadApp) {
+        self.filters = loaded.filters;
+        self.language = loaded.language;
+        self.overlay = loaded.overlay;
+        self.block_rules = loaded.block_rules;
+        self.rules_file = loaded.rules_file;
+        self.watch_rules_file = loaded.watch_rules_file;
+        self.freeze_order = loaded.freeze_order;
+        self.single_expand = loaded.single_expand;
+        self.pinned = loaded.pinned.into_iter().map(ServerItem::with_text).collect();
+        self.seed_threshold = loaded.seed_threshold;
+        self.expected_version = loaded.expected_version;
+        self.saved_searches = loaded.saved_searches;
+        self.sessions = loaded.sessions;
+        self.rotations = loaded.rotations;
+        self.quiet_hours = loaded.quiet_hours;
+        self.announcements = loaded.announcements;
+        self.seeding = loaded.seeding;
+        self.patch_day = loaded.patch_day;
+        self.veto = loaded.veto;
+        self.layer_db_url = loaded.layer_db_url;
+        self.notifications = loaded.notifications;
+        self.skin = loaded.skin;
+        self.smart_lists = loaded.smart_lists;
+        self.events = loaded.events;
+        self.ics_path = loaded.ics_path;
+        self.backup_path = loaded.backup_path;
+        self.export = loaded.export;
+        // У файлі налаштувань секрету немає - лишаємо той, що вже завантажено
+        let secret = std::mem::take(&mut self.sync.secret);
+        self.sync = loaded.sync;
+        if self.sync.secret.is_empty() {
+            self.sync.secret = secret;
+        } else {
+            self.store_sync_secret();
+        }
+        self.population = loaded.population;
+        self.uptime = loaded.uptime;
+        self.first_seen = loaded.first_seen;
+        self.prime_times = loaded.prime_times;
+        self.ping_history = loaded.ping_history;
+        self.density = loaded.density;
+        self.row_columns = loaded.row_columns;
+        self.quick_actions = loaded.quick_actions;
+        self.home_city = loaded.home_city;
+        self.sidebar_width = loaded.sidebar_width;
+        self.layouts = loaded.layouts;
+        self.resize_sidebar = true;
+        self.active_smart_list = self.active_smart_list.filter(|&index| index < self.smart_lists.len());
+        // Відкриті вкладки з їхніми результатами лишаємо, з диску беремо назви і фільтри
+        for (index, (tab, loaded_tab)) in self.tabs.iter_mut().zip(loaded.tabs).enumerate() {
+            tab.title = loaded_tab.title;
+            if index != self.active_tab {
+                tab.filters = loaded_tab.filters;
+            }
+        }
+    }
+
+    fn reload_config(&mut self) {
+        let path = match &self.config_path {
+            Some(path) => path.clone(),
+            None => return,
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                println!("[ERR] Failed to read config: {}", e);
+                return;
+            }
+        };
+        let kv: HashMap<String, String> = match ron::from_str(&text) {
+            Ok(kv) => kv,
+            Err(e) => {
+                println!("[ERR] Failed to parse config: {}", e);
+                return;
+            }
+        };
+        let value = match kv.get(eframe::APP_KEY) {
+            Some(value) => value,
+            None => return,
+        };
+
+        // eframe сам періодично зберігає цей файл - такі зміни ігноруємо
+        if ron::ser::to_string(&*self).ok().as_deref() == Some(value.as_str()) {
+            return;
+        }
+
+        match ron::from_str::<SquadApp>(value) {
+            Ok(mut loaded) => {
+                println!("[LOG] Config changed on disk, applying...");
+                config::migrate(&mut loaded, value);
+                self.apply_settings(loaded);
+                self.show_toast(self.tr("config_reloaded").to_owned());
+            }
+            Err(e) => println!("[ERR] Failed to parse config: {}", e),
+        }
+    }
+
+    fn poll_file_changes(&mut self) {
+        let mut targets = Vec::new();
+        if let Some(path) = &self.config_path {
+            targets.push(path.clone());
+        }
+        if self.watch_rules_file && !self.rules_file.is_empty() {
+            targets.push(PathBuf::from(&self.rules_file));
+        }
+
+        let changed = match &mut self.watcher {
+            Some(watcher) => {
+                watcher.set_targets(&targets);
+                watcher.changed()
+            }
+            None => return,
+        };
+
+        for path in changed {
+            if self.config_path.as_ref() == Some(&path) {
+                self.reload_config();
+            } else if watcher::absolute(Path::new(&self.rules_file)) == path {
+                let mtime = fs::metadata(&self.rules_file).and_then(|m| m.modified()).ok();
+                if mtime != self.rules_file_mtime {
+                    println!("[LOG] Rules file changed on disk, reloading...");
+                    self.import_rules();
+                    self.show_toast(self.rules_status.clone());
+                }
+            }
+        }
+    }
+
+    fn draw_toast(&mut self, ctx: &egui::Context) {
+        let toast_time = Duration::from_secs(3);
+        if let Some((text, shown_at)) = &self.toast {
+            if shown_at.elapsed() >= toast_time {
+                self.toast = None;
+                return;
+            }
+            egui::Area::new("toast")
+                .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(text.as_str());
+                    });
+                });
+            // Перемальовуємо лише тоді, коли тост треба прибрати
+            ctx.request_repaint_after(toast_time.saturating_sub(shown_at.elapsed()));
+        }
+    }
+
+    // Ручне значення з налаштувань має пріоритет над найновішою версією зі скану
+    fn latest_version(&self) -> &str {
+        if self.expected_version.trim().is_empty() {
+            self.versions.latest()
+        } else {
+            self.expected_version.trim()
+        }
+    }
+
+    fn distance_km(&self, server: &ServerItem) -> Option<f32> {
+        let home = location::find(&self.home_city)?;
+        server.coords().map(|at| location::distance_km((home.lat, home.lon), at))
+    }
+
+    fn is_outdated(&self, server: &ServerItem) -> bool {
+        version::is_older(&server.version, self.latest_version())
+    }
+
+    fn show_advanced_query(&mut self, ui: &mut egui::Ui) {
+        ui.small(self.tr("advanced_hint"));
+        let mut params = self.filters.params();
+        let before = params.clone();
+        let mut remove_row = None;
+        egui::Grid::new("advanced_query_grid").num_columns(3).show(ui, |ui| {
+            for (index, (key, value)) in params.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(key).hint_text("filter[...]").desired_width(180.0));
+                ui.add(egui::TextEdit::singleline(value).desired_width(120.0));
+                if ui.small_button("✖").clicked() {
+                    remove_row = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = remove_row {
+            params.remove(index);
+        }
+        if ui.button(format!("➕ {}", self.tr("add_param"))).clicked() {
+            params.push((String::new(), String::new()));
+        }
+
+        for (key, value) in &params {
+            if let Err(e) = provider::validate_param(key, value) {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+        }
+
+        let base_url = self.launch.api_url.as_deref().unwrap_or(BATTLEMETRICS_URL);
+        let mut preview = provider::first_page_url(base_url, self.filters.min_players(), self.filters.max_players(), &valid_params(&params));
+        ui.label(self.tr("url_preview"));
+        ui.add(egui::TextEdit::multiline(&mut preview).desired_rows(2).interactive(false));
+        if params != before {
+            self.filters.set_params(params);
+        }
+    }
+
+    fn show_validation(&self, ui: &mut egui::Ui) {
+        let (rules, _) = RuleSet::parse(&self.block_rules);
+        for issue in validation::check(&self.filters, &rules) {
+            let text = match issue {
+                Issue::MinAboveMax => self.tr("warn_min_max").to_owned(),
+                Issue::CountryBannedAndAllowed(code) => format!("{} {}", code, self.tr("warn_country_allowed")),
+                Issue::NameFilterBlocked(word) => format!("\"{}\" {}", word, self.tr("warn_name_blocked")),
+            };
+            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), format!("⚠ {}", text));
+        }
+    }
+
+    // --- КОМПАКТНИЙ РЕЖИМ ---
+
+    fn apply_skin(&mut self, ctx: &egui::Context) {
+        if self.applied_skin.as_ref() == Some(&self.skin) {
+            return;
+        }
+        let compact = self.skin.enabled;
+        let level = if compact { egui::WindowLevel::AlwaysOnTop } else { egui::WindowLevel::Normal };
+        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(!compact));
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+        ctx.send_viewport_cmd(egui::ViewportCommand::MousePassthrough(compact && self.skin.click_through));
+        self.applied_skin = Some(self.skin.clone());
+    }
+
+    fn show_compact(&mut self, ctx: &egui::Context) {
+        let fill = ctx.style().visuals.panel_fill.gamma_multiply(self.skin.opacity);
+        let mut exit = false;
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none().fill(fill).inner_margin(6.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    // Рамки немає - вікно тягнемо за заголовок
+                    let title = ui.add(egui::Label::new(egui::RichText::new(APP_NAME).strong()).sense(egui::Sense::drag()));
+                    if title.drag_started() {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                    }
+                    if self.view.is_loading {
+                        ui.spinner();
+                    }
+                    ui.with_layout(self.direction().trailing(), |ui| {
+                        exit = ui.small_button("⤢").on_hover_text(self.tr("skin_exit")).clicked();
+                    });
+                });
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let unpinned = self.view.servers.iter().filter(|s| !self.is_pinned(&s.id));
+                    for server in self.pinned.iter().chain(unpinned) {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), server.text.country.as_str());
+                            ui.label(server.text.name.as_str());
+                            ui.with_layout(self.direction().trailing(), |ui| {
+                                let color = if server.is_nearly_full() { egui::Color32::RED } else { egui::Color32::GREEN };
+                                ui.colored_label(color, server.text.players.as_str());
+                            });
+                        });
+                    }
+                });
+            });
+        if exit {
+            self.skin.enabled = false;
+        }
+    }
+
+    // --- РЕЖИМ КІОСКУ ---
+
+    fn show_kiosk(&mut self, ctx: &egui::Context) {
+        if self.kiosk.refresh_due() && !self.view.is_loading {
+            self.kiosk.refreshed();
+            self.run_scan(None);
+        }
+        if ctx.input(|i| i.pointer.is_moving() || !i.events.is_empty()) {
+            self.kiosk.touched();
+        }
+        ctx.request_repaint_after(Duration::from_secs(1));
+
+        let slide = if self.kiosk.is_idle() && !self.pinned.is_empty() {
+            Some(self.kiosk.slide(self.pinned.len()))
+        } else {
+            None
+        };
+        egui::CentralPanel::default().show(ctx, |ui| match slide {
+            Some(index) => self.show_kiosk_slide(ui, index),
+            None => self.show_kiosk_list(ui),
+        });
+    }
+
+    fn show_kiosk_list(&self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(self.tr("app_title")).size(32.0).strong());
+            if self.view.is_loading {
+                ui.spinner();
+            }
+            ui.with_layout(self.direction().trailing(), |ui| {
+                ui.label(egui::RichText::new(format!("{} {}", self.tr("found"), self.view.servers.len())).size(24.0));
+            });
+        });
+        ui.separator();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let unpinned = self.view.servers.iter().filter(|s| !self.is_pinned(&s.id));
+            for server in self.pinned.iter().chain(unpinned) {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(server.text.country.as_str()).size(22.0).color(egui::Color32::from_rgb(255, 165, 0)));
+                    ui.label(egui::RichText::new(server.text.name.as_str()).size(22.0));
+                    ui.with_layout(self.direction().trailing(), |ui| {
+                        let color = if server.is_nearly_full() { egui::Color32::RED } else { egui::Color32::GREEN };
+                        ui.label(egui::RichText::new(server.text.players.as_str()).size(22.0).color(color));
+                    });
+                });
+            }
+        });
+    }
+
+    fn show_kiosk_slide(&self, ui: &mut egui::Ui, index: usize) {
+        let server = &self.pinned[index];
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() * 0.2);
+            ui.label(egui::RichText::new(server.text.country.as_str()).size(32.0).color(egui::Color32::from_rgb(255, 165, 0)));
+            ui.label(egui::RichText::new(&server.full_name).size(40.0).strong());
+            let color = if server.is_nearly_full() { egui::Color32::RED } else { egui::Color32::GREEN };
+            ui.label(egui::RichText::new(server.text.players.as_str()).size(96.0).color(color));
+            ui.label(egui::RichText::new(server.text.map_mode.as_str()).size(28.0));
+            let queue = server.public_queue + server.reserved_queue;
+            if queue > 0 {
+                ui.label(egui::RichText::new(format!("{} {}", self.tr("queue"), queue)).size(24.0));
+            }
+            ui.add_space(20.0);
+            ui.label(format!("{} / {}", index + 1, self.pinned.len()));
+        });
+    }
+
+    // --- ГРА ТА ОБЛІК ЧАСУ ---
+
+    fn join_server(&mut self, ctx: &egui::Context, id: &str) {
+        let server = match self.view.servers.iter().chain(self.pinned.iter()).find(|s| s.id == id) {
+            Some(server) => server.clone(),
+            None => return,
+        };
+        self.join(ctx, &server);
+    }
+
+    fn join(&mut self, ctx: &egui::Context, server: &ServerItem) {
+        let url = format!("steam://connect/{}:{}", server.ip, server.join_port());
+        println!("[LOG] Joining {}", url);
+        ctx.output_mut(|o| o.open_url = Some(egui::OpenUrl::same_tab(url)));
+        self.session_tracker.start(&server.id, &server.full_name, &server.map);
+    }
+
+    fn poll_session(&mut self, ctx: &egui::Context) {
+        if let Some(session) = self.session_tracker.poll() {
+            self.sessions.push(session);
+        }
+        if let Some(interval) = self.session_tracker.check_interval() {
+            ctx.request_repaint_after(interval);
+        }
+    }
+
+    fn show_history(&self, ui: &mut egui::Ui) {
+        ui.heading(self.tr("nav_history"));
+        if let Some(server) = self.session_tracker.active_server() {
+            ui.colored_label(egui::Color32::LIGHT_GREEN, format!("▶ {} {}", self.tr("playing_on"), server));
+        }
+        ui.separator();
+        if self.sessions.is_empty() {
+            ui.label(self.tr("no_sessions"));
+            return;
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for session in self.sessions.iter().rev() {
+                ui.horizontal(|ui| {
+                    ui.label(sessions::date_label(session.started));
+                    ui.strong(session.server_name.as_str());
+                    ui.label(session.map.as_str());
+                    ui.with_layout(self.direction().trailing(), |ui| {
+                        ui.label(format!("{} {}", session.minutes(), self.tr("minutes")));
+                    });
+                });
+            }
+        });
+    }
+
+    // Середній сумарний онлайн вкладки по годинах доби
+    fn show_population_chart(&mut self, ui: &mut egui::Ui) {
+        let names: Vec<String> = self.population.names().into_iter().map(str::to_owned).collect();
+        if names.is_empty() {
+            ui.label(self.tr("population_empty"));
+            return;
+        }
+        if !names.contains(&self.population_series) {
+            self.population_series = self.tab_title(self.active_tab);
+            if !names.contains(&self.population_series) {
+                self.population_series = names[0].clone();
+            }
+        }
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("population_series")
+                .selected_text(self.population_series.as_str())
+                .show_ui(ui, |ui| {
+                    for name in &names {
+                        ui.selectable_value(&mut self.population_series, name.clone(), name.as_str());
+                    }
+                });
+            let ranges = [
+                (population::Range::Day, self.tr("range_day")),
+                (population::Range::Week, self.tr("range_week")),
+                (population::Range::Month, self.tr("range_month")),
+            ];
+            for (range, label) in ranges {
+                ui.selectable_value(&mut self.population_range, range, label);
+            }
+        });
+
+        let hours = self.population.by_hour(&self.population_series, sessions::now_secs(), self.population_range);
+        let peak = hours.iter().flatten().copied().fold(1.0, f32::max);
+        let size = egui::vec2(ui.available_width().min(480.0), 120.0);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+        let bar_width = rect.width() / 24.0;
+        let mut hovered = None;
+        for (hour, average) in hours.iter().enumerate() {
+            let left = rect.left() + hour as f32 * bar_width;
+            let column = egui::Rect::from_x_y_ranges(left..=left + bar_width, rect.y_range());
+            if let Some(average) = average {
+                let top = rect.bottom() - rect.height() * (average / peak);
+                let bar = egui::Rect::from_x_y_ranges(left + 1.0..=left + bar_width - 1.0, top..=rect.bottom());
+                painter.rect_filled(bar, 1.0, egui::Color32::from_rgb(60, 160, 90));
+            }
+            if response.hover_pos().is_some_and(|pos| column.contains(pos)) {
+                hovered = Some((hour, *average));
+            }
+        }
+        for hour in (0..24).step_by(6) {
+            let x = rect.left() + hour as f32 * bar_width;
+            painter.text(egui::pos2(x + 2.0, rect.top() + 2.0), egui::Align2::LEFT_TOP, format!("{:02}", hour), egui::FontId::proportional(10.0), egui::Color32::GRAY);
+        }
+        if let Some((hour, average)) = hovered {
+            let text = match average {
+                Some(average) => format!("{:02}:00 - {:.0} {}", hour, average, self.tr("players_avg")),
+                None => format!("{:02}:00 - {}", hour, self.tr("no_data")),
+            };
+            response.on_hover_text(text);
+        }
+    }
+
+    fn show_stats(&mut self, ui: &mut egui::Ui) {
+        let mut export = None;
+        ui.heading(format!("📈 {}", self.tr("population")));
+        self.show_population_chart(ui);
+        ui.add_space(10.0);
+        ui.heading(format!("📊 {}", self.tr("playtime")));
+        ui.separator();
+        if self.sessions.is_empty() {
+            ui.label(self.tr("no_sessions"));
+            return;
+        }
+        ui.collapsing(self.tr("weekly_summary"), |ui| {
+            let summary = WeeklySummary::build(&self.sessions, sessions::now_secs());
+            ui.label(format!("{} – {}", summary.from, summary.to));
+            ui.label(format!("{} {} | {}", summary.sessions, self.tr("sessions"), report::hours(summary.total_minutes)));
+            for (name, minutes) in summary.servers.iter().take(5) {
+                ui.label(format!("    {} | {}", report::hours(*minutes), name));
+            }
+            if !summary.maps.is_empty() {
+                let maps: Vec<&str> = summary.maps.iter().take(5).map(|(map, _)| map.as_str()).collect();
+                ui.label(format!("{} {}", self.tr("map"), maps.join(", ")));
+            }
+            ui.horizontal(|ui| {
+                if ui.button("💾 Markdown").clicked() {
+                    export = Some(false);
+                }
+                if ui.button("💾 HTML").clicked() {
+                    export = Some(true);
+                }
+            });
+        });
+        ui.separator();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (day, servers) in sessions::playtime_by_day(&self.sessions).iter().rev() {
+                let total: u64 = servers.values().sum();
+                ui.strong(format!("{} - {} {}", day, total, self.tr("minutes")));
+                for (name, minutes) in servers {
+                    ui.label(format!("    {} {} | {}", minutes, self.tr("minutes"), name));
+                }
+            }
+        });
+
+        if let Some(html) = export {
+            self.export_weekly(html);
+        }
+    }
+
+    fn export_weekly(&mut self, html: bool) {
+        let summary = WeeklySummary::build(&self.sessions, sessions::now_secs());
+        let (path, content) =
+            if html { ("squad_weekly.html", summary.to_html()) } else { ("squad_weekly.md", summary.to_markdown()) };
+        match fs::write(path, content) {
+            Ok(()) => self.show_toast(format!("{} {}", self.tr("exported_to"), path)),
+            Err(e) => {
+                println!("[ERR] Failed to export summary: {}", e);
+                self.show_toast(e.to_string());
+            }
+        }
+    }
+
+    // --- ЕКСПОРТ СПИСКУ СЕРВЕРІВ ---
+
+    fn export_value(&self, server: &ServerItem, column: Column) -> serde_json::Value {
+        use serde_json::{json, Value};
+        match column {
+            Column::Id => json!(server.id),
+            Column::Name => json!(server.full_name),
+            Column::Players => json!(server.players),
+            Column::MaxPlayers => json!(server.max_players),
+            Column::PublicQueue => json!(server.public_queue),
+            Column::ReservedQueue => json!(server.reserved_queue),
+            Column::Map => json!(&*server.map),
+            Column::Mode => json!(&*server.mode),
+            Column::Country => json!(&*server.country),
+            Column::City => json!(server.city),
+            Column::Version => json!(server.version),
+            Column::AntiCheat => server.anticheat.map_or(Value::Null, Value::Bool),
+            Column::Address => json!(server.address()),
+            Column::QueryPort => json!(server.query_port),
+            Column::PingAverage => self.ping_history.stats(&server.id).map_or(Value::Null, |stats| json!(stats.average)),
+            Column::PingJitter => self.ping_history.stats(&server.id).map_or(Value::Null, |stats| json!(stats.jitter)),
+            Column::Uptime7d => self
+                .uptime
+                .over_days(&server.id, sessions::now_secs(), 7)
+                .map_or(Value::Null, |uptime| json!((uptime.percent as f64 * 10.0).round() / 10.0)),
+            Column::Trend => self.history.trend(&server.id).map_or(Value::Null, |trend| {
+                json!(match trend {
+                    Trend::Rising => "rising",
+                    Trend::Falling => "falling",
+                    Trend::Stable => "stable",
+                })
+            }),
+            Column::PlaytimeMinutes => {
+                json!(self.sessions.iter().filter(|s| s.server_id == server.id).map(PlaySession::minutes).sum::<u64>())
+            }
+        }
+    }
+
+    // Те саме, що в списку: спочатку закріплені, далі результати вкладки
+    fn export_servers(&mut self) {
+        let servers: Vec<&ServerItem> = self
+            .pinned
+            .iter()
+            .chain(self.view.servers.iter().filter(|server| !self.is_pinned(&server.id)))
+            .collect();
+        let rows: Vec<Vec<serde_json::Value>> = servers
+            .iter()
+            .map(|server| self.export.columns.iter().map(|&column| self.export_value(server, column)).collect())
+            .collect();
+        let path = self.export.file_name();
+        match fs::write(&path, self.export.render(&rows)) {
+            Ok(()) => {
+                println!("[LOG] Exported {} servers to {}", rows.len(), path);
+                self.show_toast(format!("{} {}", self.tr("exported_to"), path));
+            }
+            Err(e) => {
+                println!("[ERR] Failed to export servers: {}", e);
+                self.show_toast(e.to_string());
+            }
+        }
+    }
+
+    fn show_export_dialog(&mut self, ctx: &egui::Context) {
+        if !self.show_export {
+            return;
+        }
+        let mut open = true;
+        let (mut run, mut save_preset, mut load_preset, mut remove_preset) = (false, false, None, None);
+        let preset_hint = self.tr("export_preset_name");
+        egui::Window::new(self.tr("export_servers"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(self.tr("export_columns"));
+                egui::Grid::new("export_columns").num_columns(3).show(ui, |ui| {
+                    for (index, column) in Column::ALL.into_iter().enumerate() {
+                        let mut checked = self.export.columns.contains(&column);
+                        if ui.checkbox(&mut checked, column.key()).changed() {
+                            self.export.toggle(column);
+                        }
+                        if index % 3 == 2 {
+                            ui.end_row();
+                        }
+                    }
+                });
+                ui.small(self.tr("export_extended_hint"));
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("export_format"));
+                    ui.selectable_value(&mut self.export.format, Format::Csv, "CSV");
+                    ui.selectable_value(&mut self.export.format, Format::Json, "JSON");
+                });
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("export_file"));
+                    ui.text_edit_singleline(&mut self.export.path);
+                });
+                ui.separator();
+                ui.label(self.tr("export_presets"));
+                ui.horizontal_wrapped(|ui| {
+                    for (index, preset) in self.export.presets.iter().enumerate() {
+                        if ui.button(&preset.name).clicked() {
+                            load_preset = Some(index);
+                        }
+                        if ui.small_button("✖").clicked() {
+                            remove_preset = Some(index);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.export_preset_name).hint_text(preset_hint));
+                    save_preset = ui.button(format!("💾 {}", self.tr("export_save_preset"))).clicked();
+                });
+                ui.separator();
+                let can_export = !self.export.columns.is_empty() && !self.export.path.trim().is_empty();
+                run = ui.add_enabled(can_export, egui::Button::new(self.tr("export_run"))).clicked();
+            });
+
+        if let Some(index) = load_preset {
+            self.export.load_preset(index);
+        }
+        if let Some(index) = remove_preset {
+            self.export.presets.remove(index);
+        }
+        let preset_name = self.export_preset_name.trim().to_owned();
+        if save_preset && !preset_name.is_empty() {
+            self.export.save_preset(&preset_name);
+            self.export_preset_name.clear();
+        }
+        if run {
+            self.export_servers();
+        }
+        self.show_export = open && !run;
+    }
+
+    // --- МАКЕТИ ВІКНА ---
+
+    fn current_layout(&self, name: String) -> WindowLayout {
+        WindowLayout {
+            name,
+            // Макет зберігають з налаштувань, тож відкривати треба те, що було перед ними
+            section: if self.section == Section::Settings { Section::AllServers } else { self.section },
+            sidebar_width: self.sidebar_width,
+            density: self.density,
+            columns: self.row_columns,
+            quick_actions: self.quick_actions.clone(),
+            notifications_open: self.show_notifications,
+            compact_overlay: self.skin.enabled,
+        }
+    }
+
+    fn save_layout(&mut self) {
+        let name = self.new_layout_name.trim().to_owned();
+        if name.is_empty() {
+            return;
+        }
+        let layout = self.current_layout(name.clone());
+        match self.layouts.iter_mut().find(|l| l.name == name) {
+            Some(existing) => *existing = layout,
+            None => self.layouts.push(layout),
+        }
+        println!("[LOG] Layout saved: {}", name);
+        self.new_layout_name.clear();
+    }
+
+    fn show_quick_action_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label(self.tr("quick_actions"));
+        let (mut move_up, mut remove) = (None, None);
+        for (index, action) in self.quick_actions.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} {}", action.icon(), self.tr(action.key())));
+                if ui.add_enabled(index > 0, egui::Button::new("⬆").small()).clicked() {
+                    move_up = Some(index);
+                }
+                if ui.add_enabled(index + 1 < self.quick_actions.len(), egui::Button::new("⬇").small()).clicked() {
+                    move_up = Some(index + 1);
+                }
+                if ui.small_button("✖").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        let missing: Vec<QuickAction> = QuickAction::ALL.into_iter().filter(|a| !self.quick_actions.contains(a)).collect();
+        if !missing.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for action in missing {
+                    if ui.small_button(format!("➕ {} {}", action.icon(), self.tr(action.key()))).clicked() {
+                        self.quick_actions.push(action);
+                    }
+                }
+            });
+        }
+        if let Some(index) = move_up {
+            self.quick_actions.swap(index - 1, index);
+        }
+        if let Some(index) = remove {
+            self.quick_actions.remove(index);
+        }
+    }
+
+    fn apply_layout(&mut self, index: usize) {
+        let layout = match self.layouts.get(index) {
+            Some(layout) => layout.clone(),
+            None => return,
+        };
+        println!("[LOG] Layout applied: {}", layout.name);
+        self.section = layout.section;
+        self.sidebar_width = layout.sidebar_width.clamp(*layouts::SIDEBAR_RANGE.start(), *layouts::SIDEBAR_RANGE.end());
+        self.resize_sidebar = true;
+        self.density = layout.density;
+        self.row_columns = layout.columns;
+        self.quick_actions = layout.quick_actions;
+        self.show_notifications = layout.notifications_open;
+        self.skin.enabled = layout.compact_overlay;
+    }
+
+    fn show_layouts(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(self.tr("density"));
+            let (comfortable, compact) = (self.tr("density_comfortable"), self.tr("density_compact"));
+            ui.selectable_value(&mut self.density, Density::Comfortable, comfortable);
+            ui.selectable_value(&mut self.density, Density::Compact, compact);
+        });
+        ui.label(self.tr("row_columns"));
+        let mut columns = self.row_columns;
+        let labels = columns.toggles().map(|(key, _)| self.tr(key));
+        ui.horizontal_wrapped(|ui| {
+            for ((_, value), label) in columns.toggles().into_iter().zip(labels) {
+                ui.checkbox(value, label);
+            }
+        });
+        self.row_columns = columns;
+        self.show_quick_action_settings(ui);
+        ui.separator();
+        ui.label(self.tr("layouts_saved"));
+        let (mut apply, mut remove) = (None, None);
+        ui.horizontal_wrapped(|ui| {
+            for (index, layout) in self.layouts.iter().enumerate() {
+                if ui.button(&layout.name).clicked() {
+                    apply = Some(index);
+                }
+                if ui.small_button("✖").clicked() {
+                    remove = Some(index);
+                }
+            }
+        });
+        let name_hint = self.tr("layout_name");
+        let save_label = format!("💾 {}", self.tr("layout_save"));
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_layout_name).hint_text(name_hint));
+            if ui.button(save_label).clicked() {
+                self.save_layout();
+            }
+        });
+        ui.small(self.tr("layouts_hint"));
+        if let Some(index) = apply {
+            self.apply_layout(index);
+        }
+        if let Some(index) = remove {
+            self.layouts.remove(index);
+        }
+    }
+
+    // --- НАЛАШТУВАННЯ ---
+
+    fn show_settings_page(&mut self, ui: &mut egui::Ui) {
+        ui.heading(self.tr("conf_title"));
+        ui.separator();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(self.tr("lang"));
+                ui.selectable_value(&mut self.language, Language::En, "English");
+                ui.selectable_value(&mut self.language, Language::Ua, "Українська");
+                for translation in &self.translations.list {
+                    ui.selectable_value(&mut self.language, Language::Custom(translation.code.clone()), &translation.name);
+                }
+            });
+            ui.separator();
+            let mut min_players = self.filters.min_players();
+            let mut max_players = self.filters.max_players();
+            ui.horizontal(|ui| {
+                ui.label(self.tr("min_p"));
+                // Повзунки зв'язані: мінімум не може перевищити максимум
+                if ui.add(egui::Slider::new(&mut min_players, 0..=100)).changed() {
+                    self.filters.set(Filter::MinPlayers(min_players));
+                    self.filters.set(Filter::MaxPlayers(max_players.max(min_players)));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(self.tr("max_p"));
+                if ui.add(egui::Slider::new(&mut max_players, 0..=100)).changed() {
+                    self.filters.set(Filter::MaxPlayers(max_players));
+                    self.filters.set(Filter::MinPlayers(min_players.min(max_players)));
+                }
+            });
+            self.show_validation(ui);
+            let mut anticheat_only = self.filters.anticheat_only();
+            if ui.checkbox(&mut anticheat_only, self.tr("hide_no_anticheat")).changed() {
+                if anticheat_only {
+                    self.filters.set(Filter::AntiCheatOnly);
+                } else {
+                    self.filters.remove(&Filter::AntiCheatOnly);
+                }
+            }
+            self.show_vehicle_filters(ui);
+            let (public_queue, reserved_queue) = (self.filters.max_public_queue(), self.filters.max_reserved_queue());
+            queue_filter(ui, self.tr("queue_public"), &mut self.filters, Filter::MaxPublicQueue, public_queue);
+            queue_filter(ui, self.tr("queue_reserved"), &mut self.filters, Filter::MaxReservedQueue, reserved_queue);
+            ui.horizontal(|ui| {
+                ui.label(self.tr("seed_threshold"));
+                ui.add(egui::Slider::new(&mut self.seed_threshold, 10..=100));
+            });
+            ui.horizontal(|ui| {
+                ui.label(self.tr("expected_version"));
+                ui.add(egui::TextEdit::singleline(&mut self.expected_version).hint_text(self.versions.latest()));
+            });
+            ui.horizontal(|ui| {
+                ui.label(self.tr("home_city"));
+                let none = self.tr("home_city_none");
+                egui::ComboBox::from_id_source("home_city")
+                    .selected_text(if self.home_city.is_empty() { none } else { self.home_city.as_str() })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.home_city, String::new(), none);
+                        for city in &location::CITIES {
+                            ui.selectable_value(&mut self.home_city, city.name.to_owned(), format!("{}, {}", city.name, city.country));
+                        }
+                    });
+            });
+            let single_expand_label = self.tr("single_expand");
+            ui.checkbox(&mut self.single_expand, single_expand_label);
+            self.show_autostart(ui);
+            ui.separator();
+            ui.collapsing(format!("🏓 {}", self.tr("ping_title")), |ui| {
+                self.show_ping_test(ui);
+            });
+            ui.collapsing(self.tr("bl_title"), |ui| {
+                self.show_region_bans(ui);
+                egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                    let countries = vec![
+                        ("RU", "Russia"), ("BY", "Belarus"), 
+                        ("CN", "China"), ("BR", "Brazil"), 
+                        ("AU", "Australia"), ("SG", "Singapore"), 
+                        ("KZ", "Kazakhstan"), ("HK", "Hong Kong"),
+                        ("TR", "Turkey"), ("US", "USA"), ("CA", "Canada")
+                    ];
+                    for (code, name) in countries {
+                        let mut is_banned = self.filters.is_banned(code);
+                        if ui.checkbox(&mut is_banned, format!("{} ({})", code, name)).changed() {
+                            if is_banned {
+                                self.filters.set(Filter::NoCountry(code.to_string()));
+                            } else {
+                                self.filters.remove(&Filter::NoCountry(code.to_string()));
+                            }
+                        }
+                    }
+                });
+            });
+            ui.separator();
+            
+            let text_filters = [
+                (self.tr("search_name"), self.filters.name().to_owned(), Filter::Name as fn(String) -> Filter),
+                (self.tr("map"), self.filters.map().to_owned(), Filter::Map),
+                (self.tr("mode"), self.filters.mode().to_owned(), Filter::Mode),
+            ];
+            for (label, mut text, make) in text_filters {
+                ui.horizontal(|ui| {
+                    ui.label(label);
+                    if ui.text_edit_singleline(&mut text).changed() {
+                        self.filters.set(make(text));
+                    }
+                });
+            }
+            ui.separator();
+            ui.collapsing(self.tr("advanced_title"), |ui| {
+                self.show_advanced_query(ui);
+            });
+            ui.separator();
+            ui.collapsing(self.tr("rules_title"), |ui| {
+                ui.small("country:RU   name~wagner   id:123456   !id:999");
+                ui.add(egui::TextEdit::multiline(&mut self.block_rules).desired_rows(5).code_editor());
+                let (set, errors) = RuleSet::parse(&self.block_rules);
+                ui.label(format!("{} {}", self.tr("rules_active"), set.rules.len()));
+                for error in errors {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("rules_file"));
+                    ui.text_edit_singleline(&mut self.rules_file);
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(self.tr("import")).clicked() {
+                        self.import_rules();
+                    }
+                    if ui.button(self.tr("export")).clicked() {
+                        self.export_rules();
+                    }
+                    let watch_label = self.tr("rules_watch");
+                    ui.checkbox(&mut self.watch_rules_file, watch_label);
+                });
+                if !self.rules_status.is_empty() {
+                    ui.small(self.rules_status.as_str());
+                }
+            });
+            ui.separator();
+            ui.collapsing(self.tr("quiet_title"), |ui| {
+                let quiet_label = self.tr("quiet_enabled");
+                ui.checkbox(&mut self.quiet_hours.enabled, quiet_label);
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.quiet_hours.start).clamp_range(0..=23).suffix(":00"));
+                    ui.label("–");
+                    ui.add(egui::DragValue::new(&mut self.quiet_hours.end).clamp_range(0..=23).suffix(":00"));
+                });
+                ui.small(self.tr("quiet_hint"));
+            });
+            ui.separator();
+            ui.collapsing(self.tr("announcements_title"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("announcements_url"));
+                    ui.text_edit_singleline(&mut self.announcements.url);
+                    if ui.button(self.tr("announcements_reload")).clicked() {
+                        self.announcements_checked = None;
+                    }
+                });
+                ui.small(self.tr("announcements_hint"));
+            });
+            ui.separator();
+            ui.collapsing(self.tr("layer_db_title"), |ui| self.show_layer_db_settings(ui));
+            ui.separator();
+            ui.collapsing(self.tr("patch_title"), |ui| self.show_patch_day_settings(ui));
+            ui.separator();
+            ui.collapsing(self.tr("seeding_title"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("seeding_url"));
+                    ui.text_edit_singleline(&mut self.seeding.url);
+                    if ui.button(self.tr("announcements_reload")).clicked() {
+                        self.seeding_checked = None;
+                    }
+                });
+                let alert_label = self.tr("seeding_alert");
+                ui.checkbox(&mut self.seeding.alert, alert_label);
+                ui.small(self.tr("seeding_hint"));
+            });
+            ui.separator();
+            ui.collapsing(self.tr("skin_title"), |ui| {
+                let skin_label = self.tr("skin_enabled");
+                ui.checkbox(&mut self.skin.enabled, skin_label);
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("skin_opacity"));
+                    ui.add(egui::Slider::new(&mut self.skin.opacity, 0.2..=1.0));
+                });
+                let click_label = self.tr("skin_click_through");
+                ui.checkbox(&mut self.skin.click_through, click_label);
+                ui.small(self.tr("skin_hint"));
+            });
+            ui.separator();
+            ui.collapsing(self.tr("overlay_title"), |ui| {
+                let enabled_label = self.tr("overlay_enabled");
+                ui.checkbox(&mut self.overlay.enabled, enabled_label);
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("overlay_path"));
+                    ui.text_edit_singleline(&mut self.overlay.path);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("overlay_format"));
+                    ui.selectable_value(&mut self.overlay.format, OverlayFormat::Text, "TXT");
+                    ui.selectable_value(&mut self.overlay.format, OverlayFormat::Html, "HTML");
+                });
+                ui.label(self.tr("overlay_template"));
+                ui.text_edit_singleline(&mut self.overlay.template);
+                ui.small(overlay::PLACEHOLDERS);
+                ui.small(self.tr("overlay_hint"));
+            });
+            ui.separator();
+            ui.collapsing(self.tr("layouts_title"), |ui| self.show_layouts(ui));
+            ui.separator();
+            ui.collapsing(self.tr("backup_title"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.tr("backup_path"));
+                    ui.text_edit_singleline(&mut self.backup_path);
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(self.tr("backup_create")).clicked() {
+                        self.backup_all();
+                    }
+                    if ui.button(self.tr("backup_restore")).clicked() {
+                        self.open_backup();
+                    }
+                });
+                ui.small(self.tr("backup_hint"));
+                if !self.backup_status.is_empty() {
+                    ui.small(self.backup_status.as_str());
+                }
+            });
+            ui.separator();
+            ui.collapsing(self.tr("sync_title"), |ui| {
+                self.show_sync_settings(ui);
+            });
+            ui.separator();
+            ui.collapsing(self.tr("links_title"), |ui| self.show_link_settings(ui));
+            ui.separator();
+            ui.horizontal_wrapped(|ui| {
+                ui.label(self.tr("reset"));
+                for scope in [ResetScope::Filters, ResetScope::Blacklist, ResetScope::Appearance, ResetScope::Everything] {
+                    if ui.small_button(self.reset_label(scope)).clicked() {
+                        self.pending_reset = Some(scope);
+                    }
+                }
+            });
+        });
+    }
+
+    // --- СКИДАННЯ НАЛАШТУВАНЬ ---
+
+    fn reset_label(&self, scope: ResetScope) -> &'static str {
+        match scope {
+            ResetScope::Filters => self.tr("reset_filters"),
+            ResetScope::Blacklist => self.tr("reset_blacklist"),
+            ResetScope::Appearance => self.tr("reset_appearance"),
+            ResetScope::Everything => self.tr("reset_everything"),
+        }
+    }
+
+    fn reset_settings(&mut self, scope: ResetScope) {
+        let defaults = SquadApp::default();
+        match scope {
+            ResetScope::Filters => {
+                // Чорний список - окрема група, його не чіпаємо
+                let mut filters = FilterSet::default();
+                for filter in self.filters.iter().filter(|f| matches!(f, Filter::NoCountry(_))) {
+                    filters.set(filter.clone());
+                }
+                self.filters = filters;
+            }
+            ResetScope::Blacklist => {
+                self.filters.clear_like(&Filter::NoCountry(String::new()));
+                for filter in defaults.filters.iter().filter(|f| matches!(f, Filter::NoCountry(_))) {
+                    self.filters.set(filter.clone());
+                }
+                self.block_rules = defaults.block_rules;
+            }
+            ResetScope::Appearance => {
+                self.language = defaults.language;
+                self.single_expand = defaults.single_expand;
+                self.freeze_order = defaults.freeze_order;
+                // Зламаний компактний режим чи макет теж має лікуватись цим скиданням
+                self.skin = defaults.skin;
+                self.density = defaults.density;
+                self.row_columns = defaults.row_columns;
+                self.sidebar_width = defaults.sidebar_width;
+                self.layouts = defaults.layouts;
+                self.quick_actions = defaults.quick_actions;
+            }
+            ResetScope::Everything => self.apply_settings(defaults),
+        }
+        println!("[LOG] Settings reset: {}", self.reset_label(scope));
+    }
+
+    fn show_reset_confirmation(&mut self, ctx: &egui::Context) {
+        let scope = match self.pending_reset {
+            Some(scope) => scope,
+            None => return,
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(self.tr("reset_confirm_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("{} \"{}\"?", self.tr("reset_confirm"), self.reset_label(scope)));
+                ui.horizontal(|ui| {
+                    confirmed = ui.button(self.tr("reset")).clicked();
+                    cancelled = ui.button(self.tr("cancel")).clicked();
+                });
+            });
+        if confirmed {
+            self.reset_settings(scope);
+        }
+        if confirmed || cancelled {
+            self.pending_reset = None;
+        }
+    }
+
+    // --- РЕЗЕРВНА КОПІЯ ТА ВІДНОВЛЕННЯ ---
+
+    fn backup_all(&mut self) {
+        self.tabs[self.active_tab].filters = self.filters.clone();
+        let settings = match ron::ser::to_string_pretty(&*self, ron::ser::PrettyConfig::default()) {
+            Ok(settings) => settings,
+            Err(e) => {
+                println!("[ERR] Failed to serialize settings: {}", e);
+                self.backup_status = e.to_string();
+                return;
+            }
+        };
+        let mut entries = vec![(backup::SETTINGS_ENTRY, settings.into_bytes())];
+        // Зовнішній файл правил може відрізнятися від тексту в налаштуваннях
+        if let Ok(rules) = fs::read(&self.rules_file) {
+            entries.push((backup::RULES_ENTRY, rules));
+        }
+        match backup::write_zip(&self.backup_path, &entries) {
+            Ok(()) => {
+                println!("[LOG] Backup written to {}", self.backup_path);
+                self.backup_status = format!("{} {}", self.tr("exported_to"), self.backup_path);
+            }
+            Err(e) => {
+                println!("[ERR] Failed to write backup: {}", e);
+                self.backup_status = e.to_string();
+            }
+        }
+    }
+
+    fn read_backup(&self) -> Result<(SquadApp, Option<String>), String> {
+        let entries = backup::read_zip(&self.backup_path)?;
+        let text = |name: &str| {
+            entries.iter().find(|(entry, _)| entry == name).map(|(_, data)| String::from_utf8_lossy(data).into_owned())
+        };
+        let raw = text(backup::SETTINGS_ENTRY).ok_or_else(|| format!("{} is missing", backup::SETTINGS_ENTRY))?;
+        let mut loaded = ron::from_str::<SquadApp>(&raw).map_err(|e| e.to_string())?;
+        if loaded.config_version > config::CONFIG_VERSION {
+            return Err(format!("backup is v{}, this build reads up to v{}", loaded.config_version, config::CONFIG_VERSION));
+        }
+        config::migrate(&mut loaded, &raw);
+        Ok((loaded, text(backup::RULES_ENTRY)))
+    }
+
+    fn open_backup(&mut self) {
+        match self.read_backup() {
+            Ok((loaded, rules)) => {
+                println!("[LOG] Backup {} opened, waiting for confirmation", self.backup_path);
+                self.pending_restore = Some(Box::new(loaded));
+                self.pending_restore_rules = rules;
+                self.backup_status.clear();
+            }
+            Err(e) => {
+                println!("[ERR] Failed to read backup: {}", e);
+                self.backup_status = e;
+            }
+        }
+    }
+
+    // Рядок порівняння для вікна: скільки в копії, скільки з них нових
+    fn restore_line<T>(&self, key: &'static str, backup: &[T], current: &[T], same: impl Fn(&T, &T) -> bool) -> String {
+        let new = backup.iter().filter(|b| !current.iter().any(|c| same(b, c))).count();
+        format!("{}: {} ({} {})", self.tr(key), backup.len(), new, self.tr("restore_new"))
+    }
+
+    fn restore_backup(&mut self, mut loaded: SquadApp, rules: Option<String>, merge: bool) {
+        if !merge {
+            // Шляхи до файлів лишаються свої: з підробленого архіву запис правил,
+            // календаря чи оверлею пішов би в будь-який файл користувача
+            loaded.rules_file = self.rules_file.clone();
+            loaded.ics_path = self.ics_path.clone();
+            loaded.backup_path = self.backup_path.clone();
+            loaded.overlay.path = self.overlay.path.clone();
+            loaded.export.path = self.export.path.clone();
+            self.apply_settings(loaded);
+            if let Some(rules) = rules {
+                if let Err(e) = fs::write(&self.rules_file, rules) {
+                    println!("[ERR] Failed to restore rules file: {}", e);
+                }
+            }
+            println!("[LOG] Backup restored, all data replaced");
+            self.backup_status = self.tr("restore_done").to_owned();
+            return;
+        }
+
+        // Об'єднання: за збігу імені чи ID лишається поточна версія
+        for server in loaded.pinned {
+            if self.pinned.len() < MAX_PINNED && !self.pinned.iter().any(|s| s.id == server.id) {
+                self.pinned.push(ServerItem::with_text(server));
+            }
+        }
+        for search in loaded.saved_searches {
+            if !self.saved_searches.iter().any(|s| s.name == search.name) {
+                self.saved_searches.push(search);
+            }
+        }
+        for list in loaded.smart_lists {
+            if !self.smart_lists.iter().any(|l| l.name == list.name) {
+                self.smart_lists.push(list);
+            }
+        }
+        for session in loaded.sessions {
+            if !self.sessions.iter().any(|s| s.server_id == session.server_id && s.started == session.started) {
+                self.sessions.push(session);
+            }
+        }
+        self.sessions.sort_by_key(|s| s.started);
+        for event in loaded.events {
+            if !self.events.iter().any(|e| e.server_id == event.server_id && e.start == event.start) {
+                self.events.push(event);
+            }
+        }
+        self.events.sort_by_key(|e| e.start);
+        // Рядки правил, яких тут ще немає, дописуємо в кінець
+        let missing: Vec<&str> = loaded
+            .block_rules
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !self.block_rules.lines().any(|l| l.trim() == line.trim()))
+            .collect();
+        if !missing.is_empty() {
+            if !self.block_rules.is_empty() && !self.block_rules.ends_with('\n') {
+                self.block_rules.push('\n');
+            }
+            self.block_rules.push_str(&missing.join("\n"));
+        }
+        // Файл правил відновлюємо, лише якщо його тут немає
+        if let Some(rules) = rules {
+            if !Path::new(&self.rules_file).exists() {
+                if let Err(e) = fs::write(&self.rules_file, rules) {
+                    println!("[ERR] Failed to restore rules file: {}", e);
+                }
+            }
+        }
+        println!("[LOG] Backup merged into current data");
+        self.backup_status = self.tr("restore_done").to_owned();
+    }
+
+    fn show_restore_confirmation(&mut self, ctx: &egui::Context) {
+        let loaded = match &self.pending_restore {
+            Some(loaded) => loaded,
+            None => return,
+        };
+        let lines = [
+            self.restore_line("nav_favorites", &loaded.pinned, &self.pinned, |a, b| a.id == b.id),
+            self.restore_line("restore_searches", &loaded.saved_searches, &self.saved_searches, |a, b| a.name == b.name),
+            self.restore_line("restore_smart_lists", &loaded.smart_lists, &self.smart_lists, |a, b| a.name == b.name),
+            self.restore_line("restore_sessions", &loaded.sessions, &self.sessions, |a, b| {
+                a.server_id == b.server_id && a.started == b.started
+            }),
+            self.restore_line("nav_events", &loaded.events, &self.events, |a, b| {
+                a.server_id == b.server_id && a.start == b.start
+            }),
+        ];
+        let (mut replace, mut merge, mut cancelled) = (false, false, false);
+        egui::Window::new(self.tr("restore_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                for line in &lines {
+                    ui.label(line);
+                }
+                ui.small(self.tr("restore_hint"));
+                ui.horizontal(|ui| {
+                    replace = ui.button(self.tr("restore_replace")).clicked();
+                    merge = ui.button(self.tr("restore_merge")).clicked();
+                    cancelled = ui.button(self.tr("cancel")).clicked();
+                });
+            });
+        if replace || merge || cancelled {
+            let loaded = self.pending_restore.take();
+            let rules = self.pending_restore_rules.take();
+            if let (Some(loaded), false) = (loaded, cancelled) {
+                self.restore_backup(*loaded, rules, merge);
+            }
+        }
+    }
+
+    // --- СИНХРОНІЗАЦІЯ ПРОФІЛЮ ---
+
+    fn sync_profile(&self) -> Profile {
+        Profile {
+            modified: 0,
+            device: String::new(),
+            filters: self.filters.clone(),
+            language: self.language.clone(),
+            block_rules: self.block_rules.clone(),
+            pinned: self.pinned.clone(),
+            seed_threshold: self.seed_threshold,
+            saved_searches: self.saved_searches.clone(),
+            smart_lists: self.smart_lists.clone(),
+            events: self.events.clone(),
+            quiet_hours: self.quiet_hours.clone(),
+        }
+    }
+
+    fn apply_profile(&mut self, profile: Profile) {
+        self.filters = profile.filters;
+        self.language = profile.language;
+        self.block_rules = profile.block_rules;
+        self.pinned = profile.pinned.into_iter().take(MAX_PINNED).map(ServerItem::with_text).collect();
+        self.seed_threshold = profile.seed_threshold;
+        self.saved_searches = profile.saved_searches;
+        self.smart_lists = profile.smart_lists;
+        self.events = profile.events;
+        self.quiet_hours = profile.quiet_hours;
+        self.active_smart_list = self.active_smart_list.filter(|&index| index < self.smart_lists.len());
+        let (set, _) = RuleSet::parse(&self.block_rules);
+        self.apply_rules_to_views(&set);
+    }
+
+    // Запам'ятовуємо час першої локальної зміни після синхронізації - для last-write-wins
+    fn note_local_changes(&mut self) {
+        if self.sync.enabled && self.sync.local_changed_at == 0 && self.sync_profile().hash() != self.sync.last_hash {
+            self.sync.local_changed_at = sessions::now_secs();
+        }
+    }
+
+    fn synced(&mut self, modified: u64, hash: u64) {
+        self.sync.last_remote = modified;
+        self.sync.last_hash = hash;
+        self.sync.local_changed_at = 0;
+        self.note_local_changes();
+        self.sync_status = format!("{} {}", self.tr("sync_done"), events::format_local(sessions::now_secs()));
+    }
+
+    fn pull_profile(&mut self, remote: Profile) {
+        println!("[LOG] Sync: applying profile from {}", remote.device);
+        let modified = remote.modified;
+        self.apply_profile(remote);
+        let hash = self.sync_profile().hash();
+        self.synced(modified, hash);
+    }
+
+    fn push_profile(&mut self, ctx: &egui::Context) {
+        let mut profile = self.sync_profile();
+        profile.modified = sessions::now_secs();
+        profile.device = match self.sync.device.trim() {
+            "" => std::env::var("COMPUTERNAME").or_else(|_| std::env::var("HOSTNAME")).unwrap_or_default(),
+            device => device.to_owned(),
+        };
+        let hash = profile.hash();
+        let settings = self.sync.clone();
+        let tx = self.sync_tx.clone();
+        let ctx = ctx.clone();
+        self.sync_busy = true;
+        println!("[LOG] Sync: uploading profile");
+        thread::spawn(move || {
+            let _ = tx.send(SyncResult::Uploaded(sync::upload(&settings, &profile).map(|()| (profile.modified, hash))));
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_sync(&mut self, ctx: &egui::Context) {
+        while let Ok(result) = self.sync_rx.try_recv() {
+            self.sync_busy = false;
+            match result {
+                SyncResult::Fetched(Ok(remote)) => {
+                    self.note_local_changes();
+                    match sync::decide(&self.sync, self.sync_profile().hash(), remote) {
+                        Decision::UpToDate => {
+                            self.sync_status = format!("{} {}", self.tr("sync_done"), events::format_local(sessions::now_secs()));
+                        }
+                        Decision::Push => self.push_profile(ctx),
+                        Decision::Pull(remote) => self.pull_profile(*remote),
+                        Decision::Conflict(remote) => {
+                            println!("[LOG] Sync: both sides changed, asking the user");
+                            self.sync_conflict = Some(remote);
+                        }
+                    }
+                }
+                SyncResult::Uploaded(Ok((modified, hash))) => self.synced(modified, hash),
+                SyncResult::Fetched(Err(e)) | SyncResult::Uploaded(Err(e)) => {
+                    println!("[ERR] Sync failed: {}", e);
+                    self.sync_status = e;
+                }
+            }
+        }
+
+        if !self.sync.enabled || self.sync.url.trim().is_empty() || self.launch.kiosk {
+            return;
+        }
+        if self.sync_busy || self.sync_conflict.is_some() {
+            return;
+        }
+        if self.sync_checked.is_some_and(|at| at.elapsed() < sync::CHECK_INTERVAL) {
+            return;
+        }
+        self.sync_checked = Some(Instant::now());
+        self.sync_busy = true;
+        ctx.request_repaint_after(sync::CHECK_INTERVAL);
+        let settings = self.sync.clone();
+        let tx = self.sync_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(SyncResult::Fetched(sync::download(&settings)));
+            ctx.request_repaint();
+        });
+    }
+
+    // Секрет зі старого конфігу переносимо в сховище ОС; наступне автозбереження
+    // вже запише app.ron без нього
+    fn load_sync_secret(&mut self) {
+        if !self.sync.secret.is_empty() {
+            println!("[LOG] Moving the sync secret from the config file into the OS credential store");
+            self.store_sync_secret();
+        } else {
+            self.sync.secret = secrets::load(sync::SECRET_ACCOUNT).unwrap_or_default();
+        }
+    }
+
+    fn store_sync_secret(&mut self) {
+        self.sync_secret_dirty = false;
+        if let Err(e) = secrets::store(sync::SECRET_ACCOUNT, &self.sync.secret) {
+            println!("[ERR] Cannot store the sync secret: {}", e);
+            self.sync_status = format!("{} {}", self.tr("sync_secret_failed"), e);
+        }
+    }
+
+    fn show_sync_settings(&mut self, ui: &mut egui::Ui) {
+        let enabled_label = self.tr("sync_enabled");
+        ui.checkbox(&mut self.sync.enabled, enabled_label);
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.sync.backend, sync::Backend::WebDav, "WebDAV");
+            ui.selectable_value(&mut self.sync.backend, sync::Backend::S3, "S3");
+            ui.selectable_value(&mut self.sync.backend, sync::Backend::Gist, "Gist");
+        });
+        let (url_label, user_label, secret_label) = match self.sync.backend {
+            sync::Backend::WebDav => (self.tr("sync_file_url"), Some(self.tr("sync_username")), self.tr("sync_password")),
+            sync::Backend::S3 => (self.tr("sync_file_url"), Some(self.tr("sync_access_key")), self.tr("sync_secret_key")),
+            sync::Backend::Gist => (self.tr("sync_gist_id"), None, self.tr("sync_token")),
+        };
+        let mut secret_edited = false;
+        egui::Grid::new("sync_settings").num_columns(2).show(ui, |ui| {
+            ui.label(url_label);
+            ui.text_edit_singleline(&mut self.sync.url);
+            ui.end_row();
+            if let Some(user_label) = user_label {
+                ui.label(user_label);
+                ui.text_edit_singleline(&mut self.sync.username);
+                ui.end_row();
+            }
+            ui.label(secret_label);
+            let secret = ui.add(egui::TextEdit::singleline(&mut self.sync.secret).password(true));
+            self.sync_secret_dirty |= secret.changed();
+            secret_edited = secret.lost_focus();
+            ui.end_row();
+            if self.sync.backend == sync::Backend::S3 {
+                ui.label(self.tr("sync_region"));
+                ui.text_edit_singleline(&mut self.sync.region);
+                ui.end_row();
+            }
+            ui.label(self.tr("sync_device"));
+            ui.text_edit_singleline(&mut self.sync.device);
+            ui.end_row();
+        });
+        if secret_edited && self.sync_secret_dirty {
+            self.store_sync_secret();
+        }
+        let ask_label = self.tr("sync_ask");
+        ui.checkbox(&mut self.sync.ask_on_conflict, ask_label);
+        ui.horizontal(|ui| {
+            let button = egui::Button::new(self.tr("sync_now"));
+            if ui.add_enabled(self.sync.enabled && !self.sync_busy, button).clicked() {
+                self.sync_checked = None;
+            }
+            if self.sync_busy {
+                ui.spinner();
+            }
+        });
+        ui.small(self.tr("sync_hint"));
+        if !self.sync_status.is_empty() {
+            ui.small(self.sync_status.as_str());
+        }
+    }
+
+    fn show_sync_conflict(&mut self, ctx: &egui::Context) {
+        let remote = match &self.sync_conflict {
+            Some(remote) => remote,
+            None => return,
+        };
+        let local_time = match self.sync.local_changed_at {
+            0 => "?".to_owned(),
+            at => events::format_local(at),
+        };
+        let (mut keep_local, mut use_remote) = (false, false);
+        egui::Window::new(self.tr("sync_conflict_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(self.tr("sync_conflict"));
+                ui.label(format!("{}: {}", self.tr("sync_this_device"), local_time));
+                ui.label(format!("{} \"{}\": {}", self.tr("sync_remote"), remote.device, events::format_local(remote.modified)));
+                ui.horizontal(|ui| {
+                    keep_local = ui.button(self.tr("sync_keep_local")).clicked();
+                    use_remote = ui.button(self.tr("sync_use_remote")).clicked();
+                });
+            });
+        if keep_local || use_remote {
+            let remote = self.sync_conflict.take();
+            match (remote, use_remote) {
+                (Some(remote), true) => self.pull_profile(*remote),
+                _ => self.push_profile(ctx),
+            }
+        }
+    }
+
+    fn show_rotation(&self, ui: &mut egui::Ui, server: &ServerItem) {
+        let rotation = match self.rotations.rotation(&server.id) {
+            Some(rotation) => rotation,
+            None => {
+                let recent = self.rotations.recent(&server.id, 5);
+                if recent.len() > 1 {
+                    ui.label(format!("{} {}", self.tr("rotation_seen"), recent.join(" → ")));
+                }
+                return;
+            }
+        };
+        ui.label(format!("{} {} ↺", self.tr("rotation"), rotation.join(" → ")));
+        // Ротація закінчується поточною картою, тож наступні - з її початку
+        let average = self.rotations.average_minutes(&server.id);
+        for (index, map) in rotation.iter().take(rotation.len() - 1).enumerate() {
+            let steps = index as u64 + 1;
+            let eta = average.map(|minutes| format!(" (≈{})", report::hours(minutes * steps))).unwrap_or_default();
+            ui.small(format!("    +{} {}{}", steps, map, eta));
+        }
+    }
+
+    // Плитка на країну: чим більше серверів у поточному скані, тим яскравіша.
+    // Клік по плитці блокує або розблоковує країну.
+    fn show_country_map(&mut self, ui: &mut egui::Ui) {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for server in &self.view.servers {
+            *counts.entry(server.country.as_ref()).or_default() += 1;
+        }
+        let most = counts.values().copied().max().unwrap_or(0).max(1);
+
+        let cell = (ui.available_width() / worldmap::COLUMNS as f32).min(32.0);
+        let size = egui::vec2(cell * worldmap::COLUMNS as f32, cell * worldmap::ROWS as f32);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 4.0, egui::Color32::from_gray(20));
+
+        let mut hovered = None;
+        let mut toggled = None;
+        for (code, column, row) in worldmap::TILES {
+            let offset = egui::vec2(*column as f32 * cell, *row as f32 * cell);
+            let tile = egui::Rect::from_min_size(rect.min + offset, egui::vec2(cell, cell)).shrink(1.0);
+            let count = counts.get(code).copied().unwrap_or(0);
+            let banned = self.filters.is_banned(code);
+            let fill = if banned {
+                egui::Color32::from_rgb(90, 25, 25)
+            } else if count == 0 {
+                egui::Color32::from_gray(45)
+            } else {
+                let share = count as f32 / most as f32;
+                egui::Color32::from_rgb(20, (80.0 + 175.0 * share) as u8, 40)
+            };
+            painter.rect_filled(tile, 2.0, fill);
+            painter.text(tile.center(), egui::Align2::CENTER_CENTER, *code, egui::FontId::proportional(cell * 0.35), egui::Color32::WHITE);
+
+            if response.hover_pos().is_some_and(|pos| tile.contains(pos)) {
+                painter.rect_stroke(tile, 2.0, egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 165, 0)));
+                hovered = Some((*code, count, banned));
+            }
+            if response.clicked() && response.interact_pointer_pos().is_some_and(|pos| tile.contains(pos)) {
+                toggled = Some((*code, banned));
+            }
+        }
+        if let Some((code, count, banned)) = hovered {
+            let hint = if banned { self.tr("map_unban") } else { self.tr("map_ban") };
+            response.on_hover_text(format!("{}: {} {}\n{}", code, count, self.tr("map_servers"), hint));
+        }
+
+        let mut others: Vec<String> = counts
+            .iter()
+            .filter(|(code, _)| !worldmap::has_tile(code))
+            .map(|(code, count)| format!("{} {}", code, count))
+            .collect();
+        if !others.is_empty() {
+            others.sort();
+            ui.small(format!("{} {}", self.tr("map_other"), others.join(", ")));
+        }
+
+        if let Some((code, banned)) = toggled {
+            if banned {
+                self.filters.remove(&Filter::NoCountry(code.to_owned()));
+            } else {
+                self.filters.set(Filter::NoCountry(code.to_owned()));
+            }
+        }
+    }
+
+    // --- ПРАВИЛА СЕРВЕРА (A2S_RULES) ---
+
+    fn query_rules(&mut self, id: &str) {
+        let (ip, port) = match self.latest_servers().into_iter().find(|s| s.id == id) {
+            Some(server) => (server.ip.clone(), server.query_port),
+            None => return,
+        };
+        println!("[LOG] Querying rules of {}:{}", ip, port);
+        self.server_rules.insert(id.to_owned(), None);
+        let tx = self.rules_tx.clone();
+        let ctx = self.egui_ctx.clone();
+        let id = id.to_owned();
+        thread::spawn(move || {
+            let _ = tx.send((id, a2s::rules(&ip, port)));
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_rules(&mut self) {
+        while let Ok((id, result)) = self.rules_rx.try_recv() {
+            if let Err(e) = &result {
+                println!("[ERR] Rules query for {} failed: {}", id, e);
+            }
+            self.server_rules.insert(id, Some(result));
+        }
+    }
+
+    // --- ТЕСТ ПІНГУ ---
+
+    // Від кожного регіону - найбільш заповнений сервер з останніх сканувань
+    fn start_ping_test(&mut self) {
+        let servers = self.latest_servers();
+        let targets: Vec<(&'static str, String, String, u32)> = regions::REGIONS
+            .iter()
+            .filter_map(|region| {
+                servers
+                    .iter()
+                    .filter(|s| region.countries.contains(&s.country.as_ref()) && s.can_query())
+                    .max_by_key(|s| s.players)
+                    .map(|s| (region.key, s.full_name.clone(), s.ip.clone(), s.query_port))
+            })
+            .collect();
+
+        println!("[LOG] Ping test started for {} regions", targets.len());
+        self.ping_results = targets.iter().map(|(key, name, _, _)| (*key, (name.clone(), None))).collect();
+        let tx = self.ping_tx.clone();
+        let ctx = self.egui_ctx.clone();
+        thread::spawn(move || {
+            for (key, _, ip, port) in targets {
+                let _ = tx.send((key, a2s::ping(&ip, port)));
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    fn poll_ping(&mut self) {
+        while let Ok((key, result)) = self.ping_rx.try_recv() {
+            if let Some(entry) = self.ping_results.get_mut(key) {
+                entry.1 = Some(result);
+            }
+        }
+    }
+
+    fn show_ping_test(&mut self, ui: &mut egui::Ui) {
+        ui.small(self.tr("ping_hint"));
+        let running = self.ping_results.values().any(|(_, result)| result.is_none());
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!running, egui::Button::new(self.tr("ping_run"))).clicked() {
+                self.start_ping_test();
+            }
+            if running {
+                ui.spinner();
+            }
+        });
+        if self.ping_results.is_empty() {
+            return;
+        }
+        egui::Grid::new("ping_grid").num_columns(3).striped(true).show(ui, |ui| {
+            for region in &regions::REGIONS {
+                ui.label(self.tr(region.key));
+                match self.ping_results.get(region.key) {
+                    Some((server, result)) => {
+                        ui.label(server.as_str());
+                        match result {
+                            None => ui.spinner(),
+                            Some(Ok(latency)) => ui.label(format!("{} ms", latency.as_millis())),
+                            Some(Err(e)) => ui.colored_label(egui::Color32::RED, self.tr("ping_timeout")).on_hover_text(e.as_str()),
+                        };
+                    }
+                    None => {
+                        ui.label("-");
+                        ui.label(self.tr("ping_no_server"));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    // Кнопка регіону: якщо забанено все - розбанює, інакше банить усі країни
+    fn show_region_bans(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for region in &regions::REGIONS {
+                let banned = region.countries.iter().filter(|c| self.filters.is_banned(c)).count();
+                let (mark, hint) = if banned == region.countries.len() {
+                    ("☑", self.tr("region_unban"))
+                } else if banned > 0 {
+                    ("◐", self.tr("region_partial"))
+                } else {
+                    ("☐", self.tr("region_ban"))
+                };
+                let label = format!("{} {} ({}/{})", mark, self.tr(region.key), banned, region.countries.len());
+                if ui.button(label).on_hover_text(hint).clicked() {
+                    if banned == region.countries.len() {
+                        for code in region.countries {
+                            self.filters.remove(&Filter::NoCountry(code.to_string()));
+                        }
+                    } else {
+                        for code in region.countries {
+                            self.filters.set(Filter::NoCountry(code.to_string()));
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn is_pinned(&self, id: &str) -> bool {
+        self.pinned.iter().any(|s| s.id == id)
+    }
+
+    // Закріплюємо, поки є місце; вже закріплені пропускаємо
+    fn pin_family(&mut self, key: &str) {
+        let members: Vec<ServerItem> = self.family_members(key).into_iter().cloned().collect();
+        for server in members {
+            if self.pinned.len() >= MAX_PINNED {
+                break;
+            }
+            if !self.is_pinned(&server.id) {
+                self.pinned_matching.insert(server.id.clone());
+                self.pinned.push(server);
+            }
+        }
+    }
+
+    fn ban_family(&mut self, key: &str) {
+        let rule = format!("name~{}", key);
+        println!("[LOG] Banning server family: {}", rule);
+        self.add_block_rule(&rule);
+    }
+
+    fn hide_server(&mut self, id: &str) {
+        println!("[LOG] Hiding server {}", id);
+        self.add_block_rule(&format!("id:{}", id));
+    }
+
+    fn add_block_rule(&mut self, rule: &str) {
+        if !self.block_rules.is_empty() && !self.block_rules.ends_with('\n') {
+            self.block_rules.push('\n');
+        }
+        self.block_rules.push_str(rule);
+        self.block_rules.push('\n');
+        let (set, _) = RuleSet::parse(&self.block_rules);
+        self.apply_rules_to_views(&set);
+    }
+
+    fn toggle_pin(&mut self, id: &str) {
+        if let Some(pos) = self.pinned.iter().position(|s| s.id == id) {
+            self.pinned.remove(pos);
+        } else if self.pinned.len() < MAX_PINNED {
+            if let Some(server) = self.view.servers.iter().find(|s| s.id == id) {
+                self.pinned.push(server.clone());
+                self.pinned_matching.insert(server.id.clone());
+            }
+        }
+    }
+
+    // Оновлюємо збережені дані закріплених серверів свіжими з API
+    fn refresh_pins(&mut self, matched: &[ServerItem], unmatched: &[ServerItem]) {
+        for pin in self.pinned.iter_mut() {
+            if let Some(fresh) = matched.iter().find(|s| s.id == pin.id) {
+                *pin = fresh.clone();
+                self.pinned_matching.insert(pin.id.clone());
+            } else if let Some(fresh) = unmatched.iter().find(|s| s.id == pin.id) {
+                *pin = fresh.clone();
+                self.pinned_unmatched.insert(pin.id.clone());
+            }
+        }
+    }
+
+    fn show_server_row(
+        &self,
+        ui: &mut egui::Ui,
+        server: &ServerItem,
+        badge: Option<&str>,
+        actions: &mut RowActions,
+    ) -> egui::Rect {
+        let is_selected = self.selected_id.as_ref() == Some(&server.id);
+        let mut frame = egui::Frame::group(ui.style());
+        if is_selected {
+            frame = frame.stroke(egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 165, 0)));
+        }
+        let mut quick_clicked = false;
+        let response = frame.show(ui, |ui| {
+            let columns = self.row_columns;
+            ui.horizontal(|ui| {
+                if columns.country {
+                    let place = if columns.city { &server.text.place } else { &server.text.country };
+                    ui.colored_label(egui::Color32::from_rgb(255, 165, 0), place.as_str());
+                }
+                ui.colored_label(egui::Color32::LIGHT_BLUE, server.text.name.as_str());
+                if self.patch_day_active() {
+                    let color = if self.is_outdated(server) { egui::Color32::RED } else { egui::Color32::LIGHT_GREEN };
+                    ui.colored_label(color, egui::RichText::new(format!("🛠 {}", server.version)).strong());
+                }
+                if !self.quick_actions.is_empty() {
+                    ui.with_layout(self.direction().trailing(), |ui| {
+                        quick_clicked = self.show_quick_actions(ui, server, actions);
+                    });
+                }
+            });
+            if let Some(badge) = badge {
+                ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", badge));
+            }
+            if server.obfuscated {
+                ui.colored_label(egui::Color32::YELLOW, format!("🔤 {}", self.tr("obfuscated")))
+                    .on_hover_text(format!("{} {}", self.tr("obfuscated_hint"), normalize::readable(&server.full_name)));
+            }
+            if server.anticheat == Some(false) {
+                ui.colored_label(egui::Color32::RED, format!("🛡 {}", self.tr("anticheat_off")));
+            }
+            self.show_seed_badge(ui, server);
+            if let Some(seen) = server.first_seen.filter(|_| firstseen::is_new(server.first_seen, sessions::now_secs())) {
+                ui.colored_label(egui::Color32::LIGHT_BLUE, format!("🆕 {}", self.tr("new_server")))
+                    .on_hover_text(format!("{} {}", self.tr("first_seen"), self.first_seen_text(seen)));
+            }
+            if let Some(prime) = server.prime_time.filter(|prime| prime.contains(primetime::hour_of(sessions::now_secs()))) {
+                ui.colored_label(egui::Color32::LIGHT_GREEN, format!("🌙 {}", self.tr("prime_now")))
+                    .on_hover_text(self.prime_time_text(prime));
+            }
+            if columns.rank {
+                if let Some(text) = self.rank_text(server) {
+                    ui.colored_label(egui::Color32::GOLD, text);
+                }
+            }
+            if self.is_outdated(server) {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!("⚠ {} ({} → {})", self.tr("outdated"), server.version, self.latest_version()),
+                );
+            }
+            ui.horizontal(|ui| {
+                if columns.map_mode {
+                    ui.label(server.text.map_mode.as_str());
+                }
+                let eta = self.history.minutes_until(&server.id, server.players, self.seed_threshold).filter(|_| columns.seed_eta);
+                if let Some(minutes) = eta {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GREEN,
+                        format!("⏱ {} {} ~{:.0} {}", self.seed_threshold, self.tr("seed_eta"), minutes.ceil(), self.tr("minutes")),
+                    );
+                }
+                ui.with_layout(self.direction().trailing(), |ui| {
+                    let color = if server.is_nearly_full() { egui::Color32::RED } else { egui::Color32::GREEN };
+                    ui.colored_label(color, server.text.players.as_str());
+                    if columns.queue && (server.public_queue > 0 || server.reserved_queue > 0) {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            format!("{}+{} {}", server.public_queue, server.reserved_queue, self.tr("queue")),
+                        )
+                        .on_hover_text(self.tr("queue_hint"));
+                    }
+                    if let Some(trend) = self.history.trend(&server.id).filter(|_| columns.trend) {
+                        let (arrow, arrow_color) = match trend {
+                            Trend::Rising => ("▲", egui::Color32::GREEN),
+                            Trend::Falling => ("▼", egui::Color32::RED),
+                            Trend::Stable => ("▶", egui::Color32::GRAY),
+                        };
+                        ui.colored_label(arrow_color, arrow).on_hover_text(self.tr("trend_hint"));
+                    }
+                    if let Some(&previous) = self.previous_players.get(&server.id).filter(|_| columns.delta) {
+                        let delta = server.players as i64 - previous as i64;
+                        if delta != 0 {
+                            let delta_color = if delta > 0 { egui::Color32::GREEN } else { egui::Color32::RED };
+                            ui.colored_label(delta_color, format!("{:+}", delta))
+                                .on_hover_text(self.tr("delta_hint"));
+                        }
+                    }
+                });
+            });
+
+            let mut details = egui::collapsing_header::CollapsingState::load_with_default_open(
+                ui.ctx(),
+                egui::Id::new(("server_details", &server.id)),
+                false,
+            );
+            details.set_open(self.expanded_ids.contains(&server.id));
+            details.show_body_unindented(ui, |ui| self.show_server_details(ui, server, actions));
+        });
+
+        let mut row = response.response.interact(egui::Sense::click());
+        if !self.expanded_ids.contains(&server.id) {
+            row = row.on_hover_ui(|ui| self.show_row_tooltip(ui, server));
+        }
+        // Клік по кнопці швидкої дії не розгортає рядок
+        if row.clicked() && !quick_clicked {
+            actions.clicked = Some(server.id.clone());
+        }
+        response.response.rect
+    }
+
+    /// Кнопки в порядку з налаштувань; розкладка тут від кінця рядка, тому йдемо з кінця.
+    fn show_quick_actions(&self, ui: &mut egui::Ui, server: &ServerItem, actions: &mut RowActions) -> bool {
+        let mut clicked = false;
+        let pinned = self.is_pinned(&server.id);
+        for &action in self.quick_actions.iter().rev() {
+            let (enabled, hint) = match action {
+                QuickAction::Join => (server.can_join(), self.tr(action.key())),
+                QuickAction::Favorite => (pinned || self.pinned.len() < MAX_PINNED, if pinned { self.tr("unpin") } else { self.tr("pin") }),
+                QuickAction::CopyAddress => (!server.ip.is_empty() && server.game_port != 0, self.tr(action.key())),
+                QuickAction::Hide | QuickAction::CopyName => (true, self.tr(action.key())),
+            };
+            let button = egui::Button::new(action.icon()).small().selected(action == QuickAction::Favorite && pinned);
+            if !ui.add_enabled(enabled, button).on_hover_text(hint).clicked() {
+                continue;
+            }
+            clicked = true;
+            match action {
+                QuickAction::Join => actions.join = Some(server.id.clone()),
+                QuickAction::Favorite => actions.toggle_pin = Some(server.id.clone()),
+                QuickAction::Hide => actions.hide = Some(server.id.clone()),
+                QuickAction::CopyAddress => ui.output_mut(|o| o.copied_text = server.address()),
+                QuickAction::CopyName => ui.output_mut(|o| o.copied_text = server.full_name.clone()),
+            }
+        }
+        clicked
+    }
+
+    fn show_server_details(&self, ui: &mut egui::Ui, server: &ServerItem, actions: &mut RowActions) {
+        ui.separator();
+        egui::Grid::new(("server_details_grid", &server.id))
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(self.tr("search_name"));
+                ui.label(&server.full_name);
+                ui.end_row();
+                ui.label(self.tr("map"));
+                ui.label(server.map.as_ref());
+                ui.end_row();
+                ui.label(self.tr("mode"));
+                ui.label(server.mode.as_ref());
+                ui.end_row();
+                ui.label(self.tr("country"));
+                ui.label(server.place());
+                ui.end_row();
+                if let Some(km) = self.distance_km(server) {
+                    ui.label(self.tr("distance"));
+                    ui.label(format!("~{:.0} km ({})", km, self.home_city));
+                    ui.end_row();
+                }
+                ui.label(self.tr("queue_public"));
+                ui.label(server.public_queue.to_string());
+                ui.end_row();
+                ui.label(self.tr("queue_reserved"));
+                ui.label(server.reserved_queue.to_string());
+                ui.end_row();
+                ui.label(self.tr("version"));
+                ui.label(&server.version);
+                ui.end_row();
+                ui.label(self.tr("anticheat"));
+                match server.anticheat {
+                    Some(true) => ui.colored_label(egui::Color32::GREEN, "✔"),
+                    Some(false) => ui.colored_label(egui::Color32::RED, format!("✖ {}", self.tr("anticheat_off"))),
+                    None => ui.label("?"),
+                };
+                ui.end_row();
+                ui.label(self.tr("address"));
+                ui.label(if server.ip.is_empty() { "?".to_owned() } else { server.address() });
+                ui.end_row();
+                ui.label(self.tr("query_port"));
+                ui.label(if server.query_port == 0 { "?".to_owned() } else { server.query_port.to_string() });
+                ui.end_row();
+                ui.label("ID:");
+                ui.label(&server.id);
+                ui.end_row();
+                if let Some(seen) = server.first_seen {
+                    ui.label(self.tr("first_seen"));
+                    ui.label(self.first_seen_text(seen));
+                    ui.end_row();
+                }
+                if let Some(prime) = server.prime_time {
+                    ui.label(self.tr("prime_time"));
+                    ui.label(self.prime_time_text(prime));
+                    ui.end_row();
+                }
+            });
+        self.show_layer_info(ui, server);
+        if self.is_pinned(&server.id) {
+            self.show_uptime(ui, server);
+            self.show_ping_history(ui, server);
+            self.show_rotation(ui, server);
+        }
+        self.show_family(ui, server, actions);
+        self.show_server_rules(ui, server, actions);
+        self.show_traceroute(ui, server, actions);
+        ui.horizontal(|ui| {
+            if ui.add_enabled(server.can_join(), egui::Button::new(format!("▶ {}", self.tr("join")))).clicked() {
+                actions.join = Some(server.id.clone());
+            }
+            ui.hyperlink_to("BattleMetrics ↗", format!("https://www.battlemetrics.com/servers/squad/{}", server.id));
+            if ui.button("🔗").on_hover_text(self.tr("links_copy_server")).clicked() {
+                ui.output_mut(|o| o.copied_text = deeplink::server_link(&server.id));
+            }
+            if ui.button(format!("📋 {}", self.tr("copy_name"))).clicked() {
+                ui.output_mut(|o| o.copied_text = server.full_name.clone());
+            }
+            let has_address = !server.ip.is_empty() && server.game_port != 0;
+            if ui.add_enabled(has_address, egui::Button::new(format!("📋 {}", self.tr("copy_address")))).clicked() {
+                ui.output_mut(|o| o.copied_text = server.address());
+            }
+            if ui.button("📅").on_hover_text(self.tr("event_plan")).clicked() {
+                actions.plan_event = Some(server.id.clone());
+            }
+            let target = self.queue_assistant.is_target(&server.id);
+            let target_label = if target { self.tr("untarget") } else { self.tr("target") };
+            if ui.selectable_label(target, format!("🎯 {}", target_label)).on_hover_text(self.tr("target_hint")).clicked() {
+                actions.toggle_target = Some(server.id.clone());
+            }
+            let pinned = self.is_pinned(&server.id);
+            let pin_label = if pinned { self.tr("unpin") } else { self.tr("pin") };
+            let can_pin = pinned || self.pinned.len() < MAX_PINNED;
+            if ui.add_enabled(can_pin, egui::Button::new(format!("📌 {}", pin_label))).clicked() {
+                actions.toggle_pin = Some(server.id.clone());
+            }
+        });
+    }
+
+    fn show_uptime(&self, ui: &mut egui::Ui, server: &ServerItem) {
+        let now = sessions::now_secs();
+        let periods: Vec<String> = [7, 30]
+            .into_iter()
+            .map(|days| match self.uptime.over_days(&server.id, now, days) {
+                Some(uptime) => format!(
+                    "{}{}: {:.1}% ({} {})",
+                    days,
+                    self.tr("days_short"),
+                    uptime.percent,
+                    uptime.checks,
+                    self.tr("uptime_checks")
+                ),
+                None => format!("{}{}: {}", days, self.tr("days_short"), self.tr("no_data")),
+            })
+            .collect();
+        ui.label(format!("📶 {} {}", self.tr("uptime"), periods.join("   ")))
+            .on_hover_text(self.tr("uptime_hint"));
+        let interval = self.pin_polls.interval(&server.id).as_secs();
+        ui.small(format!("🔄 {}", self.tr("poll_every").replace("{s}", &interval.to_string()))).on_hover_text(self.tr("poll_hint"));
+    }
+
+    fn poll_ping_history(&mut self, ctx: &egui::Context) {
+        while let Ok(results) = self.ping_history_rx.try_recv() {
+            for (id, ms) in results {
+                self.ping_history.record(&id, ms);
+            }
+        }
+
+        let ids: Vec<String> = self.pinned.iter().map(|s| s.id.clone()).collect();
+        self.ping_history.retain(&ids);
+        let targets: Vec<(String, String, u32)> =
+            self.pinned.iter().filter(|s| s.can_query()).map(|s| (s.id.clone(), s.ip.clone(), s.query_port)).collect();
+        if targets.is_empty() || self.launch.demo || self.launch.replay.is_some() {
+            return;
+        }
+        if self.ping_history_checked.is_some_and(|at| at.elapsed() < latency::SAMPLE_INTERVAL) {
+            return;
+        }
+        self.ping_history_checked = Some(Instant::now());
+        ctx.request_repaint_after(latency::SAMPLE_INTERVAL);
+        let tx = self.ping_history_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            // Послідовно: паралельні UDP-запити самі додали б джитеру
+            let results = targets
+                .into_iter()
+                .map(|(id, ip, port)| (id, a2s::ping(&ip, port).ok().map(|time| time.as_millis() as u32)))
+                .collect();
+            let _ = tx.send(results);
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_ping_history(&self, ui: &mut egui::Ui, server: &ServerItem) {
+        let stats = match self.ping_history.stats(&server.id) {
+            Some(stats) => stats,
+            None => return,
+        };
+        let samples = self.ping_history.samples(&server.id);
+        ui.horizontal(|ui| {
+            let size = egui::vec2(160.0, 24.0);
+            let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+            let step = rect.width() / (latency::WINDOW - 1) as f32;
+            let peak = stats.max.max(1) as f32;
+            let mut previous: Option<egui::Pos2> = None;
+            for (index, sample) in samples.iter().enumerate() {
+                let x = rect.left() + index as f32 * step;
+                match sample {
+                    Some(ms) => {
+                        let point = egui::pos2(x, rect.bottom() - 2.0 - (rect.height() - 4.0) * (*ms as f32 / peak));
+                        if let Some(previous) = previous {
+                            painter.line_segment([previous, point], egui::Stroke::new(1.5, egui::Color32::from_rgb(90, 170, 230)));
+                        }
+                        previous = Some(point);
+                    }
+                    // Втрачений пакет - червона риска на всю висоту
+                    None => {
+                        painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())], egui::Stroke::new(1.0, egui::Color32::RED));
+                        previous = None;
+                    }
+                }
+            }
+            ui.label(format!(
+                "{} {} ms ({}–{}), {} {} ms, {} {:.0}%",
+                self.tr("ping_avg"),
+                stats.average,
+                stats.min,
+                stats.max,
+                self.tr("ping_jitter"),
+                stats.jitter,
+                self.tr("ping_loss"),
+                stats.loss_percent
+            ))
+            .on_hover_text(self.tr("ping_history_hint"));
+        });
+    }
+
+    fn family_members(&self, key: &str) -> Vec<&ServerItem> {
+        self.latest_servers()
+            .into_iter()
+            .filter(|s| families::key(&s.full_name).as_deref() == Some(key))
+            .collect()
+    }
+
+    fn summary(&self, server: &ServerItem) -> Rc<ServerSummary> {
+        if let Some(summary) = self.summaries.borrow().get(&server.id) {
+            return summary.clone();
+        }
+        let family = families::key(&server.full_name)
+            .map(|key| {
+                let names = self.family_members(&key).iter().map(|s| s.full_name.clone()).collect::<Vec<_>>();
+                (key, names)
+            })
+            .filter(|(_, names)| names.len() > 1);
+        // Закріплені мають повний журнал карт, інші - лише побачене за цей запуск
+        let mut recent_maps = self.rotations.recent(&server.id, 3);
+        if recent_maps.is_empty() {
+            recent_maps = self.history.recent_maps(&server.id);
+        }
+        let summary = Rc::new(ServerSummary { family, recent_maps });
+        self.summaries.borrow_mut().insert(server.id.clone(), summary.clone());
+        summary
+    }
+
+    // Підказка над рядком: головне з деталей, щоб не розгортати рядок
+    fn show_row_tooltip(&self, ui: &mut egui::Ui, server: &ServerItem) {
+        let summary = self.summary(server);
+        ui.strong(&server.full_name);
+        ui.label(format!("{} {} ({})", self.tr("map"), server.map, server.mode));
+        ui.label(format!(
+            "{} {}/{}   {} {}   {} {}",
+            self.tr("tooltip_players"),
+            server.players,
+            server.max_players,
+            self.tr("queue_public"),
+            server.public_queue,
+            self.tr("queue_reserved"),
+            server.reserved_queue
+        ));
+        self.show_seed_badge(ui, server);
+        if let Some((key, names)) = &summary.family {
+            ui.label(format!("👪 {} \"{}\" ({})", self.tr("family"), key, names.len()));
+        }
+        if summary.recent_maps.len() > 1 {
+            ui.label(format!("{} {}", self.tr("recent_maps"), summary.recent_maps.join(" → ")));
+        }
+        if let Some(stats) = self.ping_history.stats(&server.id) {
+            ui.label(format!("📶 {} {} ms", self.tr("tooltip_ping"), stats.average));
+        }
+        ui.small(self.tr("tooltip_hint"));
+    }
+
+    // Та сама спільнота в інших регіонах: закріпити чи забанити всіх разом
+    fn show_family(&self, ui: &mut egui::Ui, server: &ServerItem, actions: &mut RowActions) {
+        let summary = self.summary(server);
+        let (key, names) = match &summary.family {
+            Some(family) => family,
+            None => return,
+        };
+        ui.horizontal(|ui| {
+            ui.label(format!("👪 {} \"{}\" ({})", self.tr("family"), key, names.len()))
+                .on_hover_text(names.join("\n"));
+            if ui.button(format!("📌 {}", self.tr("family_pin"))).clicked() {
+                actions.pin_family = Some(key.clone());
+            }
+            if ui.button(format!("🚫 {}", self.tr("family_ban"))).on_hover_text(format!("name~{}", key)).clicked() {
+                actions.ban_family = Some(key.clone());
+            }
+        });
+    }
+
+    // Сирі правила A2S - для адмінів і допитливих, тому згорнуто
+    fn show_server_rules(&self, ui: &mut egui::Ui, server: &ServerItem, actions: &mut RowActions) {
+        egui::CollapsingHeader::new(format!("⚙ {}", self.tr("server_rules")))
+            .id_source(("server_rules", &server.id))
+            .show(ui, |ui| {
+                let state = self.server_rules.get(&server.id);
+                let can_query = server.can_query() && !matches!(state, Some(None));
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(can_query, egui::Button::new(format!("🔄 {}", self.tr("server_rules_query")))).clicked() {
+                        actions.query_rules = Some(server.id.clone());
+                    }
+                    if matches!(state, Some(None)) {
+                        ui.spinner();
+                    }
+                });
+                match state {
+                    Some(Some(Ok(rules))) if rules.is_empty() => {
+                        ui.label(self.tr("server_rules_empty"));
+                    }
+                    Some(Some(Ok(rules))) => {
+                        egui::Grid::new(("server_rules_grid", &server.id))
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (key, value) in rules {
+                                    ui.monospace(key);
+                                    ui.monospace(value);
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    Some(Some(Err(e))) => {
+                        ui.colored_label(egui::Color32::RED, e.as_str());
+                    }
+                    _ => {}
+                }
+            });
+    }
+
+    fn show_traceroute(&self, ui: &mut egui::Ui, server: &ServerItem, actions: &mut RowActions) {
+        egui::CollapsingHeader::new(format!("🛰 {}", self.tr("traceroute")))
+            .id_source(("traceroute", &server.id))
+            .show(ui, |ui| {
+                let trace = self.trace.as_ref().filter(|trace| trace.server_id == server.id);
+                let running = trace.is_some_and(|trace| trace.running);
+                ui.horizontal(|ui| {
+                    let button = egui::Button::new(format!("▶ {}", self.tr("traceroute_run")));
+                    if ui.add_enabled(!server.ip.is_empty() && !running, button).clicked() {
+                        actions.traceroute = Some(server.id.clone());
+                    }
+                    if running {
+                        ui.spinner();
+                    }
+                });
+                let trace = match trace {
+                    Some(trace) => trace,
+                    None => {
+                        ui.small(self.tr("traceroute_hint"));
+                        return;
+                    }
+                };
+                let loss_from = trace.loss_starts_at();
+                egui::Grid::new(("traceroute_grid", &server.id))
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("#");
+                        ui.strong(self.tr("traceroute_host"));
+                        ui.strong(self.tr("ping_avg"));
+                        ui.strong(self.tr("ping_loss"));
+                        ui.end_row();
+                        for hop in &trace.hops {
+                            let lossy = loss_from.is_some_and(|from| hop.number >= from);
+                            let color = if lossy { egui::Color32::RED } else { ui.visuals().text_color() };
+                            ui.monospace(hop.number.to_string());
+                            ui.monospace(if hop.host.is_empty() { "*" } else { hop.host.as_str() });
+                            ui.monospace(hop.average().map_or("*".to_owned(), |ms| format!("{:.0} ms", ms)));
+                            ui.colored_label(color, format!("{:.0}%", hop.loss_percent()));
+                            ui.end_row();
+                        }
+                    });
+                if let Some(from) = loss_from {
+                    ui.colored_label(egui::Color32::RED, format!("{} #{}", self.tr("traceroute_loss_from"), from));
+                }
+                if let Some(error) = &trace.error {
+                    ui.colored_label(egui::Color32::RED, error.as_str());
+                }
+                if !trace.running && ui.small_button(format!("📋 {}", self.tr("traceroute_copy"))).clicked() {
+                    let lines: Vec<String> = trace
+                        .hops
+                        .iter()
+                        .map(|hop| {
+                            let probes: Vec<String> =
+                                hop.probes.iter().map(|p| p.map_or("*".to_owned(), |ms| format!("{:.0} ms", ms))).collect();
+                            format!("{:>2}  {:<15}  {}", hop.number, hop.host, probes.join("  "))
+                        })
+                        .collect();
+                    ui.output_mut(|o| o.copied_text = format!("traceroute {}\n{}", trace.target, lines.join("\n")));
+                }
+            });
+    }
+
+    // Активні фільтри як кнопки "✕"; повертає true, якщо якийсь зняли
+    fn show_filter_chips(&mut self, ui: &mut egui::Ui) -> bool {
+        let active: Vec<Filter> = self.filters.iter().cloned().collect();
+        if active.is_empty() {
+            return false;
+        }
+        let mut removed = None;
+        ui.horizontal_wrapped(|ui| {
+            for filter in active {
+                if ui.small_button(format!("{} ✕", filter.label())).on_hover_text(self.tr("chip_remove")).clicked() {
+                    removed = Some(filter);
+                }
+            }
+        });
+        match removed {
+            Some(filter) => {
+                println!("[LOG] Filter removed: {}", filter.label());
+                self.filters.remove(&filter);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn scan_filters(&self, filters: &FilterSet) -> ScanFilters {
+        let mut filters = filters.clone();
+        // У день патча населення непередбачуване - фільтри гравців і черги не діють
+        if self.patch_day_active() {
+            filters.clear_like(&Filter::MinPlayers(0));
+            filters.clear_like(&Filter::MaxPlayers(0));
+            filters.clear_like(&Filter::MaxPublicQueue(0));
+            filters.clear_like(&Filter::MaxReservedQueue(0));
+        }
+        ScanFilters {
+            extra_params: valid_params(&filters.params()),
+            rules: RuleSet::parse(&self.block_rules).0,
+            pinned_ids: self.pinned.iter().map(|s| s.id.clone()).collect(),
+            layers: self.layer_db.clone(),
+            filters,
+        }
+    }
+
+    // --- ЗБЕРЕЖЕНІ ПОШУКИ ---
+
+    fn save_current_search(&mut self) {
+        let name = self.new_search_name.trim().to_owned();
+        if name.is_empty() {
+            return;
+        }
+        let filters = self.filters.clone();
+        match self.saved_searches.iter_mut().find(|s| s.name == name) {
+            Some(existing) => existing.filters = filters,
+            None => self.saved_searches.push(SavedSearch { name: name.clone(), filters, monitor: false }),
+        }
+        // Фільтри змінились - базовий набір серверів треба зібрати заново
+        self.monitors.remove(&name);
+        self.new_search_name.clear();
+    }
+
+    fn load_search(&mut self, index: usize) {
+        if let Some(search) = self.saved_searches.get(index) {
+            let filters = search.filters.clone();
+            self.tabs[self.active_tab].title = search.name.clone();
+            if let Some(state) = self.monitors.get_mut(&search.name) {
+                state.new_count = 0;
+            }
+            self.filters = filters;
+            self.run_scan(None);
+        }
+    }
+
+    // Моніторинг перевіряє лише першу сторінку (найбільш заповнені сервери)
+    fn poll_monitors(&mut self, ctx: &egui::Context) {
+        while let Ok((name, ids)) = self.monitor_rx.try_recv() {
+            let state = self.monitors.entry(name.clone()).or_default();
+            match ids {
+                Some(ids) => {
+                    let fresh = state.update(ids);
+                    if fresh > 0 {
+                        println!("[LOG] Saved search \"{}\": {} new servers", name, fresh);
+                        self.notify(
+                            format!("⭐ {}: {} {}", name, fresh, self.tr("monitor_new")),
+                            NotificationAction::SavedSearch(name.clone()),
+                        );
+                    }
+                }
+                None => state.pending = false,
+            }
+        }
+
+        let provider = match &self.provider {
+            Some(provider) => provider.clone(),
+            None => return,
+        };
+        let mut monitoring = false;
+        for search in self.saved_searches.iter().filter(|s| s.monitor) {
+            monitoring = true;
+            let state = self.monitors.entry(search.name.clone()).or_default();
+            if !state.is_due() || self.scheduler.wait_time() > Duration::ZERO {
+                continue;
+            }
+            self.scheduler.started();
+            state.pending = true;
+            state.last_checked = Some(Instant::now());
+
+            let mut filters = self.scan_filters(&search.filters);
+            filters.pinned_ids.clear();
+            let name = search.name.clone();
+            let tx = self.monitor_tx.clone();
+            let provider = provider.clone();
+            let ctx = ctx.clone();
+            thread::spawn(move || {
+                let result = fetch_servers(provider.as_ref(), filters, String::new());
+                let ids = match result.error {
+                    Some(_) => None,
+                    None => Some(result.servers.into_iter().map(|s| s.id).collect()),
+                };
+                let _ = tx.send((name, ids));
+                ctx.request_repaint();
+            });
+        }
+        if monitoring {
+            ctx.request_repaint_after(MONITOR_INTERVAL);
+        }
+    }
+
+    // --- ПАКЕТНІ ЗАПИТИ ЗА ID (batch.rs) ---
+
+    // Присутність закріплених і асистент черги - одним запитом на всіх,
+    // кому вже час (або стане протягом batch::COALESCE).
+    fn poll_batch(&mut self, ctx: &egui::Context) {
+        while let Ok((batch, fetched)) = self.batch_rx.try_recv() {
+            if !batch.presence.is_empty() {
+                self.receive_presence(&batch.presence, fetched.as_ref());
+            }
+            if !batch.queue.is_empty() {
+                self.receive_queue(ctx, fetched.as_ref().map(|fetched| fetched.servers.as_slice()));
+            }
+        }
+
+        let watched: Vec<String> = self.pinned.iter().map(|s| s.id.clone()).collect();
+        self.pin_polls.retain(&watched);
+        let now = Instant::now();
+        if !watched.is_empty() {
+            ctx.request_repaint_after(self.pin_polls.wait_time(&watched, now).max(scheduler::POLL_MIN));
+        }
+        if self.queue_assistant.running {
+            ctx.request_repaint_after(queueing::CHECK_INTERVAL);
+        }
+        if self.scheduler.wait_time() > Duration::ZERO {
+            return;
+        }
+        let presence_due = self.presence.is_due_within(Duration::ZERO) && !self.pin_polls.due(&watched, now).is_empty();
+        let queue_due = self.queue_assistant.is_due_within(Duration::ZERO);
+        if !presence_due && !queue_due {
+            return;
+        }
+        let provider = match &self.provider {
+            Some(provider) => provider.clone(),
+            None => return,
+        };
+
+        let mut batch = Batch::default();
+        if self.presence.is_due_within(batch::COALESCE) {
+            batch.presence = self.pin_polls.due(&watched, now + batch::COALESCE);
+        }
+        if self.queue_assistant.is_due_within(batch::COALESCE) {
+            batch.queue = self.queue_assistant.targets.clone();
+        }
+        if !batch.presence.is_empty() {
+            self.presence.started();
+        }
+        if !batch.queue.is_empty() {
+            self.queue_assistant.started();
+        }
+        self.scheduler.started();
+
+        // Без фільтрів і банів: сервери вибрані вручну
+        let mut filters = FilterSet::default();
+        filters.set(Filter::MaxPlayers(1000));
+        let chunks = batch.chunks();
+        println!(
+            "[LOG] Batch request: {} pinned, {} queue targets in {} request(s)",
+            batch.presence.len(),
+            batch.queue.len(),
+            chunks.len()
+        );
+        let layers = self.layer_db.clone();
+        let tx = self.batch_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let mut fetched = Some(Fetched::default());
+            for ids in chunks {
+                let scan = ScanFilters {
+                    filters: filters.clone(),
+                    extra_params: vec![("filter[ids][whitelist]".to_owned(), ids)],
+                    rules: RuleSet::default(),
+                    pinned_ids: HashSet::new(),
+                    layers: layers.clone(),
+                };
+                let result = fetch_servers(provider.as_ref(), scan, String::new());
+                if let Some(e) = result.error {
+                    println!("[ERR] Batch request failed: {}", e);
+                    fetched = None;
+                    break;
+                }
+                if let Some(fetched) = fetched.as_mut() {
+                    fetched.servers.extend(result.servers);
+                    fetched.ids.extend(result.raw_ids);
+                }
+            }
+            let _ = tx.send((batch, fetched));
+            ctx.request_repaint();
+        });
+    }
+
+    // Окремий запит за закріпленими id: звичайний скан бачить тільки
+    // завантажені сторінки і не може сказати, що сервер офлайн.
+    // У батчі лише ті, кому вже час за scheduler::AdaptivePolls.
+    fn receive_presence(&mut self, polled: &[String], fetched: Option<&Fetched>) {
+        let at = Instant::now();
+        let fetched = match fetched {
+            Some(fetched) => fetched,
+            None => {
+                self.presence.failed();
+                for id in polled {
+                    self.pin_polls.record(id, None, at);
+                }
+                return;
+            }
+        };
+        // Онлайн - за сирими id: для присутності їх досить, навіть якщо решта
+        // запису зіпсована і сервер не потрапив у servers
+        let parsed: HashMap<&str, &ServerItem> = fetched.servers.iter().map(|s| (s.id.as_str(), s)).collect();
+        let now = sessions::now_secs();
+        for id in polled {
+            self.uptime.record(id, now, fetched.ids.contains(id));
+            self.pin_polls.record(id, parsed.get(id.as_str()).map(|s| s.players), at);
+            // Часті перевірки закріплених заповнюють їхні години швидше за сканування
+            if let Some(server) = parsed.get(id.as_str()) {
+                self.prime_times.record(id, now, server.players);
+            }
+        }
+        for pin in self.pinned.iter_mut() {
+            if let Some(&fresh) = parsed.get(pin.id.as_str()) {
+                let first_seen = pin.first_seen;
+                *pin = fresh.clone();
+                pin.first_seen = first_seen;
+                pin.prime_time = self.prime_times.prime_time(&pin.id, &pin.country);
+            }
+        }
+        let watched: Vec<String> = self.pinned.iter().map(|s| s.id.clone()).collect();
+        for (id, is_online) in self.presence.update(&watched, polled, &fetched.ids) {
+            let name = self.pinned.iter().find(|s| s.id == id).map(|s| s.name.clone()).unwrap_or(id.clone());
+            let status = if is_online { self.tr("came_online") } else { self.tr("went_offline") };
+            println!("[LOG] Pinned server {}: {}", name, status);
+            self.notify(format!("📌 {} {}", name, status), NotificationAction::Server(id));
+        }
+    }
+
+    // --- ЗАПЛАНОВАНІ ПОДІЇ ---
+
+    fn plan_event(&mut self, id: &str) {
+        let server = self.latest_servers().into_iter().find(|s| s.id == id).map(|s| (s.id.clone(), s.full_name.clone()));
+        if let Some((id, name)) = server {
+            self.new_event.server_id = id;
+            self.new_event.server_name = name;
+        }
+        self.section = Section::Events;
+    }
+
+    fn add_event(&mut self) {
+        let start = match events::parse_local(&self.new_event_date, &self.new_event_time) {
+            Ok(start) => start,
+            Err(e) => {
+                self.events_status = e;
+                return;
+            }
+        };
+        let mut event = self.new_event.clone();
+        event.title = event.title.trim().to_owned();
+        if event.title.is_empty() {
+            event.title = self.tr("event_default_title").to_owned();
+        }
+        event.start = start;
+        self.events.push(event);
+        self.events.sort_by_key(|e| e.start);
+        self.new_event = PlannedEvent { minutes: self.new_event.minutes, ..PlannedEvent::default() };
+        self.write_ics();
+    }
+
+    // Перезаписуємо після кожної зміни: на файл можна підписатися в календарі
+    fn write_ics(&mut self) {
+        let ics = events::to_ics(&self.events, sessions::now_secs());
+        match fs::write(&self.ics_path, ics) {
+            Ok(()) => {
+                println!("[LOG] Wrote {} events to {}", self.events.len(), self.ics_path);
+                self.events_status = format!("{} {}", self.tr("exported_to"), self.ics_path);
+            }
+            Err(e) => {
+                println!("[ERR] Failed to write calendar file: {}", e);
+                self.events_status = e.to_string();
+            }
+        }
+    }
+
+    fn show_events(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(format!("📅 {}", self.tr("nav_events")));
+            if ui.button(format!("⚔ {}", self.tr("veto_title"))).clicked() {
+                self.show_veto = true;
+            }
+        });
+        if self.new_event_date.is_empty() {
+            self.new_event_date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        }
+
+        let mut servers: Vec<(String, String)> = self.pinned.iter().map(|s| (s.id.clone(), s.full_name.clone())).collect();
+        for server in self.latest_servers() {
+            if !servers.iter().any(|(id, _)| id == &server.id) {
+                servers.push((server.id.clone(), server.full_name.clone()));
+            }
+        }
+        let (title_label, server_label, when_label, duration_label) =
+            (self.tr("event_title"), self.tr("event_server"), self.tr("event_when"), self.tr("event_duration"));
+        let (no_server, add_label, minutes_label) = (self.tr("event_no_server"), self.tr("event_add"), self.tr("minutes"));
+        let mut add = false;
+        egui::Grid::new("new_event_grid").num_columns(2).show(ui, |ui| {
+            ui.label(title_label);
+            ui.text_edit_singleline(&mut self.new_event.title);
+            ui.end_row();
+            ui.label(server_label);
+            let selected = if self.new_event.server_id.is_empty() { no_server.to_owned() } else { self.new_event.server_name.clone() };
+            egui::ComboBox::from_id_source("event_server").selected_text(selected).width(260.0).show_ui(ui, |ui| {
+                if ui.selectable_label(self.new_event.server_id.is_empty(), no_server).clicked() {
+                    self.new_event.server_id.clear();
+                    self.new_event.server_name.clear();
+                }
+                for (id, name) in &servers {
+                    if ui.selectable_label(&self.new_event.server_id == id, name).clicked() {
+                        self.new_event.server_id = id.clone();
+                        self.new_event.server_name = name.clone();
+                    }
+                }
+            });
+            ui.end_row();
+            ui.label(when_label);
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.new_event_date).desired_width(90.0).hint_text("YYYY-MM-DD"));
+                ui.add(egui::TextEdit::singleline(&mut self.new_event_time).desired_width(50.0).hint_text("HH:MM"));
+            });
+            ui.end_row();
+            ui.label(duration_label);
+            ui.add(egui::DragValue::new(&mut self.new_event.minutes).clamp_range(15..=600).speed(5).suffix(format!(" {}", minutes_label)));
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            add = ui.button(format!("➕ {}", add_label)).clicked();
+            ui.label(self.tr("event_file"));
+            ui.text_edit_singleline(&mut self.ics_path);
+        });
+        if add {
+            self.add_event();
+        }
+        ui.small(self.tr("event_hint"));
+        if !self.events_status.is_empty() {
+            ui.small(self.events_status.as_str());
+        }
+        ui.separator();
+
+        let now = sessions::now_secs();
+        let mut remove = None;
+        let mut export = false;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if self.events.is_empty() {
+                ui.label(self.tr("event_none"));
+            }
+            for (index, event) in self.events.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let past = event.start + event.minutes as u64 * 60 < now;
+                    let text = format!("{}  {}", events::format_local(event.start), event.title);
+                    if past {
+                        ui.weak(text);
+                    } else {
+                        ui.label(text);
+                    }
+                    if !event.server_name.is_empty() {
+                        ui.small(event.server_name.as_str());
+                    }
+                    if ui.small_button("✕").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if !self.events.is_empty() {
+                export = ui.button(format!("💾 {}", self.tr("event_export"))).clicked();
+            }
+        });
+        if let Some(index) = remove {
+            self.events.remove(index);
+            self.write_ics();
+        }
+        if export {
+            self.write_ics();
+        }
+    }
+
+    // --- ОГОЛОШЕННЯ ---
+
+    fn poll_announcements(&mut self, ctx: &egui::Context) {
+        while let Ok(result) = self.announcements_rx.try_recv() {
+            match result {
+                Ok(items) => {
+                    println!("[LOG] Announcements: {} items", items.len());
+                    self.announcements.replace(items);
+                }
+                Err(e) => println!("[ERR] Failed to load announcements: {}", e),
+            }
+        }
+
+        let url = self.announcements.url.trim().to_owned();
+        if url.is_empty() {
+            return;
+        }
+        if self.announcements_checked.is_some_and(|at| at.elapsed() < announcements::CHECK_INTERVAL) {
+            return;
+        }
+        self.announcements_checked = Some(Instant::now());
+        ctx.request_repaint_after(announcements::CHECK_INTERVAL);
+        let tx = self.announcements_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(announcements::fetch(&url));
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_announcement(&mut self, ui: &mut egui::Ui) {
+        let item = match self.announcements.current() {
+            Some(item) => item.clone(),
+            None => return,
+        };
+        let mut dismiss = false;
+        let mut plan = false;
+        egui::Frame::group(ui.style())
+            .fill(egui::Color32::from_rgb(60, 50, 10))
+            .show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("📣 {}", item.text));
+                    if !item.link.is_empty() {
+                        ui.hyperlink_to("↗", &item.link);
+                    }
+                    ui.with_layout(self.direction().trailing(), |ui| {
+                        dismiss = ui.small_button("✕").on_hover_text(self.tr("announcements_dismiss")).clicked();
+                        plan = ui.small_button("📅").on_hover_text(self.tr("event_plan")).clicked();
+                    });
+                });
+            });
+        if dismiss {
+            self.announcements.dismiss(&item.id);
+        }
+        if plan {
+            self.new_event.title = item.text.clone();
+            self.section = Section::Events;
+        }
+    }
+
+    // --- БАЗА ШАРІВ ---
+
+    fn layer_db_path(&self) -> Option<PathBuf> {
+        self.config_path.as_deref().and_then(Path::parent).map(|dir| dir.join(layerdb::FILE_NAME))
+    }
+
+    fn show_vehicle_filters(&mut self, ui: &mut egui::Ui) {
+        let hint = self.tr("filter_vehicles_hint");
+        for class in VehicleClass::ALL {
+            let label = match class {
+                VehicleClass::Helicopters => self.tr("filter_helicopters"),
+                VehicleClass::HeavyArmor => self.tr("filter_armor"),
+            };
+            let mut wanted = self.filters.wants_vehicles(class);
+            if ui.checkbox(&mut wanted, label).on_hover_text(hint).changed() {
+                if wanted {
+                    self.filters.set(Filter::Vehicles(class));
+                } else {
+                    self.filters.remove(&Filter::Vehicles(class));
+                }
+            }
+        }
+    }
+
+    fn update_layer_db(&mut self, ctx: &egui::Context) {
+        let (url, path) = match (self.layer_db_url.trim(), self.layer_db_path()) {
+            ("", _) | (_, None) => return,
+            (url, Some(path)) => (url.to_owned(), path),
+        };
+        println!("[LOG] Updating layer database from {}", url);
+        self.layer_db_busy = true;
+        let tx = self.layer_db_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(layerdb::update(&url, &path));
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_layer_db(&mut self) {
+        while let Ok(result) = self.layer_db_rx.try_recv() {
+            self.layer_db_busy = false;
+            match result {
+                Ok(db) => {
+                    println!("[LOG] Layer database updated: {} layers", db.count());
+                    self.layer_db = Arc::new(db);
+                    self.layer_db_status.clear();
+                }
+                Err(e) => {
+                    println!("[ERR] Failed to update layer database: {}", e);
+                    self.layer_db_status = e;
+                }
+            }
+        }
+    }
+
+    fn show_layer_db_settings(&mut self, ui: &mut egui::Ui) {
+        let source = if self.layer_db.downloaded { self.tr("layer_db_downloaded") } else { self.tr("layer_db_embedded") };
+        ui.label(format!("{} {} (v{}, {})", self.layer_db.count(), self.tr("layer_db_layers"), self.layer_db.version, source));
+        ui.horizontal(|ui| {
+            ui.label(self.tr("layer_db_url"));
+            ui.text_edit_singleline(&mut self.layer_db_url);
+            let can_update = !self.layer_db_busy && !self.layer_db_url.trim().is_empty() && self.config_path.is_some();
+            if ui.add_enabled(can_update, egui::Button::new(self.tr("layer_db_update"))).clicked() {
+                self.update_layer_db(ui.ctx());
+            }
+            if self.layer_db_busy {
+                ui.spinner();
+            }
+        });
+        if !self.layer_db_status.is_empty() {
+            ui.colored_label(egui::Color32::RED, self.layer_db_status.as_str());
+        }
+        ui.small(self.tr("layer_db_hint"));
+    }
+
+    fn show_layer_info(&self, ui: &mut egui::Ui, server: &ServerItem) {
+        let info = match self.layer_db.get(&server.map) {
+            Some(info) => info,
+            None => return,
+        };
+        egui::CollapsingHeader::new(format!("🗺 {}", self.tr("layer_info")))
+            .id_source(("layer_info", &server.id))
+            .show(ui, |ui| {
+                ui.label(format!("{} ({}) - {:.1} km", info.map, info.mode, info.size_km));
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        for (team, vehicles) in [(self.tr("layer_team1"), &info.team1), (self.tr("layer_team2"), &info.team2)] {
+                            ui.strong(team);
+                            if vehicles.is_empty() {
+                                ui.small(self.tr("layer_no_vehicles"));
+                            }
+                            for vehicle in vehicles {
+                                ui.small(vehicle.as_str());
+                            }
+                        }
+                    });
+                    if !info.flags.is_empty() {
+                        draw_flags(ui, &info.flags);
+                    }
+                });
+            });
+    }
+
+    // --- ВЕТО КАРТ ---
+
+    fn show_veto_window(&mut self, ctx: &egui::Context) {
+        if !self.show_veto {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(format!("⚔ {}", self.tr("veto_title")))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(360.0)
+            .show(ctx, |ui| match self.veto_session.take() {
+                Some(session) => self.veto_session = self.show_veto_session(ui, session),
+                None => self.show_veto_setup(ui),
+            });
+        self.show_veto = open;
+    }
+
+    fn show_veto_setup(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("veto_setup").num_columns(2).show(ui, |ui| {
+            ui.label(self.tr("veto_teams"));
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.veto.team_a).desired_width(120.0));
+                ui.label("vs");
+                ui.add(egui::TextEdit::singleline(&mut self.veto.team_b).desired_width(120.0));
+            });
+            ui.end_row();
+            ui.label(self.tr("veto_format"));
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.veto.format, veto::Format::BestOf1, "Bo1").on_hover_text(self.tr("veto_bo1_hint"));
+                ui.selectable_value(&mut self.veto.format, veto::Format::BestOf3, "Bo3").on_hover_text(self.tr("veto_bo3_hint"));
+            });
+            ui.end_row();
+        });
+        ui.label(self.tr("veto_pool"));
+        ui.add(egui::TextEdit::multiline(&mut self.veto.pool).desired_rows(8));
+        ui.horizontal(|ui| {
+            let pool_size = Veto::new(&self.veto).pool.len();
+            // Для Bo3 потрібні два вибори і вирішальна
+            let minimum = if self.veto.format == veto::Format::BestOf3 { 5 } else { 2 };
+            if ui.add_enabled(pool_size >= minimum, egui::Button::new(format!("▶ {}", self.tr("veto_start")))).clicked() {
+                println!("[LOG] Map veto started: {} maps", pool_size);
+                self.veto_session = Some(Veto::new(&self.veto));
+            }
+            if ui.button(self.tr("veto_default_pool")).clicked() {
+                self.veto.pool = veto::DEFAULT_POOL.join("\n");
+            }
+            ui.small(format!("{} {}", pool_size, self.tr("veto_maps")));
+        });
+    }
+
+    // Повертає None, щоб закінчити вето і повернутись до налаштування
+    fn show_veto_session(&self, ui: &mut egui::Ui, mut session: Veto) -> Option<Veto> {
+        match session.next() {
+            Some((team, action)) => {
+                let verb = if action == veto::Action::Ban { self.tr("veto_bans") } else { self.tr("veto_picks") };
+                let color = if action == veto::Action::Ban { egui::Color32::RED } else { egui::Color32::LIGHT_GREEN };
+                ui.colored_label(color, format!("{} {}…", session.teams[team], verb));
+                let mut chosen = None;
+                ui.horizontal_wrapped(|ui| {
+                    for map in session.remaining() {
+                        if ui.button(map.as_str()).clicked() {
+                            chosen = Some(map.clone());
+                        }
+                    }
+                });
+                if let Some(map) = chosen {
+                    session.choose(&map);
+                }
+            }
+            None => {
+                ui.strong(format!("{} {}", self.tr("veto_done"), session.maps().iter().map(|m| m.as_str()).collect::<Vec<_>>().join(", ")));
+            }
+        }
+        ui.separator();
+        ui.label(session.to_text());
+        let mut keep = true;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!session.steps.is_empty(), egui::Button::new(format!("↶ {}", self.tr("veto_undo")))).clicked() {
+                session.undo();
+            }
+            if ui.button(format!("📋 {}", self.tr("copy_text"))).clicked() {
+                ui.output_mut(|o| o.copied_text = session.to_text());
+            }
+            if ui.button(self.tr("veto_new")).clicked() {
+                keep = false;
+            }
+        });
+        keep.then_some(session)
+    }
+
+    // --- ДЕНЬ ПАТЧА ---
+
+    fn patch_day_active(&self) -> bool {
+        self.patch_day.is_active(sessions::now_secs())
+    }
+
+    fn set_patch_day(&mut self, active: bool) {
+        let now = sessions::now_secs();
+        self.patch_day.until = match &self.patch_note {
+            Some(note) if active && note.is_fresh(now) => note.date + patchday::WINDOW_SECS,
+            _ if active => now + patchday::WINDOW_SECS,
+            _ => 0,
+        };
+        println!("[LOG] Patch-day mode {}", if active { "on" } else { "off" });
+        if self.view.first_load_done {
+            self.run_scan(None);
+        }
+    }
+
+    fn poll_patch_day(&mut self, ctx: &egui::Context) {
+        while let Ok(result) = self.patch_rx.try_recv() {
+            match result {
+                Ok(note) => {
+                    self.patch_note = note;
+                    self.enter_patch_day();
+                }
+                Err(e) => println!("[ERR] Failed to load Steam news: {}", e),
+            }
+        }
+
+        if self.patch_checked.is_some_and(|at| at.elapsed() < patchday::CHECK_INTERVAL) {
+            return;
+        }
+        self.patch_checked = Some(Instant::now());
+        ctx.request_repaint_after(patchday::CHECK_INTERVAL);
+        let tx = self.patch_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(patchday::fetch());
+            ctx.request_repaint();
+        });
+    }
+
+    // На кожне оновлення реагуємо один раз: вимкнений вручну режим не повертається
+    fn enter_patch_day(&mut self) {
+        let note = match &self.patch_note {
+            Some(note) if note.is_fresh(sessions::now_secs()) && note.gid != self.patch_day.seen => note.clone(),
+            _ => return,
+        };
+        println!("[LOG] Squad update detected: {}", note.title);
+        self.patch_day.seen = note.gid.clone();
+        if !self.patch_day.auto {
+            return;
+        }
+        self.set_patch_day(true);
+        self.notify(format!("🛠 {} {}", self.tr("patch_detected"), note.title), NotificationAction::None);
+    }
+
+    fn show_patch_banner(&mut self, ui: &mut egui::Ui) {
+        let now = sessions::now_secs();
+        let note = self.patch_note.clone().filter(|note| note.is_fresh(now));
+        let active = self.patch_day.is_active(now);
+        if note.is_none() && !active {
+            return;
+        }
+        let mut toggle = false;
+        egui::Frame::group(ui.style())
+            .fill(egui::Color32::from_rgb(20, 45, 70))
+            .show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    if let Some(note) = &note {
+                        ui.label(format!("🛠 {} {}", self.tr("patch_detected"), note.title));
+                        if !note.url.is_empty() {
+                            ui.hyperlink_to("↗", &note.url);
+                        }
+                    }
+                    if active {
+                        ui.colored_label(egui::Color32::LIGHT_BLUE, self.tr("patch_mode_on"))
+                            .on_hover_text(self.tr("patch_mode_hint"));
+                    }
+                    ui.with_layout(self.direction().trailing(), |ui| {
+                        let label = if active { self.tr("patch_mode_exit") } else { self.tr("patch_mode_enter") };
+                        toggle = ui.small_button(label).clicked();
+                    });
+                });
+            });
+        if toggle {
+            self.set_patch_day(!active);
+        }
+    }
+
+    fn show_patch_day_settings(&mut self, ui: &mut egui::Ui) {
+        let auto_label = self.tr("patch_auto");
+        ui.checkbox(&mut self.patch_day.auto, auto_label);
+        match &self.patch_note {
+            Some(note) => {
+                let date = chrono::DateTime::from_timestamp(note.date as i64, 0)
+                    .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                ui.label(format!("{} {} ({})", self.tr("patch_last"), note.title, date));
+            }
+            None => {
+                ui.label(self.tr("patch_none"));
+            }
+        }
+        let active = self.patch_day_active();
+        let label = if active { self.tr("patch_mode_exit") } else { self.tr("patch_mode_enter") };
+        if ui.button(label).clicked() {
+            self.set_patch_day(!active);
+        }
+        ui.small(self.tr("patch_mode_hint"));
+    }
+
+    // --- СИГНАЛИ СІДУВАННЯ ВІД СПІЛЬНОТ ---
+
+    fn poll_seeding(&mut self, ctx: &egui::Context) {
+        while let Ok(result) = self.seeding_rx.try_recv() {
+            match result {
+                Ok(calls) => {
+                    println!("[LOG] Seeding signals: {} servers", calls.len());
+                    self.seed_calls = calls;
+                    self.alert_seeding();
+                }
+                Err(e) => println!("[ERR] Failed to load seeding signals: {}", e),
+            }
+        }
+
+        let url = self.seeding.url.trim().to_owned();
+        if url.is_empty() {
+            self.seed_calls.clear();
+            return;
+        }
+        if self.seeding_checked.is_some_and(|at| at.elapsed() < seeding::CHECK_INTERVAL) {
+            return;
+        }
+        self.seeding_checked = Some(Instant::now());
+        ctx.request_repaint_after(seeding::CHECK_INTERVAL);
+        let tx = self.seeding_tx.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(seeding::fetch(&url, sessions::now_secs()));
+            ctx.request_repaint();
+        });
+    }
+
+    fn alert_seeding(&mut self) {
+        let fresh: Vec<SeedCall> = self.seed_calls.iter().filter(|call| !self.seed_alerted.contains(&call.key())).cloned().collect();
+        // Пам'ятаємо лише актуальні сигнали, інакше набір ріс би весь час
+        self.seed_alerted = self.seed_calls.iter().map(SeedCall::key).collect();
+        if !self.seeding.alert {
+            return;
+        }
+        for call in fresh {
+            let name = self
+                .latest_servers()
+                .into_iter()
+                .find(|s| s.id == call.server_id)
+                .map_or(call.server_id.clone(), |s| s.name.clone());
+            let community = if call.community.is_empty() { self.tr("seeding_someone") } else { call.community.as_str() };
+            self.notify(
+                format!("🌱 {} {} {}", community, self.tr("seeding_started"), name),
+                NotificationAction::Server(call.server_id.clone()),
+            );
+        }
+    }
+
+    fn seed_call(&self, id: &str) -> Option<&SeedCall> {
+        self.seed_calls.iter().find(|call| call.server_id == id)
+    }
+
+    // "19:00-23:00 (your time, from player history)"
+    fn prime_time_text(&self, prime: PrimeTime) -> String {
+        let (start, end) = prime.local_hours(primetime::local_offset());
+        let source = match prime.source {
+            primetime::Source::History => self.tr("prime_history"),
+            primetime::Source::Region => self.tr("prime_region"),
+        };
+        format!("{:02}:00-{:02}:00 ({})", start, end, source)
+    }
+
+    // "3 days ago (2026-10-13)"
+    fn first_seen_text(&self, seen: u64) -> String {
+        let date = chrono::DateTime::from_timestamp(seen as i64, 0)
+            .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let ago = match firstseen::age_days(seen, sessions::now_secs()) {
+            0 => self.tr("today").to_owned(),
+            days => self.tr("days_ago").replace("{d}", &days.to_string()),
+        };
+        format!("{} ({})", ago, date)
+    }
+
+    fn rank_text(&self, server: &ServerItem) -> Option<String> {
+        let badge = self.view.badges.get(&server.id)?;
+        let template = match &badge.country {
+            Some(_) => self.tr("rank_country"),
+            None => self.tr("rank_overall"),
+        };
+        let text = template.replace("{p}", &badge.top_percent.to_string()).replace("{c}", badge.country.as_deref().unwrap_or_default());
+        Some(format!("🏅 {}", text))
+    }
+
+    fn show_seed_badge(&self, ui: &mut egui::Ui, server: &ServerItem) {
+        if let Some(call) = self.seed_call(&server.id) {
+            let label = ui.colored_label(egui::Color32::LIGHT_GREEN, format!("🌱 {} {}", self.tr("seeding_now"), call.community));
+            if !call.message.is_empty() {
+                label.on_hover_text(call.message.as_str());
+            }
+        }
+    }
+
+    // --- ЧЕРГА НА КІЛЬКА СЕРВЕРІВ ---
+
+    fn receive_queue(&mut self, ctx: &egui::Context, servers: Option<&[ServerItem]>) {
+        self.queue_assistant.pending = false;
+        let free = match servers.and_then(queueing::pick_free) {
+            Some(free) if self.queue_assistant.running => free.clone(),
+            _ => return,
+        };
+        self.queue_assistant.running = false;
+        println!("[LOG] Queue assistant: free slot on {}", free.full_name);
+        self.notify(
+            format!("🎯 {}: {} ({})", self.tr("target_free"), free.name, free.text.players),
+            NotificationAction::Server(free.id.clone()),
+        );
+        if self.queue_assistant.auto_join {
+            self.join(ctx, &free);
+        }
+    }
+
+    fn show_queue_assistant(&mut self, ui: &mut egui::Ui) {
+        if self.queue_assistant.targets.is_empty() {
+            return;
+        }
+        let servers = self.latest_servers();
+        let names: Vec<String> = self
+            .queue_assistant
+            .targets
+            .iter()
+            .map(|id| servers.iter().find(|s| &s.id == id).map_or(id.clone(), |s| s.full_name.clone()))
+            .collect();
+        let title = format!("🎯 {} {}", self.tr("target_any"), self.queue_assistant.targets.len());
+        let auto_join_label = self.tr("target_auto_join");
+        let (start_label, stop_label, clear_label) = (self.tr("target_start"), self.tr("target_stop"), self.tr("target_clear"));
+        let checked_label = self.tr("target_checked");
+        ui.horizontal(|ui| {
+            ui.label(title).on_hover_text(names.join("\n"));
+            let assistant = &mut self.queue_assistant;
+            if assistant.running {
+                if ui.button(format!("⏹ {}", stop_label)).clicked() {
+                    assistant.running = false;
+                }
+                ui.spinner();
+                if let Some(at) = assistant.last_checked() {
+                    ui.small(format!("{} {}s", checked_label, at.elapsed().as_secs()));
+                }
+            } else if ui.button(format!("▶ {}", start_label)).clicked() {
+                assistant.start();
+            }
+            ui.checkbox(&mut assistant.auto_join, auto_join_label);
+            if ui.button(clear_label).clicked() {
+                assistant.targets.clear();
+                assistant.running = false;
+            }
+        });
+    }
+
+    fn new_matches(&self) -> usize {
+        self.saved_searches
+            .iter()
+            .filter_map(|s| self.monitors.get(&s.name))
+            .map(|state| state.new_count)
+            .sum()
+    }
+
+    // Повертає індекс пошуку, який треба завантажити
+    fn show_saved_searches(&mut self, ui: &mut egui::Ui) -> Option<usize> {
+        let mut load = None;
+        let mut remove = None;
+        let new_total = self.new_matches();
+        let mut title = format!("⭐ {} ({})", self.tr("saved_searches"), self.saved_searches.len());
+        if new_total > 0 {
+            title = format!("{} • {} {}", title, new_total, self.tr("monitor_new"));
+        }
+        let monitor_hint = self.tr("monitor_hint");
+        let name_hint = self.tr("search_title");
+        let save_label = format!("💾 {}", self.tr("save_search"));
+        let mut save = false;
+        let trailing = self.direction().trailing();
+        ui.collapsing(title, |ui| {
+            for (index, search) in self.saved_searches.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let new_count = self.monitors.get(&search.name).map_or(0, |state| state.new_count);
+                    if ui.button(&search.name).clicked() {
+                        load = Some(index);
+                    }
+                    if new_count > 0 {
+                        ui.colored_label(egui::Color32::LIGHT_GREEN, format!("+{}", new_count));
+                    }
+                    ui.with_layout(trailing, |ui| {
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(index);
+                        }
+                        ui.checkbox(&mut search.monitor, "👁").on_hover_text(monitor_hint);
+                    });
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.new_search_name).hint_text(name_hint));
+                save = ui.button(save_label.as_str()).clicked();
+            });
+        });
+        if save {
+            self.save_current_search();
+        }
+        if let Some(index) = remove {
+            let search = self.saved_searches.remove(index);
+            self.monitors.remove(&search.name);
+        }
+        load
+    }
+
+    // --- РОЗУМНІ СПИСКИ ---
+
+    // Останні відомі дані: активна вкладка, решта вкладок і закріплені (без повторів)
+    fn latest_servers(&self) -> Vec<&ServerItem> {
+        let mut seen = HashSet::new();
+        std::iter::once(&self.view)
+            .chain(self.tabs.iter().map(|tab| &tab.view))
+            .flat_map(|view| view.servers.iter())
+            .chain(self.pinned.iter())
+            .filter(|server| seen.insert(server.id.as_str()))
+            .collect()
+    }
+
+    fn show_smart_lists(&mut self, ui: &mut egui::Ui, actions: &mut RowActions) {
+        let mut select = None;
+        let mut remove = None;
+        let name_hint = self.tr("smart_name");
+        let expr_hint = self.tr("smart_expr");
+        let add_label = format!("➕ {}", self.tr("smart_add"));
+        let new_week_label = format!("➕ 🆕 {}", self.tr("smart_new_week"));
+        let evenings_label = format!("➕ 🌙 {}", self.tr("smart_evenings"));
+
+        ui.heading(format!("🧠 {}", self.tr("smart_lists")));
+        let servers = self.latest_servers();
+        for (index, list) in self.smart_lists.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let selected = self.active_smart_list == Some(index);
+                match list.matching(&servers) {
+                    Ok(matches) => {
+                        let label = format!("{} ({})", list.name, matches.len());
+                        if ui.selectable_label(selected, label).on_hover_text(list.expr.as_str()).clicked() {
+                            select = Some(index);
+                        }
+                    }
+                    Err(error) => {
+                        ui.colored_label(egui::Color32::RED, format!("⚠ {}", list.name)).on_hover_text(error);
+                    }
+                }
+                ui.with_layout(self.direction().trailing(), |ui| {
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_smart_name).hint_text(name_hint).desired_width(120.0));
+            ui.add(egui::TextEdit::singleline(&mut self.new_smart_expr).hint_text(expr_hint));
+        });
+        ui.small(expr::SYNTAX_HINT);
+        let parsed = expr::Expr::parse(&self.new_smart_expr);
+        if let Err(error) = &parsed {
+            ui.colored_label(egui::Color32::RED, error.as_str());
+        }
+        let can_add = parsed.is_ok() && !self.new_smart_name.trim().is_empty() && !self.new_smart_expr.trim().is_empty();
+        let add = ui.add_enabled(can_add, egui::Button::new(add_label.as_str())).clicked();
+        let has_new_week = self.smart_lists.iter().any(|list| list.expr.trim() == expr::NEW_SERVERS);
+        let add_new_week = !has_new_week && ui.button(new_week_label).clicked();
+        let has_evenings = self.smart_lists.iter().any(|list| list.expr.trim() == expr::MY_EVENINGS);
+        let add_evenings = !has_evenings && ui.button(evenings_label).clicked();
+        ui.separator();
+
+        if let Some(list) = self.active_smart_list.and_then(|index| self.smart_lists.get(index)) {
+            let servers = self.latest_servers();
+            let matches = list.matching(&servers).unwrap_or_default();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                if matches.is_empty() {
+                    ui.label(self.tr("smart_empty"));
+                }
+                for server in matches {
+                    self.show_server_row(ui, server, None, actions);
+                }
+            });
+        }
+
+        if add {
+            println!("[LOG] Smart list added: {}", self.new_smart_name.trim());
+            self.smart_lists.push(SmartList {
+                name: std::mem::take(&mut self.new_smart_name).trim().to_owned(),
+                expr: std::mem::take(&mut self.new_smart_expr).trim().to_owned(),
+            });
+        }
+        if add_new_week {
+            self.smart_lists.push(SmartList { name: self.tr("smart_new_week").to_owned(), expr: expr::NEW_SERVERS.to_owned() });
+        }
+        if add_evenings {
+            self.smart_lists.push(SmartList { name: self.tr("smart_evenings").to_owned(), expr: expr::MY_EVENINGS.to_owned() });
+        }
+        if let Some(index) = select {
+            self.active_smart_list = Some(index);
+        }
+        if let Some(index) = remove {
+            self.smart_lists.remove(index);
+            self.active_smart_list = match self.active_smart_list {
+                Some(active) if active == index => None,
+                Some(active) if active > index => Some(active - 1),
+                other => other,
+            };
+        }
+    }
+
+    // --- НАВІГАЦІЯ ---
+
+    fn show_navigation(&mut self, ctx: &egui::Context) {
+        let mut section = self.section;
+        let mut smart_list = None;
+        let mut panel = self.direction().side_panel("navigation").width_range(layouts::SIDEBAR_RANGE).default_width(self.sidebar_width);
+        if self.resize_sidebar {
+            panel = panel.exact_width(self.sidebar_width);
+            self.resize_sidebar = false;
+        }
+        let response = panel.show(ctx, |ui| {
+            ui.add_space(6.0);
+            let favorites = format!("📌 {} ({})", self.tr("nav_favorites"), self.pinned.len());
+            let items = [
+                (Section::AllServers, format!("🌐 {}", self.tr("nav_all"))),
+                (Section::Favorites, favorites),
+                (Section::History, format!("🕘 {}", self.tr("nav_history"))),
+                (Section::Events, format!("📅 {}", self.tr("nav_events"))),
+                (Section::SmartLists, format!("🧠 {}", self.tr("smart_lists"))),
+            ];
+            for (value, label) in items {
+                ui.selectable_value(&mut section, value, label);
+            }
+            // Розумні списки - одразу в навігації, з актуальною кількістю
+            let servers = self.latest_servers();
+            ui.indent("smart_list_nav", |ui| {
+                for (index, list) in self.smart_lists.iter().enumerate() {
+                    let count = list.matching(&servers).map_or("⚠".to_owned(), |matches| matches.len().to_string());
+                    let selected = self.section == Section::SmartLists && self.active_smart_list == Some(index);
+                    if ui.selectable_label(selected, format!("{} ({})", list.name, count)).clicked() {
+                        smart_list = Some(index);
+                    }
+                }
+            });
+            ui.selectable_value(&mut section, Section::Stats, format!("📊 {}", self.tr("nav_stats")));
+            ui.selectable_value(&mut section, Section::Settings, format!("⚙ {}", self.tr("settings")));
+        });
+        self.sidebar_width = response.response.rect.width();
+        if let Some(index) = smart_list {
+            section = Section::SmartLists;
+            self.active_smart_list = Some(index);
+        }
+        self.section = section;
+    }
+
+    // --- ПАЛІТРА КОМАНД ---
+
+    fn palette_commands(&self) -> Vec<(String, Command)> {
+        let mut commands = vec![
+            (format!("🔄 {}", self.tr("cmd_refresh")), Command::Refresh),
+            (format!("🔄 {}", self.tr("cmd_refresh_all")), Command::RefreshAll),
+            (format!("➕ {}", self.tr("new_tab")), Command::NewTab),
+        ];
+        let go_to = self.tr("cmd_go_to");
+        let sections = [
+            (Section::AllServers, self.tr("nav_all")),
+            (Section::Favorites, self.tr("nav_favorites")),
+            (Section::History, self.tr("nav_history")),
+            (Section::Events, self.tr("nav_events")),
+            (Section::SmartLists, self.tr("smart_lists")),
+            (Section::Stats, self.tr("nav_stats")),
+            (Section::Settings, self.tr("settings")),
+        ];
+        for (section, label) in sections {
+            commands.push((format!("➡ {}: {}", go_to, label), Command::ShowSection(section)));
+        }
+        for index in 0..self.tabs.len() {
+            commands.push((format!("🗂 {}: {}", self.tr("cmd_switch_tab"), self.tab_title(index)), Command::SwitchTab(index)));
+        }
+        for (index, search) in self.saved_searches.iter().enumerate() {
+            commands.push((format!("🔍 {}: {}", self.tr("cmd_load_search"), search.name), Command::LoadSearch(index)));
+        }
+        for (index, list) in self.smart_lists.iter().enumerate() {
+            commands.push((format!("🧠 {}: {}", self.tr("smart_lists"), list.name), Command::ShowSmartList(index)));
+        }
+        for (index, server) in self.pinned.iter().enumerate() {
+            let name = &server.full_name;
+            commands.push((format!("📌 {} #{}: {}", self.tr("cmd_open_favorite"), index + 1, name), Command::OpenFavorite(index)));
+            commands.push((format!("▶ {} #{}: {}", self.tr("cmd_join_favorite"), index + 1, name), Command::JoinFavorite(index)));
+        }
+        for (index, layout) in self.layouts.iter().enumerate() {
+            commands.push((format!("🗔 {}: {}", self.tr("cmd_apply_layout"), layout.name), Command::ApplyLayout(index)));
+        }
+        for filter in self.filters.iter() {
+            commands.push((format!("✕ {}: {}", self.tr("cmd_remove_filter"), filter.label()), Command::RemoveFilter(filter.clone())));
+        }
+        commands.extend([
+            (format!("🛡 {}", self.tr("cmd_toggle_anti_cheat")), Command::ToggleAntiCheat),
+            (format!("⇅ {}", self.tr("cmd_toggle_freeze")), Command::ToggleFreeze),
+            (format!("🗔 {}", self.tr("cmd_toggle_compact")), Command::ToggleCompact),
+            (format!("🌐 {}", self.tr("cmd_toggle_language")), Command::ToggleLanguage),
+            (format!("🔔 {}", self.tr("notifications")), Command::ToggleNotifications),
+            (format!("💾 {}", self.tr("cmd_export_rules")), Command::ExportRules),
+            (format!("💾 {}", self.tr("cmd_export_calendar")), Command::ExportCalendar),
+            (format!("💾 {} (Markdown)", self.tr("cmd_export_weekly")), Command::ExportWeekly(false)),
+            (format!("💾 {} (HTML)", self.tr("cmd_export_weekly")), Command::ExportWeekly(true)),
+            (format!("💾 {}", self.tr("export_servers")), Command::ExportServers),
+            (format!("⚔ {}", self.tr("veto_title")), Command::MapVeto),
+            (format!("🛠 {}", self.tr("cmd_toggle_patch_day")), Command::TogglePatchDay),
+            (format!("💾 {}", self.tr("backup_create")), Command::BackupAll),
+        ]);
+        if self.sync.enabled {
+            commands.push((format!("☁ {}", self.tr("sync_now")), Command::SyncNow));
+        }
+        commands
+    }
+
+    fn run_command(&mut self, ctx: &egui::Context, command: Command) {
+        match command {
+            Command::Refresh => {
+                self.section = Section::AllServers;
+                self.run_scan(None);
+            }
+            Command::RefreshAll => self.refresh_all(),
+            Command::NewTab => {
+                self.section = Section::AllServers;
+                self.new_tab();
+            }
+            Command::ShowSection(section) => self.section = section,
+            Command::SwitchTab(index) => {
+                self.section = Section::AllServers;
+                self.switch_tab(index);
+            }
+            Command::LoadSearch(index) => {
+                self.section = Section::AllServers;
+                self.load_search(index);
+            }
+            Command::ShowSmartList(index) => {
+                self.section = Section::SmartLists;
+                self.active_smart_list = Some(index);
+            }
+            Command::OpenFavorite(index) => {
+                if let Some(id) = self.pinned.get(index).map(|s| s.id.clone()) {
+                    self.section = Section::Favorites;
+                    if self.single_expand {
+                        self.expanded_ids.clear();
+                    }
+                    self.expanded_ids.insert(id.clone());
+                    self.selected_id = Some(id);
+                    self.update_overlay();
+                }
+            }
+            Command::JoinFavorite(index) => {
+                if let Some(server) = self.pinned.get(index).cloned() {
+                    self.join(ctx, &server);
+                }
+            }
+            Command::RemoveFilter(filter) => {
+                println!("[LOG] Filter removed: {}", filter.label());
+                self.filters.remove(&filter);
+                if self.view.first_load_done {
+                    self.run_scan(None);
+                }
+            }
+            Command::ToggleAntiCheat => {
+                if self.filters.anticheat_only() {
+                    self.filters.remove(&Filter::AntiCheatOnly);
+                } else {
+                    self.filters.set(Filter::AntiCheatOnly);
+                }
+                if self.view.first_load_done {
+                    self.run_scan(None);
+                }
+            }
+            Command::ToggleFreeze => self.freeze_order = !self.freeze_order,
+            Command::ToggleCompact => self.skin.enabled = !self.skin.enabled,
+            Command::ToggleLanguage => {
+                self.language = if self.language == Language::En { Language::Ua } else { Language::En };
+            }
+            Command::ToggleNotifications => self.show_notifications = !self.show_notifications,
+            Command::ExportRules => {
+                self.export_rules();
+                self.show_toast(self.rules_status.clone());
+            }
+            Command::ExportCalendar => {
+                self.write_ics();
+                self.show_toast(self.events_status.clone());
+            }
+            Command::ExportWeekly(html) => self.export_weekly(html),
+            Command::ExportServers => self.show_export = true,
+            Command::MapVeto => self.show_veto = true,
+            Command::TogglePatchDay => self.set_patch_day(!self.patch_day_active()),
+            Command::ApplyLayout(index) => self.apply_layout(index),
+            Command::BackupAll => {
+                self.backup_all();
+                self.show_toast(self.backup_status.clone());
+            }
+            Command::SyncNow => self.sync_checked = None,
+        }
+    }
+
+    fn show_palette(&mut self, ctx: &egui::Context) {
+        if !self.palette.open {
+            return;
+        }
+        let commands = palette::filter(&self.palette.query, self.palette_commands());
+        self.palette.selected = self.palette.selected.min(commands.len().saturating_sub(1));
+        let (down, up, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if down && self.palette.selected + 1 < commands.len() {
+            self.palette.selected += 1;
+        }
+        if up {
+            self.palette.selected = self.palette.selected.saturating_sub(1);
+        }
+
+        let mut chosen = enter.then_some(self.palette.selected);
+        let hint = self.tr("cmd_hint");
+        egui::Window::new("palette")
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size([420.0, 0.0])
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .show(ctx, |ui| {
+                let edit = ui.add(egui::TextEdit::singleline(&mut self.palette.query).hint_text(hint).desired_width(f32::INFINITY));
+                edit.request_focus();
+                if edit.changed() {
+                    self.palette.selected = 0;
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for (index, (label, _)) in commands.iter().enumerate() {
+                        let selected = index == self.palette.selected;
+                        let response = ui.selectable_label(selected, label);
+                        if selected && (up || down) {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            chosen = Some(index);
+                        }
+                    }
+                });
+            });
+
+        if escape {
+            self.palette.close();
+        }
+        if let Some((label, command)) = chosen.and_then(|index| commands.into_iter().nth(index)) {
+            println!("[LOG] Command: {}", label);
+            self.palette.close();
+            self.run_command(ctx, command);
+        }
+    }
+
+    // --- КОМАНДИ З squad-browser-ctl ---
+
+    fn poll_control(&mut self, ctx: &egui::Context) {
+        while let Some(request) = self.control.as_ref().and_then(ControlServer::try_recv) {
+            println!("[LOG] Control command: {} {}", request.command, request.args.join(" "));
+            let answer = match self.control_command(ctx, &request.command, &request.args) {
+                Ok(text) if text.is_empty() => "OK".to_owned(),
+                Ok(text) => format!("OK {}", text),
+                Err(e) => format!("ERR {}", e),
+            };
+            request.reply(answer);
+        }
+    }
+
+    fn control_command(&mut self, ctx: &egui::Context, command: &str, args: &[String]) -> Result<String, String> {
+        let arg = args.join(" ");
+        match command {
+            "refresh" => self.run_scan(None),
+            "refresh-all" => self.refresh_all(),
+            "join" => {
+                let server = self.latest_servers().into_iter().find(|s| s.id == arg).cloned();
+                let server = server.ok_or_else(|| format!("server {} is not in the loaded lists", arg))?;
+                self.join(ctx, &server);
+                return Ok(server.full_name);
+            }
+            "set-filter" | "clear-filter" => {
+                let pairs = ipc::filter_pairs(args);
+                if pairs.is_empty() {
+                    return Err(ipc::HELP.to_owned());
+                }
+                for pair in pairs {
+                    if command == "clear-filter" && pair == "all" {
+                        // Як "Скинути фільтри": чорний список країн лишається
+                        self.reset_settings(ResetScope::Filters);
+                        continue;
+                    }
+                    let pair = if command == "clear-filter" { format!("{}=0", pair) } else { pair };
+                    match ipc::parse_filter(&pair)? {
+                        (filter, true) if command == "set-filter" => self.filters.set(filter),
+                        (filter, _) => self.filters.clear_like(&filter),
+                    }
+                }
+                self.section = Section::AllServers;
+                self.run_scan(None);
+            }
+            "tab" => {
+                let index = arg.parse::<usize>().ok().filter(|n| (1..=self.tabs.len()).contains(n));
+                let index = index.ok_or_else(|| format!("expected a tab number from 1 to {}", self.tabs.len()))?;
+                self.section = Section::AllServers;
+                self.switch_tab(index - 1);
+            }
+            "search" => {
+                let index = self.saved_searches.iter().position(|s| s.name.eq_ignore_ascii_case(&arg));
+                let index = index.ok_or_else(|| format!("no saved search \"{}\"", arg))?;
+                self.section = Section::AllServers;
+                self.load_search(index);
+            }
+            "status" => {
+                let filters: Vec<String> = self.filters.iter().map(Filter::label).collect();
+                return Ok(format!(
+                    "tab={} servers={} loading={} filters=[{}]",
+                    self.tab_title(self.active_tab),
+                    self.view.servers.len(),
+                    self.view.is_loading,
+                    filters.join(", ")
+                ));
+            }
+            "open" => {
+                self.open_link(&arg)?;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            }
+            "help" => return Ok(ipc::HELP.to_owned()),
+            other => return Err(format!("unknown command \"{}\"; {}", other, ipc::HELP)),
+        }
+        Ok(String::new())
+    }
+
+    fn show_autostart(&mut self, ui: &mut egui::Ui) {
+        let mut enabled = self.autostart;
+        let label = self.tr("autostart");
+        if ui.checkbox(&mut enabled, label).on_hover_text(self.tr("autostart_hint")).changed() {
+            let result = if enabled {
+                std::env::current_exe().map_err(|e| e.to_string()).and_then(|exe| autostart::enable(&exe))
+            } else {
+                autostart::disable()
+            };
+            match result {
+                Ok(()) => {
+                    println!("[LOG] Start with the system: {}", enabled);
+                    self.autostart = enabled;
+                    self.autostart_status.clear();
+                }
+                Err(e) => {
+                    println!("[ERR] Failed to change autostart: {}", e);
+                    self.autostart_status = e;
+                }
+            }
+        }
+        if !self.autostart_status.is_empty() {
+            ui.colored_label(egui::Color32::RED, self.autostart_status.as_str());
+        }
+    }
+
+    // --- ПОСИЛАННЯ squadbrowser:// ---
+
+    fn open_link(&mut self, url: &str) -> Result<(), String> {
+        println!("[LOG] Opening link {}", url);
+        match deeplink::parse(url)? {
+            DeepLink::Server(id) => {
+                // Сервера ще немає в списках - окрема вкладка лише з ним
+                if !self.latest_servers().iter().any(|s| s.id == id) {
+                    self.section = Section::AllServers;
+                    self.new_tab();
+                    self.filters = FilterSet::default();
+                    self.filters.set(Filter::Param("filter[ids][whitelist]".to_owned(), id.clone()));
+                    self.run_scan(None);
+                }
+                self.open_notification(NotificationAction::Server(id));
+            }
+            DeepLink::Filters(pairs) => {
+                // Фільтри з посилання замінюють поточні, але свій чорний список країн лишається
+                let mut filters = FilterSet::default();
+                for filter in self.filters.iter().filter(|f| matches!(f, Filter::NoCountry(_))) {
+                    filters.set(filter.clone());
+                }
+                for pair in pairs {
+                    if let (filter, true) = ipc::parse_filter(&pair)? {
+                        filters.set(filter);
+                    }
+                }
+                self.filters = filters;
+                self.section = Section::AllServers;
+                self.run_scan(None);
+            }
+        }
+        Ok(())
+    }
+
+    fn show_link_settings(&mut self, ui: &mut egui::Ui) {
+        ui.small(self.tr("links_hint"));
+        ui.horizontal(|ui| {
+            if ui.button(self.tr("links_register")).clicked() {
+                let result = std::env::current_exe().map_err(|e| e.to_string()).and_then(|exe| deeplink::register(&exe));
+                self.links_status = match result {
+                    Ok(place) => {
+                        println!("[LOG] Registered {}:// links: {}", deeplink::SCHEME, place);
+                        format!("{} {}", self.tr("links_registered"), place)
+                    }
+                    Err(e) => {
+                        println!("[ERR] Failed to register links: {}", e);
+                        e
+                    }
+                };
+            }
+            let can_share = self.filters.iter().any(|f| !matches!(f, Filter::Param(..)));
+            if ui.add_enabled(can_share, egui::Button::new(format!("🔗 {}", self.tr("links_copy_filters")))).clicked() {
+                ui.output_mut(|o| o.copied_text = deeplink::filter_link(&self.filters));
+            }
+        });
+        if !self.links_status.is_empty() {
+            ui.small(self.links_status.as_str());
+        }
+    }
+
+    // --- STREAM DECK ---
+
+    fn favorite_json(&self, slot: usize, server: &ServerItem) -> serde_json::Value {
+        serde_json::json!({
+            "slot": slot + 1,
+            "id": server.id,
+            "name": server.full_name,
+            "map": &*server.map,
+            "players": server.players,
+            "max_players": server.max_players,
+            "queue": server.public_queue,
+            "free": queueing::pick_free(std::slice::from_ref(server)).is_some(),
+        })
+    }
+
+    fn poll_deck(&mut self, ctx: &egui::Context) {
+        while let Some(request) = self.deck.as_ref().and_then(DeckServer::try_recv) {
+            let command = request.message.get("command").and_then(|c| c.as_str()).unwrap_or_default().to_owned();
+            println!("[LOG] Stream Deck command: {}", command);
+            let reply = match command.as_str() {
+                "join_favorite" => {
+                    let slot = request.message.get("slot").and_then(|s| s.as_u64()).unwrap_or(0) as usize;
+                    match slot.checked_sub(1).and_then(|index| self.pinned.get(index)).cloned() {
+                        Some(server) => {
+                            self.join(ctx, &server);
+                            serde_json::json!({ "reply": "ok", "command": command })
+                        }
+                        None => serde_json::json!({ "reply": "error", "message": format!("no favorite in slot {}", slot) }),
+                    }
+                }
+                "refresh" => {
+                    self.run_scan(None);
+                    serde_json::json!({ "reply": "ok", "command": command })
+                }
+                "state" => {
+                    let favorites: Vec<serde_json::Value> =
+                        self.pinned.iter().enumerate().map(|(slot, server)| self.favorite_json(slot, server)).collect();
+                    serde_json::json!({ "reply": "state", "favorites": favorites })
+                }
+                "schema" => streamdeck::schema(),
+                other => serde_json::json!({ "reply": "error", "message": format!("unknown command \"{}\"", other) }),
+            };
+            request.reply(reply);
+        }
+    }
+
+    fn deck_scan_done(&self, index: usize, view: &ScanView) {
+        if let Some(deck) = &self.deck {
+            deck.broadcast(serde_json::json!({
+                "event": "scan_done",
+                "tab": self.tab_title(index),
+                "servers": view.servers.len(),
+                "error": view.scan_error,
+            }));
+        }
+    }
+
+    // Подія лише при переході "повний -> є місце", а не на кожному оновленні
+    fn deck_check_favorites(&mut self) {
+        let free: HashSet<String> =
+            self.pinned.iter().filter(|s| queueing::pick_free(std::slice::from_ref(*s)).is_some()).map(|s| s.id.clone()).collect();
+        if let Some(deck) = &self.deck {
+            for (slot, server) in self.pinned.iter().enumerate() {
+                if free.contains(&server.id) && !self.deck_free.contains(&server.id) {
+                    deck.broadcast(serde_json::json!({ "event": "favorite_slot_free", "favorite": self.favorite_json(slot, server) }));
+                }
+            }
+        }
+        self.deck_free = free;
+    }
+
+    fn pin_badge(&self, id: &str) -> Option<&'static str> {
+        if self.presence.is_offline(id) {
+            Some(self.tr("pin_offline"))
+        } else if self.pinned_unmatched.contains(id) {
+            Some(self.tr("pin_unmatched"))
+        } else if self.view.first_load_done && !self.view.is_loading && !self.pinned_matching.contains(id) {
+            Some(self.tr("pin_missing"))
+        } else {
+            None
+        }
+    }
+
+    fn show_favorites(&self, ui: &mut egui::Ui, actions: &mut RowActions) {
+        ui.heading(format!("📌 {}", self.tr("nav_favorites")));
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if self.pinned.is_empty() {
+                ui.label(self.tr("no_favorites"));
+            }
+            for server in &self.pinned {
+                self.show_server_row(ui, server, self.pin_badge(&server.id), actions);
+            }
+        });
+    }
+
+    // Вміст центральної панелі для всіх розділів, крім списку серверів
+    fn show_section(&mut self, ui: &mut egui::Ui, actions: &mut RowActions) {
+        match self.section {
+            Section::AllServers => {}
+            Section::Favorites => self.show_favorites(ui, actions),
+            Section::History => self.show_history(ui),
+            Section::Events => self.show_events(ui),
+            Section::SmartLists => self.show_smart_lists(ui, actions),
+            Section::Stats => self.show_stats(ui),
+            Section::Settings => self.show_settings_page(ui),
+        }
+    }
+
+    // --- ВКЛАДКИ ---
+
+    // Забираємо готові результати з усіх вкладок, не лише з активної
+    fn receive_results(&mut self) {
+        let now = sessions::now_secs();
+        let mut view = std::mem::take(&mut self.view);
+        let active_updated = self.receive_into(&mut view);
+        self.view = view;
+        if active_updated {
+            let players = self.view.servers.iter().map(|s| s.players).sum();
+            self.population.record(&self.tab_title(self.active_tab), now, players);
+            self.deck_scan_done(self.active_tab, &self.view);
+        }
+        let mut any_updated = active_updated;
+        for index in 0..self.tabs.len() {
+            let mut view = std::mem::take(&mut self.tabs[index].view);
+            if self.receive_into(&mut view) {
+                let players = view.servers.iter().map(|s| s.players).sum();
+                self.population.record(&self.tab_title(index), now, players);
+                self.deck_scan_done(index, &view);
+                any_updated = true;
+            }
+            self.tabs[index].view = view;
+        }
+        if active_updated {
+            self.update_overlay();
+        }
+        if any_updated {
+            self.deck_check_favorites();
+        }
+    }
+
+    fn receive_into(&mut self, view: &mut ScanView) -> bool {
+        let mut response = match view.rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            Some(response) => response,
+            None => return false,
+        };
+        let now = sessions::now_secs();
+        for server in response.servers.iter_mut().chain(response.unmatched_pinned.iter_mut()) {
+            self.first_seen.record(&server.id, now);
+            server.first_seen = self.first_seen.first_seen(&server.id);
+            self.prime_times.record(&server.id, now, server.players);
+            server.prime_time = self.prime_times.prime_time(&server.id, &server.country);
+        }
+        self.prime_times.prune(now);
+        for server in response.servers.iter().chain(response.unmatched_pinned.iter()) {
+            self.history.record(&server.id, server.players);
+            self.history.record_map(&server.id, &server.map);
+            self.versions.record(&server.id, &server.version);
+            if self.is_pinned(&server.id) && self.rotations.record(&server.id, &server.map, now) {
+                self.notify(
+                    format!("📌 {} {} {}", server.name, self.tr("map_changed"), server.map),
+                    NotificationAction::Server(server.id.clone()),
+                );
+            }
+        }
+        self.versions.update();
+        self.refresh_pins(&response.servers, &response.unmatched_pinned);
+        self.summaries.get_mut().clear();
+        if self.freeze_order {
+            view.merge_servers(response.servers);
+            if response.error.is_none() && response.next_url.is_empty() {
+                view.drop_stale();
+            }
+        } else {
+            view.servers.extend(response.servers);
+        }
+        view.update_badges();
+        view.next_url = response.next_url;
+        if let Some(error) = &response.error {
+            self.notify(format!("⚠ {}: {}", self.tr("scan_failed"), error), NotificationAction::None);
+        }
+        view.scan_error = response.error;
+        view.resume_cursor = response.failed_cursor.clone();
+        view.failed_cursor = response.failed_cursor;
+        view.is_loading = false;
+        view.first_load_done = true;
+        view.rx = None;
+        true
+    }
+
+    fn tab_title(&self, index: usize) -> String {
+        match self.tabs.get(index) {
+            Some(tab) if !tab.title.is_empty() => tab.title.clone(),
+            _ => format!("{} {}", self.tr("tab"), index + 1),
+        }
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        let filters = self.filters.clone();
+        let current = &mut self.tabs[self.active_tab];
+        current.filters = filters;
+        current.view = std::mem::take(&mut self.view);
+
+        self.view = std::mem::take(&mut self.tabs[index].view);
+        self.filters = self.tabs[index].filters.clone();
+        self.active_tab = index;
+        self.update_overlay();
+    }
+
+    // Нова вкладка стартує з копією поточних фільтрів
+    fn new_tab(&mut self) {
+        self.tabs.push(WorkspaceTab { filters: self.filters.clone(), ..Default::default() });
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() <= 1 || index >= self.tabs.len() {
+            return;
+        }
+        if index == self.active_tab {
+            self.switch_tab(if index == 0 { 1 } else { index - 1 });
+        }
+        self.tabs.remove(index);
+        self.scheduler.remove_tab(index);
+        if index < self.active_tab {
+            self.active_tab -= 1;
+        }
+    }
+
+    fn show_tab_bar(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        let mut close = None;
+        let mut add = false;
+        ui.horizontal_wrapped(|ui| {
+            for index in 0..self.tabs.len() {
+                let loading = if index == self.active_tab {
+                    self.view.is_loading
+                } else {
+                    self.tabs[index].view.is_loading
+                };
+                let title = if loading {
+                    format!("⟳ {}", self.tab_title(index))
+                } else if self.scheduler.is_queued(index) {
+                    format!("⏳ {}", self.tab_title(index))
+                } else {
+                    self.tab_title(index)
                 };
-                ui.label(status_msg);
+                if ui.selectable_label(index == self.active_tab, title).clicked() {
+                    switch_to = Some(index);
+                }
+                if self.tabs.len() > 1 && ui.small_button("✖").clicked() {
+                    close = Some(index);
+                }
+                ui.separator();
+            }
+            add = ui.small_button("➕").on_hover_text(self.tr("new_tab")).clicked();
+        });
+        if let Some(index) = switch_to {
+            self.switch_tab(index);
+        }
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+        if add {
+            self.new_tab();
+        }
+    }
+
+    fn run_scan(&mut self, next_page_url: Option<String>) {
+        self.run_scan_tab(self.active_tab, next_page_url);
+    }
+
+    fn run_scan_tab(&mut self, index: usize, next_page_url: Option<String>) {
+        let (mut view, filters) = if index == self.active_tab {
+            (std::mem::take(&mut self.view), self.filters.clone())
+        } else {
+            match self.tabs.get_mut(index) {
+                Some(tab) => (std::mem::take(&mut tab.view), tab.filters.clone()),
+                None => return,
+            }
+        };
+        self.start_scan(&mut view, &filters, next_page_url);
+        if index == self.active_tab {
+            self.view = view;
+        } else {
+            self.tabs[index].view = view;
+        }
+    }
+
+    fn refresh_all(&mut self) {
+        for index in 0..self.tabs.len() {
+            self.scheduler.enqueue(index);
+        }
+    }
+
+    // Черга "оновити все": не частіше одного запиту за scheduler::STAGGER
+    fn process_refresh_queue(&mut self, ctx: &egui::Context) {
+        if let Some(index) = self.scheduler.next_due() {
+            self.run_scan_tab(index, None);
+        }
+        if self.scheduler.pending() > 0 {
+            ctx.request_repaint_after(self.scheduler.wait_time());
+        }
+    }
+
+    fn start_scan(&mut self, view: &mut ScanView, filters: &FilterSet, next_page_url: Option<String>) {
+        if view.is_loading { return; }
+        let provider = match &self.provider {
+            Some(provider) => provider.clone(),
+            None => return,
+        };
+
+        view.is_loading = true;
+        view.scan_error = None;
+        view.resume_cursor = next_page_url.clone().unwrap_or_default();
+        self.scheduler.started();
+
+        if next_page_url.is_none() {
+            // Знімок для колонки "+/- з минулого оновлення"
+            if !view.servers.is_empty() || !self.pinned.is_empty() {
+                self.previous_players = view
+                    .servers
+                    .iter()
+                    .chain(self.pinned.iter())
+                    .map(|s| (s.id.clone(), s.players))
+                    .collect();
+            }
+            view.refreshed_ids.clear();
+            self.pinned_matching.clear();
+            self.pinned_unmatched.clear();
+            if !self.freeze_order {
+                view.servers.clear();
+            }
+        }
+        
+        let (tx, rx): (Sender<ScanResult>, Receiver<ScanResult>) = channel();
+        view.rx = Some(rx);
+
+        let filters = self.scan_filters(filters);
+        let url_arg = next_page_url.unwrap_or_default();
+        let ctx = self.egui_ctx.clone();
+
+        thread::spawn(move || {
+            let result = fetch_servers(provider.as_ref(), filters, url_arg);
+            let _ = tx.send(result);
+            // Без цього результат фонової вкладки чекав би на рух миші
+            ctx.request_repaint();
+        });
+    }
+}
+
+impl eframe::App for SquadApp {
+    // Вікно створюється прозорим; непрозорість дає звичайна заливка панелей
+    fn clear_color(&self, visuals: &egui::Visuals) -> [f32; 4] {
+        if self.skin.enabled {
+            [0.0; 4]
+        } else {
+            visuals.panel_fill.to_normalized_gamma_f32()
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Кіоск працює з незмінним профілем
+        if self.launch.kiosk {
+            return;
+        }
+        self.tabs[self.active_tab].filters = self.filters.clone();
+        self.note_local_changes();
+        if self.sync_secret_dirty {
+            self.store_sync_secret();
+        }
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.receive_results();
+        if std::mem::take(&mut self.launch.minimized) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        }
+        if let Some(url) = self.launch.open.take() {
+            if let Err(e) = self.open_link(&url) {
+                println!("[ERR] Cannot open link {}: {}", url, e);
+                self.show_toast(e);
+            }
+        }
+
+        self.poll_file_changes();
+        self.poll_monitors(ctx);
+        self.poll_batch(ctx);
+        self.poll_ping();
+        self.poll_ping_history(ctx);
+        if let Some(trace) = &mut self.trace {
+            trace.poll();
+        }
+        self.poll_rules();
+        self.poll_announcements(ctx);
+        self.poll_seeding(ctx);
+        self.poll_patch_day(ctx);
+        self.poll_layer_db();
+        self.poll_sync(ctx);
+        self.poll_control(ctx);
+        self.poll_deck(ctx);
+        self.process_refresh_queue(ctx);
+
+        if self.launch.kiosk {
+            self.show_kiosk(ctx);
+            self.draw_toast(ctx);
+            return;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::F9)) {
+            self.skin.enabled = !self.skin.enabled;
+        }
+        self.apply_skin(ctx);
+        if self.skin.enabled {
+            self.show_compact(ctx);
+            self.poll_session(ctx);
+            self.draw_toast(ctx);
+            return;
+        }
+
+        if self.applied_density != Some(self.density) {
+            let density = self.density;
+            ctx.style_mut(|style| density.apply(style));
+            self.applied_density = Some(density);
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+            self.palette.toggle();
+        }
+
+        let mut trigger_load_more_url: Option<String> = None;
+        let mut trigger_new_scan = false;
+        let mut trigger_retry = false;
+        let mut trigger_resume = false;
+        let mut trigger_resort = false;
+        let mut trigger_load_search = None;
+        let mut trigger_refresh_all = false;
+        let mut row_actions = RowActions::default();
+        let previous_section = self.section;
+
+        self.show_navigation(ctx);
+
+        let column = self.direction().column();
+        egui::CentralPanel::default().show(ctx, |ui| ui.with_layout(column, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(self.tr("app_title"));
+                if self.launch.replay.is_some() {
+                    ui.colored_label(egui::Color32::YELLOW, "REPLAY");
+                } else if self.launch.demo {
+                    ui.colored_label(egui::Color32::YELLOW, "DEMO");
+                }
+                if self.launch.record.is_some() {
+                    ui.colored_label(egui::Color32::RED, "● REC");
+                }
+                ui.with_layout(self.direction().trailing(), |ui| {
+                    let unread = self.notifications.iter().filter(|n| !n.read).count();
+                    let bell = if unread > 0 { format!("🔔 {}", unread) } else { "🔔".to_owned() };
+                    if ui.button(bell).on_hover_text(self.tr("notifications")).clicked() {
+                        self.show_notifications = !self.show_notifications;
+                    }
+                });
+            });
+
+            self.show_announcement(ui);
+            self.show_patch_banner(ui);
+
+            if self.section != Section::AllServers {
+                ui.separator();
+                self.show_section(ui, &mut row_actions);
+                return;
+            }
+
+            self.show_tab_bar(ui);
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                let btn_size = [140.0, 40.0];
+                if !self.view.first_load_done {
+                     if ui.add_sized(btn_size, egui::Button::new(self.tr("start"))).clicked() {
+                        trigger_new_scan = true;
+                    }
+                } else {
+                    if ui.add_sized(btn_size, egui::Button::new(self.tr("refresh"))).on_hover_text(self.tr("refresh_tab")).clicked() {
+                        trigger_new_scan = true;
+                    }
+                }
+                if self.tabs.len() > 1 && ui.add_sized([100.0, 40.0], egui::Button::new(self.tr("refresh_all"))).clicked() {
+                    trigger_refresh_all = true;
+                }
+                
+                if self.view.is_loading {
+                    ui.spinner();
+                }
+
+                // Глибоке сканування перервалось у минулій сесії - можна дочитати
+                if !self.view.is_loading && self.view.scan_error.is_none() && !self.view.resume_cursor.is_empty()
+                    && ui.button(format!("▶ {}", self.tr("resume_scan"))).clicked()
+                {
+                    trigger_resume = true;
+                }
+
+                if self.view.is_loading {
+                    ui.label(if self.view.servers.is_empty() { self.tr("scanning") } else { self.tr("loading_more") });
+                } else if self.view.first_load_done {
+                    ui.label(format!("{} {}", self.tr("found"), self.view.servers.len()));
+                }
+
+                ui.with_layout(self.direction().trailing(), |ui| {
+                    if self.view.first_load_done && ui.button(format!("💾 {}", self.tr("export_servers"))).clicked() {
+                        self.show_export = true;
+                    }
+                    if self.freeze_order && ui.button(format!("⇅ {}", self.tr("resort"))).clicked() {
+                        trigger_resort = true;
+                    }
+                    let freeze_label = self.tr("freeze_order");
+                    ui.checkbox(&mut self.freeze_order, freeze_label);
+                });
             });
 
+            if self.show_filter_chips(ui) && self.view.first_load_done {
+                trigger_new_scan = true;
+            }
+            self.show_queue_assistant(ui);
+            if self.view.first_load_done {
+                ui.collapsing(format!("🗺 {}", self.tr("country_map")), |ui| self.show_country_map(ui));
+            }
+            trigger_load_search = self.show_saved_searches(ui);
+
+            if let Some(error) = &self.view.scan_error {
+                ui.horizontal(|ui| {
+                    let title = if self.view.servers.is_empty() { self.tr("scan_failed") } else { self.tr("scan_partial") };
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {}", title))
+                        .on_hover_text(error.as_str());
+                    if ui.button(self.tr("retry")).clicked() {
+                        trigger_retry = true;
+                    }
+                });
+            }
+
             ui.separator();
 
             egui::ScrollArea::vertical().show(ui, |ui| {
-                if self.servers.is_empty() && self.first_load_done {
+                if self.view.servers.is_empty() && self.view.first_load_done {
                     ui.label(self.tr("no_servers"));
                 }
 
-                let total_servers = self.servers.len();
+                // --- ЗАКРІПЛЕНІ СЕРВЕРИ ---
+                if !self.pinned.is_empty() {
+                    ui.strong(format!("📌 {}", self.tr("pinned")));
+                    for server in &self.pinned {
+                        self.show_server_row(ui, server, self.pin_badge(&server.id), &mut row_actions);
+                    }
+                    ui.separator();
+                }
 
-                for (index, server) in self.servers.iter().enumerate() {
-                    let response = ui.group(|ui| {
-                        ui.horizontal(|ui| {
-                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), format!("[{}]", server.country));
-                            ui.colored_label(egui::Color32::LIGHT_BLUE, &server.name);
-                        });
-                        ui.horizontal(|ui| {
-                            ui.label(format!("{} | {}", server.map, server.mode));
-                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                let color = if server.players >= server.max_players - 2 { egui::Color32::RED } else { egui::Color32::GREEN };
-                                ui.colored_label(color, format!("{}/{}", server.players, server.max_players));
-                            });
-                        });
-                    });
+                // У день патча лишаємо тільки оновлені сервери
+                let patch_day = self.patch_day_active();
+                let servers: Vec<&ServerItem> = self
+                    .view
+                    .servers
+                    .iter()
+                    .filter(|server| !self.is_pinned(&server.id))
+                    .filter(|server| !patch_day || !self.is_outdated(server))
+                    .collect();
+                let total_servers = servers.len();
+                let stale_label = self.tr("row_stale");
+
+                for (index, server) in servers.into_iter().enumerate() {
+                    // Заморожений список: рядок, до якого поточне оновлення ще не дійшло
+                    let badge = (self.freeze_order && self.view.is_stale(&server.id)).then_some(stale_label);
+                    let row_rect = self.show_server_row(ui, server, badge, &mut row_actions);
 
                     // --- ЛОГІКА INFINITE SCROLL ---
                     if index >= total_servers.saturating_sub(3) 
-                       && !self.is_loading 
-                       && !self.next_url.is_empty() 
-                       && ui.is_rect_visible(response.response.rect)
+                       && !self.view.is_loading 
+                       && !self.view.next_url.is_empty() 
+                       && ui.is_rect_visible(row_rect)
                     {
-                        trigger_load_more_url = Some(self.next_url.clone());
+                        trigger_load_more_url = Some(self.view.next_url.clone());
                     }
                 }
                 
-                if self.is_loading && !self.servers.is_empty() {
+                if self.view.is_loading && !self.view.servers.is_empty() {
                     ui.add_space(10.0);
                     ui.centered_and_justified(|ui| ui.spinner());
                 }
             });
-        });
+        }));
 
         if trigger_new_scan {
             self.run_scan(None);
         }
 
+        if trigger_refresh_all {
+            self.refresh_all();
+        }
+
+        if let Some(index) = trigger_load_search {
+            self.load_search(index);
+        }
+
+        if trigger_resort {
+            self.view.resort_servers();
+        }
+
+        if trigger_resume {
+            println!("[LOG] Resuming interrupted scan");
+            self.run_scan(Some(self.view.resume_cursor.clone()));
+        }
+
+        if trigger_retry {
+            if self.view.failed_cursor.is_empty() {
+                self.run_scan(None);
+            } else {
+                self.run_scan(Some(self.view.failed_cursor.clone()));
+            }
+        }
+
+        if let Some(id) = row_actions.toggle_pin {
+            self.toggle_pin(&id);
+        }
+
+        if let Some(id) = row_actions.join {
+            self.join_server(ctx, &id);
+        }
+
+        if let Some(id) = row_actions.query_rules {
+            self.query_rules(&id);
+        }
+
+        if let Some(id) = row_actions.plan_event {
+            self.plan_event(&id);
+        }
+
+        if let Some(key) = row_actions.pin_family {
+            self.pin_family(&key);
+        }
+
+        if let Some(key) = row_actions.ban_family {
+            self.ban_family(&key);
+        }
+
+        if let Some(id) = row_actions.hide {
+            self.hide_server(&id);
+        }
+
+        if let Some(id) = row_actions.traceroute {
+            if let Some(server) = self.latest_servers().into_iter().find(|s| s.id == id) {
+                self.trace = Some(Trace::start(&server.id, &server.ip, ctx.clone()));
+            }
+        }
+
+        if let Some(id) = row_actions.toggle_target {
+            self.queue_assistant.toggle(&id);
+        }
+
+        if let Some(id) = row_actions.clicked {
+            if self.expanded_ids.contains(&id) {
+                self.expanded_ids.remove(&id);
+            } else {
+                if self.single_expand {
+                    self.expanded_ids.clear();
+                }
+                self.expanded_ids.insert(id.clone());
+            }
+            self.selected_id = Some(id);
+            self.update_overlay();
+        }
+
         if let Some(url) = trigger_load_more_url {
             self.run_scan(Some(url));
         }
 
-        if self.show_settings {
-            let mut open = true;
-            let mut close_settings = false;
+        // Налаштування закрито - застосовуємо зміни до оверлею
+        if previous_section == Section::Settings && self.section != Section::Settings {
+            self.update_overlay();
+        }
 
-            egui::Window::new(self.tr("conf_title"))
-                .open(&mut open)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.horizontal(|ui| {
-                        ui.label(self.tr("lang"));
-                        ui.selectable_value(&mut self.language, Language::En, "English");
-                        ui.selectable_value(&mut self.language, Language::Ua, "Українська");
-                    });
-                    ui.separator();
-                    ui.horizontal(|ui| {
-                        ui.label(self.tr("min_p"));
-                        ui.add(egui::Slider::new(&mut self.min_players, 0..=100));
-                    });
-                    ui.horizontal(|ui| {
-                        ui.label(self.tr("max_p"));
-                        ui.add(egui::Slider::new(&mut self.max_players, 0..=100));
-                    });
-                    ui.separator();
-                    ui.collapsing(self.tr("bl_title"), |ui| {
-                        egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
-                            let countries = vec![
-                                ("RU", "Russia"), ("BY", "Belarus"), 
-                                ("CN", "China"), ("BR", "Brazil"), 
-                                ("AU", "Australia"), ("SG", "Singapore"), 
-                                ("KZ", "Kazakhstan"), ("HK", "Hong Kong"),
-                                ("TR", "Turkey"), ("US", "USA"), ("CA", "Canada")
-                            ];
-                            for (code, name) in countries {
-                                let mut is_banned = self.banned_countries.contains(code);
-                                if ui.checkbox(&mut is_banned, format!("{} ({})", code, name)).changed() {
-                                    if is_banned {
-                                        self.banned_countries.insert(code.to_string());
-                                    } else {
-                                        self.banned_countries.remove(code);
-                                    }
-                                }
-                            }
-                        });
-                    });
-                    ui.separator();
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(self.tr("search_name"));
-                        ui.text_edit_singleline(&mut self.filter_name);
-                    });
+        self.poll_session(ctx);
+        self.show_notification_center(ctx);
+        self.show_reset_confirmation(ctx);
+        self.show_restore_confirmation(ctx);
+        self.show_export_dialog(ctx);
+        self.show_veto_window(ctx);
+        self.show_sync_conflict(ctx);
+        self.show_palette(ctx);
+        self.draw_toast(ctx);
+    }
+}
 
-                    ui.horizontal(|ui| {
-                        ui.label(self.tr("map"));
-                        ui.text_edit_singleline(&mut self.filter_map);
-                    });
-                    ui.horizontal(|ui| {
-                        ui.label(self.tr("mode"));
-                        ui.text_edit_singleline(&mut self.filter_mode);
-                    });
-                    ui.add_space(10.0);
-                    if ui.button(self.tr("close")).clicked() {
-                        close_settings = true;
-                    }
-                });
-            
-            if close_settings || !open {
-                self.show_settings = false;
+// Схема карти: прапори по порядку, з'єднані лінією
+fn draw_flags(ui: &mut egui::Ui, flags: &[layerdb::Flag]) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(160.0, 160.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, egui::Color32::from_gray(30));
+    let points: Vec<egui::Pos2> = flags
+        .iter()
+        .map(|flag| rect.min + egui::vec2(flag.x.clamp(0.0, 1.0), flag.y.clamp(0.0, 1.0)) * rect.size())
+        .collect();
+    painter.add(egui::Shape::line(points.clone(), egui::Stroke::new(1.0, egui::Color32::GRAY)));
+    let last = points.len() - 1;
+    for (index, (flag, point)) in flags.iter().zip(&points).enumerate() {
+        let color = if index == 0 || index == last { egui::Color32::from_rgb(255, 165, 0) } else { egui::Color32::LIGHT_BLUE };
+        painter.circle_filled(*point, 5.0, color);
+        painter.text(*point + egui::vec2(0.0, 7.0), egui::Align2::CENTER_TOP, &flag.name, egui::FontId::proportional(9.0), egui::Color32::WHITE);
+    }
+}
+
+// Чекбокс "обмежити" + поле значення для фільтрів черги
+fn queue_filter(ui: &mut egui::Ui, label: &str, filters: &mut FilterSet, make: fn(u32) -> Filter, current: Option<u32>) {
+    ui.horizontal(|ui| {
+        let mut enabled = current.is_some();
+        if ui.checkbox(&mut enabled, format!("{} ≤", label)).changed() {
+            if enabled {
+                filters.set(make(0));
+            } else {
+                filters.clear_like(&make(0));
+            }
+        }
+        if let Some(mut max) = current.filter(|_| enabled) {
+            if ui.add(egui::DragValue::new(&mut max).clamp_range(0..=100)).changed() {
+                filters.set(make(max));
             }
         }
+    });
+}
+
+// Некоректні рядки розширеного запиту не відправляються
+fn valid_params(params: &[(String, String)]) -> Vec<(String, String)> {
+    params
+        .iter()
+        .filter(|(key, value)| provider::validate_param(key, value).is_ok())
+        .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+        .collect()
+}
+
+fn parse_args() -> LaunchOptions {
+    let mut launch = LaunchOptions::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--demo" => launch.demo = true,
+            "--api-url" => launch.api_url = args.next(),
+            "--record" => launch.record = args.next(),
+            "--replay" => launch.replay = args.next(),
+            "--kiosk" => launch.kiosk = true,
+            "--rtl" => launch.rtl = true,
+            "--open" => launch.open = args.next(),
+            "--minimized" => launch.minimized = true,
+            // Деякі оболонки передають посилання без --open
+            other if deeplink::parse(other).is_ok() => launch.open = Some(other.to_owned()),
+            other => println!("[ERR] Unknown argument: {}", other),
+        }
     }
+    launch
+}
+
+// Запис, який не вдалося прочитати, - помилка запуску: тихо підставлені живі
+// дані під значком REPLAY лише заплутали б відтворення бага.
+fn build_provider(launch: &LaunchOptions) -> Result<Box<dyn ServerProvider>, String> {
+    let api_url = launch.api_url.as_deref().unwrap_or(BATTLEMETRICS_URL);
+    let provider: Box<dyn ServerProvider> = if let Some(path) = &launch.replay {
+        let replay = ReplayProvider::new(path).map_err(|e| format!("Failed to load recording {}: {}", path, e))?;
+        Box::new(replay)
+    } else if launch.demo {
+        println!("[LOG] Demo mode: using bundled fixture data");
+        Box::new(DemoProvider)
+    } else {
+        if api_url != BATTLEMETRICS_URL {
+            println!("[LOG] Using custom API URL: {}", api_url);
+        }
+        Box::new(BattleMetricsProvider::new(api_url))
+    };
+
+    Ok(match &launch.record {
+        Some(path) => RecordingProvider::wrap(provider, path),
+        None => provider,
+    })
 }
 
 fn main() -> Result<(), eframe::Error> {
+    let launch = parse_args();
+    // Вікно вже відкрите - віддаємо посилання йому замість другої копії
+    if let Some(url) = &launch.open {
+        if let Ok(reply) = ipc::send(&format!("open {}", url)) {
+            println!("[LOG] Link passed to the running window: {}", reply);
+            return Ok(());
+        }
+    }
+    let provider = match build_provider(&launch) {
+        Ok(provider) => provider,
+        Err(e) => {
+            println!("[ERR] {}", e);
+            std::process::exit(1);
+        }
+    };
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([650.0, 850.0]),
+        // Прозорість потрібна компактному режиму; без нього тло заливають панелі
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size([650.0, 850.0])
+            .with_transparent(true)
+            .with_fullscreen(launch.kiosk),
         ..Default::default()
     };
     eframe::run_native(
-        "Squad Browser",
+        APP_NAME,
         options,
-        Box::new(|cc| Box::new(SquadApp::new(cc))),
+        Box::new(move |cc| Box::new(SquadApp::new(cc, launch, provider))),
     )
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    // Мінімальний HTTP-сервер: шлях запиту -> (статус, тіло).
+    // {base} у тілі замінюється адресою сервера (для links.next).
+    fn mock(routes: Vec<(&'static str, u16, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let base = format!("http://{}", addr);
+        let routes: Vec<(&str, u16, String)> =
+            routes.into_iter().map(|(path, status, body)| (path, status, body.replace("{base}", &base))).collect();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut header = String::new();
+                while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                    header.clear();
+                }
+                let target = request_line.split_whitespace().nth(1).unwrap_or("/").to_owned();
+                let path = target.split('?').next().unwrap_or("/");
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, ..)| *route == path)
+                    .map_or((404, String::new()), |(_, status, body)| (*status, body.clone()));
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn server(id: &str, name: &str, players: u32) -> serde_json::Value {
+        json!({
+            "id": id,
+            "attributes": {
+                "name": name,
+                "players": players,
+                "maxPlayers": 100,
+                "country": "DE",
+                "details": { "map": "Narva", "gameMode": "RAAS", "version": "v8.0.0" }
+            }
+        })
+    }
+
+    fn scan_filters() -> ScanFilters {
+        ScanFilters {
+            filters: FilterSet::default(),
+            extra_params: Vec::new(),
+            rules: RuleSet::default(),
+            pinned_ids: HashSet::new(),
+            layers: Arc::default(),
+        }
+    }
+
+    fn fetch(base: &str, cursor: String) -> ScanResult {
+        let provider = BattleMetricsProvider::new(&format!("{}/servers", base));
+        fetch_servers(&provider, scan_filters(), cursor)
+    }
+
+    #[test]
+    fn follows_next_links_across_pages() {
+        let next = "{base}/servers/page2?page[key]=2";
+        let base = mock(vec![
+            (
+                "/servers",
+                200,
+                json!({ "data": [server("1", "Alpha", 80), server("2", "Bravo", 60)], "links": { "next": next } })
+                    .to_string(),
+            ),
+            ("/servers/page2", 200, json!({ "data": [server("3", "Charlie", 40)], "links": {} }).to_string()),
+        ]);
+
+        let first = fetch(&base, String::new());
+        assert!(first.error.is_none());
+        assert_eq!(first.servers.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["1", "2"]);
+        assert_eq!(first.next_url, next.replace("{base}", &base));
+
+        let second = fetch(&base, first.next_url);
+        assert!(second.error.is_none());
+        assert_eq!(second.servers.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["3"]);
+        assert!(second.next_url.is_empty());
+    }
+
+    #[test]
+    fn rate_limit_is_reported_as_error() {
+        let base = mock(vec![("/servers", 429, json!({ "errors": [{ "title": "Too Many Requests" }] }).to_string())]);
+        let result = fetch(&base, String::new());
+        assert!(result.error.as_deref().is_some_and(|e| e.contains("429")), "{:?}", result.error);
+        assert!(result.servers.is_empty());
+    }
+
+    #[test]
+    fn malformed_body_is_reported_as_error() {
+        let base = mock(vec![("/servers", 200, "{\"data\": [".to_owned())]);
+        let result = fetch(&base, String::new());
+        assert!(result.error.is_some());
+        assert!(result.servers.is_empty());
+    }
+
+    #[test]
+    fn failed_next_page_keeps_its_cursor() {
+        let base = mock(vec![("/servers/page2", 429, String::new())]);
+        let cursor = format!("{}/servers/page2?page[key]=2", base);
+        let result = fetch(&base, cursor.clone());
+        assert!(result.error.is_some());
+        assert_eq!(result.failed_cursor, cursor);
+    }
+
+    #[test]
+    fn malformed_entry_is_skipped_but_its_id_kept() {
+        let broken = json!({ "id": "2", "attributes": { "name": "Bravo", "players": "many" } });
+        let base = mock(vec![("/servers", 200, json!({ "data": [server("1", "Alpha", 80), broken] }).to_string())]);
+        let result = fetch(&base, String::new());
+        assert!(result.error.is_none());
+        assert_eq!(result.servers.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["1"]);
+        assert_eq!(result.raw_ids, ["1", "2"]);
+    }
+
+    fn item(id: &str, players: u32) -> ServerItem {
+        ServerItem { id: id.to_owned(), players, ..Default::default() }
+    }
+
+    #[test]
+    fn frozen_merge_updates_in_place_and_drops_departed_rows() {
+        let mut view = ScanView { servers: vec![item("a", 10), item("b", 50), item("c", 30)], ..Default::default() };
+        view.merge_servers(vec![item("c", 70), item("d", 5)]);
+        let rows = |view: &ScanView| view.servers.iter().map(|s| (s.id.clone(), s.players)).collect::<Vec<_>>();
+        assert_eq!(rows(&view), [("a".into(), 10), ("b".into(), 50), ("c".into(), 70), ("d".into(), 5)]);
+        assert!(view.is_stale("a") && !view.is_stale("c"));
+
+        view.merge_servers(vec![item("a", 12)]);
+        view.drop_stale();
+        assert_eq!(rows(&view), [("a".into(), 12), ("c".into(), 70), ("d".into(), 5)]);
+    }
+
+    #[test]
+    fn empty_page_is_not_an_error() {
+        let base = mock(vec![("/servers", 200, json!({ "data": [], "links": {} }).to_string())]);
+        let result = fetch(&base, String::new());
+        assert!(result.error.is_none());
+        assert!(result.servers.is_empty());
+        assert!(result.next_url.is_empty());
+    }
+
+    #[test]
+    fn appearance_reset_keeps_filters_and_blacklist() {
+        let mut app = SquadApp::default();
+        app.filters.set(Filter::MinPlayers(40));
+        app.filters.set(Filter::NoCountry("RU".into()));
+        app.block_rules = "name~seed".into();
+        app.language = Language::Ua;
+        app.skin.enabled = true;
+        app.skin.opacity = 0.05;
+        app.density = Density::Compact;
+        app.row_columns.rank = false;
+        app.sidebar_width = 999.0;
+        app.layouts.push(WindowLayout { name: "broken".into(), ..Default::default() });
+        app.quick_actions.clear();
+        let filters = app.filters.clone();
+
+        app.reset_settings(ResetScope::Appearance);
+
+        let defaults = SquadApp::default();
+        assert!(app.language == defaults.language);
+        assert!(app.skin == defaults.skin);
+        assert_eq!(app.density, defaults.density);
+        assert_eq!(app.row_columns, defaults.row_columns);
+        assert_eq!(app.sidebar_width, defaults.sidebar_width);
+        assert!(app.layouts.is_empty());
+        assert_eq!(app.quick_actions, defaults.quick_actions);
+        assert_eq!(app.filters, filters);
+        assert_eq!(app.block_rules, "name~seed");
+    }
+
+    #[test]
+    fn nearly_full_without_max_players() {
+        let server = ServerItem { players: 0, max_players: 0, ..Default::default() };
+        assert!(server.is_nearly_full());
+        let server = ServerItem { players: 97, max_players: 100, ..Default::default() };
+        assert!(!server.is_nearly_full());
+        let server = ServerItem { players: 98, max_players: 100, ..Default::default() };
+        assert!(server.is_nearly_full());
+    }
+}
@@ -0,0 +1,239 @@
+// --- НОРМАЛІЗАЦІЯ НАЗВ (для банів за словами) ---
+//
+// Перейменовані сервери ховаються за іншою абеткою, цифрами чи "широкими"
+// символами: "МОСКВА", "M0SCOW", "ＭＯＳＣＯＷ". Назву зводимо до латиниці
+// в нижньому регістрі двома способами - за вимовою (транслітерація) і за
+// виглядом, бо кирилична "С" читається як s, а виглядає як латинська c.
+
+/// Два варіанти тексту: транслітерований і "за виглядом" (плюс оригінал).
+pub struct Folded {
+    plain: String,
+    spoken: String,
+    visual: String,
+}
+
+impl Folded {
+    pub fn new(text: &str) -> Self {
+        Self { plain: text.to_lowercase(), spoken: fold(text, transliterate), visual: fold(text, lookalike) }
+    }
+
+    /// Слово (будь-якою абеткою чи іншим написанням з SPELLINGS) є в назві
+    /// хоча б в одному з варіантів.
+    pub fn contains(&self, word: &str) -> bool {
+        let word = fold(word, transliterate);
+        !word.is_empty()
+            && spellings(&word).any(|word| self.spoken.contains(word) || self.visual.contains(word))
+    }
+
+    /// Слово знайшлося лише завдяки нормалізації.
+    pub fn evades(&self, word: &str) -> bool {
+        self.contains(word) && !self.plain.contains(&word.to_lowercase())
+    }
+}
+
+/// Назва, як її бачить гравець: схожі літери замінені латиницею, решта як є.
+pub fn readable(text: &str) -> String {
+    text.chars()
+        .map(compat)
+        .map(|c| {
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            match lookalike(lower) {
+                Some(latin) if lower != c => latin.to_uppercase(),
+                Some(latin) => latin.to_owned(),
+                None => c.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn is_lookalike(c: char) -> bool {
+    c.to_lowercase().any(|lower| lookalike(lower).is_some())
+}
+
+/// Слово змішує латиницю з "двійниками" з інших абеток (кирилична А замість A)
+/// або складене з широких/математичних символів - типовий обхід фільтрів.
+/// Справжнє кириличне слово поруч з латиницею ("[UA]Січ") має й літери без двійників.
+pub fn has_homoglyphs(text: &str) -> bool {
+    text.split_whitespace().any(|word| {
+        let latin = word.chars().any(|c| c.is_ascii_alphabetic());
+        let foreign: Vec<char> = word.chars().filter(|c| c.is_alphabetic() && !c.is_ascii()).collect();
+        word.chars().any(|c| compat(c) != c)
+            || (latin && !foreign.is_empty() && foreign.iter().all(|&c| is_lookalike(c)))
+    })
+}
+
+// Та сама назва іншою мовою: "MOSCOW" ловить і "Moskva" / "МОСКВА"
+const SPELLINGS: &[&[&str]] = &[&["moscow", "moskva", "moskau"], &["russia", "rossiya", "rossia"]];
+
+fn spellings(word: &str) -> impl Iterator<Item = &str> {
+    let group = SPELLINGS.iter().find(|group| group.contains(&word)).copied().unwrap_or_default();
+    std::iter::once(word).chain(group.iter().copied().filter(move |&other| other != word))
+}
+
+fn fold(text: &str, script: fn(char) -> Option<&'static str>) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars().map(compat).flat_map(char::to_lowercase) {
+        // Діакритика, що прийшла окремими символами (розкладений юнікод)
+        if ('\u{0300}'..='\u{036F}').contains(&c) {
+            continue;
+        }
+        match script(c) {
+            Some(latin) => out.push_str(latin),
+            None => out.push(leet(c).unwrap_or_else(|| strip_accent(c))),
+        }
+    }
+    out
+}
+
+// Сумісні форми -> звичайна латиниця: повноширинні, математичні, в колах
+fn compat(c: char) -> char {
+    let code = c as u32;
+    let mapped = match code {
+        0xFF01..=0xFF5E => code - 0xFEE0,
+        // Математичні літери: 13 накреслень по 52 (A-Z, a-z)
+        0x1D400..=0x1D6A3 => {
+            let index = (code - 0x1D400) % 52;
+            if index < 26 { 'A' as u32 + index } else { 'a' as u32 + index - 26 }
+        }
+        0x24B6..=0x24CF => 'A' as u32 + code - 0x24B6,
+        0x24D0..=0x24E9 => 'a' as u32 + code - 0x24D0,
+        _ => code,
+    };
+    char::from_u32(mapped).unwrap_or(c)
+}
+
+fn leet(c: char) -> Option<char> {
+    match c {
+        '0' => Some('o'),
+        '1' => Some('i'),
+        '3' => Some('e'),
+        '4' | '@' => Some('a'),
+        '5' | '$' => Some('s'),
+        '7' => Some('t'),
+        _ => None,
+    }
+}
+
+fn strip_accent(c: char) -> char {
+    match c {
+        'à'..='å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è'..='ë' | 'ē' | 'ė' | 'ę' | 'ě' => 'e',
+        'ì'..='ï' | 'ī' | 'į' | 'ı' => 'i',
+        'ĺ' | 'ľ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò'..='ö' | 'ø' | 'ō' | 'ő' => 'o',
+        'ŕ' | 'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ţ' | 'ť' => 't',
+        'ù'..='ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    }
+}
+
+// Кирилиця за вимовою (російська й українська)
+fn transliterate(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+// Кирилиця й грецька, що виглядають як латинські літери
+fn lookalike(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'а' | 'α' => "a",
+        'в' | 'β' | 'ь' => "b",
+        'с' => "c",
+        'ԁ' => "d",
+        'е' | 'ё' | 'ε' => "e",
+        'г' => "r",
+        'н' => "h",
+        'і' | 'ї' | 'ι' => "i",
+        'ј' => "j",
+        'к' | 'κ' => "k",
+        'м' => "m",
+        'п' | 'η' => "n",
+        'о' | 'ο' => "o",
+        'р' | 'ρ' => "p",
+        'ԛ' => "q",
+        'ѕ' => "s",
+        'т' | 'τ' => "t",
+        'υ' => "u",
+        'ν' => "v",
+        'ԝ' | 'ω' => "w",
+        'х' | 'χ' => "x",
+        'у' => "y",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spellings_of_moscow_are_caught() {
+        for name in ["Moskva Warriors", "МОСКВА 24/7", "M0SCOW | RAAS", "ＭＯＳＣＯＷ", "MOSCOW"] {
+            assert!(Folded::new(name).contains("MOSCOW"), "{}", name);
+        }
+        for name in ["Moskva Warriors", "МОСКВА 24/7", "M0SCOW | RAAS", "ＭＯＳＣＯＷ"] {
+            assert!(Folded::new(name).evades("MOSCOW"), "{}", name);
+        }
+        assert!(!Folded::new("MOSCOW").evades("MOSCOW"));
+    }
+
+    #[test]
+    fn ordinary_names_are_not_evasions() {
+        let words = ["RUSSIA", "MOSCOW", "WAGNER", "ZOV", "CHINESE", "QQ"];
+        for name in ["[UA] Січ | Invasion", "Mosquito Gaming EU", "Frankfurt RAAS #1", "Los Angeles Seed", "Zovko's Squad"] {
+            let folded = Folded::new(name);
+            assert!(words.iter().all(|word| !folded.evades(word)), "{}", name);
+            assert!(!has_homoglyphs(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn mixed_scripts_are_homoglyphs() {
+        // Кирилична "О" серед латиниці
+        assert!(has_homoglyphs("MОSCOW"));
+        assert!(has_homoglyphs("ＭＯＳＣＯＷ"));
+        assert_eq!(readable("MОSCOW"), "MOSCOW");
+    }
+}
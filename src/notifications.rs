@@ -0,0 +1,68 @@
+use chrono::Timelike;
+use serde::{Deserialize, Serialize};
+
+// --- СПОВІЩЕННЯ І ТИХІ ГОДИНИ ---
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct QuietHours {
+    pub enabled: bool,
+    // Години за місцевим часом, 0..=23. Проміжок може переходити через північ.
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self { enabled: false, start: 23, end: 8 }
+    }
+}
+
+impl QuietHours {
+    pub fn contains(&self, hour: u32) -> bool {
+        if !self.enabled || self.start == self.end {
+            return false;
+        }
+        if self.start < self.end {
+            hour >= self.start && hour < self.end
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.contains(chrono::Local::now().hour())
+    }
+}
+
+pub const MAX_NOTIFICATIONS: usize = 100;
+
+// Що відкрити при кліку на сповіщення
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq)]
+pub enum NotificationAction {
+    #[default]
+    None,
+    Server(String),
+    SavedSearch(String),
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Notification {
+    // Секунди від UNIX epoch
+    pub at: u64,
+    pub text: String,
+    // Прийшло в тихі години - тост не показували
+    pub suppressed: bool,
+    pub read: bool,
+    pub action: NotificationAction,
+}
+
+/// Час сповіщення за місцевим часом, ГГ:ХХ.
+pub fn time_label(at: u64) -> String {
+    use chrono::TimeZone;
+    match chrono::Local.timestamp_opt(at as i64, 0).single() {
+        Some(time) => time.format("%H:%M").to_string(),
+        None => "--:--".to_owned(),
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::ServerItem;
+
+// --- ОВЕРЛЕЙ ДЛЯ СТРІМУ (OBS) ---
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub enum OverlayFormat {
+    Text,
+    Html,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct OverlaySettings {
+    pub enabled: bool,
+    pub path: String,
+    pub format: OverlayFormat,
+    pub template: String,
+}
+
+impl Default for OverlaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "squad_overlay.txt".to_owned(),
+            format: OverlayFormat::Text,
+            template: "Now browsing: {name} | {players}/{max_players} | {map}".to_owned(),
+        }
+    }
+}
+
+pub const PLACEHOLDERS: &str = "{name} {players} {max_players} {map} {mode} {country}";
+
+// Один прохід зліва направо: текст підставлених значень повторно не розбирається,
+// тож сервер "{map} giveaway" так і лишається з "{map}" у назві
+pub fn render(template: &str, server: &ServerItem) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let tail = &rest[open..];
+        let Some(close) = tail.find('}') else {
+            rest = tail;
+            break;
+        };
+        match value(&tail[1..close], server) {
+            Some(text) => {
+                out.push_str(&text);
+                rest = &tail[close + 1..];
+            }
+            // Не плейсхолдер: дужка лишається як є, шукаємо далі з наступного символу
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn value(placeholder: &str, server: &ServerItem) -> Option<String> {
+    Some(match placeholder {
+        "name" => server.name.clone(),
+        "players" => server.players.to_string(),
+        "max_players" => server.max_players.to_string(),
+        "map" => server.map.to_string(),
+        "mode" => server.mode.to_string(),
+        "country" => server.country.to_string(),
+        _ => return None,
+    })
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// OBS "Browser Source" сам перечитує файл завдяки meta refresh
+fn wrap_html(text: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><meta http-equiv=\"refresh\" content=\"2\">\
+         <style>body{{margin:0;font-family:sans-serif;font-size:28px;color:#fff;text-shadow:1px 1px 2px #000;}}</style>\
+         </head><body>{}</body></html>\n",
+        escape_html(text)
+    )
+}
+
+pub fn write(settings: &OverlaySettings, server: &ServerItem) {
+    if !settings.enabled || settings.path.is_empty() {
+        return;
+    }
+
+    let text = render(&settings.template, server);
+    let content = match settings.format {
+        OverlayFormat::Text => text,
+        OverlayFormat::Html => wrap_html(&text),
+    };
+
+    if let Err(e) = fs::write(&settings.path, content) {
+        println!("[ERR] Failed to write overlay file: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(name: &str) -> ServerItem {
+        ServerItem {
+            name: name.to_owned(),
+            players: 80,
+            max_players: 100,
+            map: "Narva".into(),
+            mode: "RAAS".into(),
+            country: "UA".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fills_every_placeholder() {
+        let text = render("{name} {players}/{max_players} {map} {mode} [{country}]", &server("Kyiv #1"));
+        assert_eq!(text, "Kyiv #1 80/100 Narva RAAS [UA]");
+    }
+
+    #[test]
+    fn values_are_not_substituted_again() {
+        let text = render("{name} | {map}", &server("{map} giveaway {players}"));
+        assert_eq!(text, "{map} giveaway {players} | Narva");
+    }
+
+    #[test]
+    fn unknown_and_unclosed_braces_stay() {
+        assert_eq!(render("{ping} {{map}} {map", &server("x")), "{ping} {Narva} {map");
+    }
+}
@@ -0,0 +1,57 @@
+// --- ПАЛІТРА КОМАНД (Ctrl+K) ---
+//
+// Список команд будує додаток щоразу, коли палітра відкрита, тож у ньому
+// завжди актуальні вкладки, пошуки й обране. Тут - лише стан вікна і
+// нечіткий пошук по назвах.
+
+#[derive(Default)]
+pub struct Palette {
+    pub open: bool,
+    pub query: String,
+    pub selected: usize,
+}
+
+impl Palette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+}
+
+/// Нечіткий збіг: усі символи запиту йдуть у назві по порядку.
+/// Більше очок за символи підряд і на початку слів; None - не збігається.
+pub fn score(query: &str, label: &str) -> Option<i32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut at = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = at + label[at..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !label[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        at = found + 1;
+    }
+    // Серед рівних - коротша назва точніша
+    Some(score * 100 - label.len() as i32)
+}
+
+/// Команди, що збігаються із запитом, від найкращого збігу.
+pub fn filter<T>(query: &str, commands: Vec<(String, T)>) -> Vec<(String, T)> {
+    let mut scored: Vec<(i32, (String, T))> =
+        commands.into_iter().filter_map(|command| Some((score(query, &command.0)?, command))).collect();
+    if !query.trim().is_empty() {
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    }
+    scored.into_iter().map(|(_, command)| command).collect()
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+// --- ДЕНЬ ПАТЧА ---
+//
+// Стрічка новин Steam (ISteamNews) підказує, що вийшло оновлення Squad. Тоді
+// вмикається режим дня патча: у рядках видно версію сервера, застарілі сервери
+// ховаються, а фільтри гравців і черги не діють - у такий день усе
+// перезапускається і населення скаче.
+
+const APP_ID: u32 = 393380;
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+// Скільки триває режим після виходу оновлення
+pub const WINDOW_SECS: u64 = 24 * 60 * 60;
+// Назви новин без тегу patchnotes, які теж означають оновлення
+const TITLE_WORDS: [&str; 4] = ["hotfix", "patch", "update v", "release notes"];
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct PatchDaySettings {
+    // Вмикати режим самому, щойно знайдено свіже оновлення
+    pub auto: bool,
+    // Режим діє до цього часу (секунди UNIX); 0 - вимкнено
+    pub until: u64,
+    // gid новини, на яку вже відреагували, щоб вимкнений режим не вмикався знову
+    pub seen: String,
+}
+
+impl Default for PatchDaySettings {
+    fn default() -> Self {
+        Self { auto: true, until: 0, seen: String::new() }
+    }
+}
+
+impl PatchDaySettings {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.until > now
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PatchNote {
+    pub gid: String,
+    pub title: String,
+    pub url: String,
+    pub date: u64,
+}
+
+impl PatchNote {
+    pub fn is_fresh(&self, now: u64) -> bool {
+        now < self.date + WINDOW_SECS
+    }
+}
+
+/// Найновіша новина-оновлення Squad (свіжа чи ні).
+pub fn fetch() -> Result<Option<PatchNote>, String> {
+    let url = format!(
+        "https://api.steampowered.com/ISteamNews/GetNewsForApp/v2/?appid={}&count=10&maxlength=1&feeds=steam_community_announcements",
+        APP_ID
+    );
+    let body = reqwest::blocking::get(url)
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.text())
+        .map_err(|e| e.to_string())?;
+    parse(&body)
+}
+
+pub fn parse(body: &str) -> Result<Option<PatchNote>, String> {
+    let json: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    let items = json
+        .pointer("/appnews/newsitems")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "expected appnews.newsitems".to_owned())?;
+    let text = |item: &Value, key: &str| item.get(key).and_then(Value::as_str).unwrap_or_default().trim().to_owned();
+    let note = items
+        .iter()
+        .filter(|item| is_patch(item))
+        .map(|item| PatchNote {
+            gid: text(item, "gid"),
+            title: text(item, "title"),
+            url: text(item, "url"),
+            date: item.get("date").and_then(Value::as_u64).unwrap_or(0),
+        })
+        .filter(|note| !note.gid.is_empty())
+        .max_by_key(|note| note.date);
+    Ok(note)
+}
+
+fn is_patch(item: &Value) -> bool {
+    let tagged = item
+        .get("tags")
+        .and_then(Value::as_array)
+        .is_some_and(|tags| tags.iter().any(|tag| tag.as_str() == Some("patchnotes")));
+    let title = item.get("title").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+    tagged || TITLE_WORDS.iter().any(|word| title.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn newest_patch_note_wins() {
+        let body = json!({"appnews": {"newsitems": [
+            {"gid": "1", "title": "Community Spotlight", "date": 300},
+            {"gid": "2", "title": "Squad v8.1 Hotfix", "url": " https://x/2 ", "date": 200},
+            {"gid": "3", "title": "Squad 8.1", "tags": ["patchnotes"], "date": 100},
+            {"gid": "", "title": "Patch without id", "date": 400},
+        ]}});
+        let note = parse(&body.to_string()).unwrap().unwrap();
+        assert_eq!(note, PatchNote { gid: "2".into(), title: "Squad v8.1 Hotfix".into(), url: "https://x/2".into(), date: 200 });
+
+        let none = json!({"appnews": {"newsitems": [{"gid": "1", "title": "Sale!", "date": 1}]}});
+        assert_eq!(parse(&none.to_string()), Ok(None));
+        assert!(parse("{}").is_err());
+    }
+
+    #[test]
+    fn window_and_mode() {
+        let note = PatchNote { gid: "1".into(), title: String::new(), url: String::new(), date: 1000 };
+        assert!(note.is_fresh(1000 + WINDOW_SECS - 1));
+        assert!(!note.is_fresh(1000 + WINDOW_SECS));
+        let settings = PatchDaySettings { until: 500, ..Default::default() };
+        assert!(settings.is_active(499));
+        assert!(!settings.is_active(500));
+        assert!(!PatchDaySettings::default().is_active(0));
+    }
+}
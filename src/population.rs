@@ -0,0 +1,80 @@
+use chrono::{TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// --- СУМАРНИЙ ОНЛАЙН ПО ВКЛАДКАХ (зберігається між запусками) ---
+//
+// Після кожного оновлення вкладки записуємо суму гравців усіх знайдених
+// серверів. Ряд - назва вкладки, тобто один набір фільтрів.
+
+const KEEP_FOR_SECS: u64 = 30 * 24 * 60 * 60;
+// Наступні сторінки того ж сканування оновлюють останній запис, а не додають новий
+const MERGE_WINDOW_SECS: u64 = 5 * 60;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct Sample {
+    // Секунди від UNIX epoch
+    pub at: u64,
+    pub players: u32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Range {
+    Day,
+    Week,
+    Month,
+}
+
+impl Range {
+    pub fn secs(self) -> u64 {
+        let days = match self {
+            Range::Day => 1,
+            Range::Week => 7,
+            Range::Month => 30,
+        };
+        days * 24 * 60 * 60
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct PopulationLog {
+    series: HashMap<String, Vec<Sample>>,
+}
+
+impl PopulationLog {
+    pub fn record(&mut self, series: &str, now: u64, players: u32) {
+        let samples = self.series.entry(series.to_owned()).or_default();
+        match samples.last_mut() {
+            Some(last) if now.saturating_sub(last.at) < MERGE_WINDOW_SECS => {
+                last.players = last.players.max(players);
+            }
+            _ => samples.push(Sample { at: now, players }),
+        }
+        samples.retain(|s| now.saturating_sub(s.at) <= KEEP_FOR_SECS);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.series.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Середній онлайн за кожну годину доби (місцевий час) у межах `range`.
+    /// None - за цю годину записів немає.
+    pub fn by_hour(&self, series: &str, now: u64, range: Range) -> [Option<f32>; 24] {
+        let mut sums = [(0u64, 0u32); 24];
+        for sample in self.series.get(series).into_iter().flatten() {
+            if now.saturating_sub(sample.at) > range.secs() {
+                continue;
+            }
+            if let Some(time) = chrono::Local.timestamp_opt(sample.at as i64, 0).single() {
+                let slot = &mut sums[time.hour() as usize];
+                slot.0 += sample.players as u64;
+                slot.1 += 1;
+            }
+        }
+        sums.map(|(total, count)| if count > 0 { Some(total as f32 / count as f32) } else { None })
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::scheduler::POLL_MIN;
+
+// --- ДОСТУПНІСТЬ ЗАКРІПЛЕНИХ СЕРВЕРІВ ---
+//
+// Статус змінюється лише після DEBOUNCE однакових перевірок поспіль, щоб
+// сервер, що перезапускається в день патча, не смикав сповіщеннями.
+// Які сервери опитувати і як часто, вирішує scheduler::AdaptivePolls.
+
+const DEBOUNCE: u32 = 2;
+
+#[derive(Default)]
+struct Presence {
+    // Підтверджений статус; None - ще не перевіряли
+    online: Option<bool>,
+    streak: u32,
+}
+
+#[derive(Default)]
+pub struct PresenceTracker {
+    servers: HashMap<String, Presence>,
+    last_checked: Option<Instant>,
+    pub pending: bool,
+}
+
+impl PresenceTracker {
+    pub fn is_due_within(&self, window: Duration) -> bool {
+        !self.pending && self.last_checked.is_none_or(|at| at.elapsed() + window >= POLL_MIN)
+    }
+
+    pub fn started(&mut self) {
+        self.pending = true;
+        self.last_checked = Some(Instant::now());
+    }
+
+    /// Результат перевірки серверів `polled` з усіх закріплених `watched`.
+    /// Повертає сервери з підтвердженою зміною статусу: (id, тепер онлайн).
+    pub fn update(&mut self, watched: &[String], polled: &[String], online_ids: &HashSet<String>) -> Vec<(String, bool)> {
+        self.pending = false;
+        self.servers.retain(|id, _| watched.contains(id));
+
+        let mut changes = Vec::new();
+        for id in polled.iter().filter(|id| watched.contains(id)) {
+            let online = online_ids.contains(id);
+            let presence = self.servers.entry(id.clone()).or_default();
+            match presence.online {
+                None => presence.online = Some(online),
+                Some(confirmed) if confirmed == online => presence.streak = 0,
+                Some(_) => {
+                    presence.streak += 1;
+                    if presence.streak >= DEBOUNCE {
+                        presence.online = Some(online);
+                        presence.streak = 0;
+                        changes.push((id.clone(), online));
+                    }
+                }
+            }
+        }
+        changes
+    }
+
+    pub fn failed(&mut self) {
+        self.pending = false;
+    }
+
+    pub fn is_offline(&self, id: &str) -> bool {
+        self.servers.get(id).is_some_and(|p| p.online == Some(false))
+    }
+}
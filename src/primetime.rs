@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// --- ПРАЙМ-ТАЙМ СЕРВЕРІВ ---
+//
+// Коли сервер зазвичай заповнений. Онлайн з кожного сканування й перевірки
+// присутності усереднюється по годинах доби (UTC), тож на сервер припадає 24
+// числа, а не ряд вимірів. Поки годин з вимірами мало, прайм-тайм береться
+// з країни: вечір 18-22 за її поясом (приблизно, без літнього часу).
+
+const HOUR_SECS: u64 = 60 * 60;
+const KEEP_FOR_SECS: u64 = 30 * 24 * HOUR_SECS;
+// Тривалість прайм-тайму, годин
+const WINDOW: u32 = 4;
+// Скільки різних годин доби треба побачити, щоб вірити історії більше, ніж країні
+const MIN_HOURS: usize = 8;
+// Вечір за місцевим часом сервера, коли країну беремо замість історії
+const REGION_START: i32 = 18;
+// "Мої вечори" для терму `evening`: 18:00-23:00 за часом користувача
+const EVENING: std::ops::Range<u32> = 18..23;
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+struct Profile {
+    // Останній вимір, секунди від UNIX epoch
+    seen: u64,
+    // Середній онлайн за кожну годину UTC; None - в цю годину сервер не бачили
+    hours: [Option<u16>; 24],
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Source {
+    History,
+    Region,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PrimeTime {
+    // Початок, година UTC
+    pub start: u32,
+    pub source: Source,
+}
+
+impl PrimeTime {
+    /// Початок і кінець (години доби) для зсуву від UTC у секундах.
+    pub fn local_hours(self, offset_secs: i32) -> (u32, u32) {
+        let start = (self.start as i32 + offset_secs.div_euclid(3600)).rem_euclid(24) as u32;
+        (start, (start + WINDOW) % 24)
+    }
+
+    pub fn contains(self, hour_utc: u32) -> bool {
+        (hour_utc + 24 - self.start) % 24 < WINDOW
+    }
+
+    /// Хоча б дві години прайм-тайму припадають на вечір користувача.
+    pub fn in_evening(self, offset_secs: i32) -> bool {
+        let (start, _) = self.local_hours(offset_secs);
+        (0..WINDOW).filter(|i| EVENING.contains(&((start + i) % 24))).count() >= 2
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct PrimeTimeLog {
+    servers: HashMap<String, Profile>,
+}
+
+impl PrimeTimeLog {
+    pub fn record(&mut self, id: &str, now: u64, players: u32) {
+        let profile = self.servers.entry(id.to_owned()).or_default();
+        let slot = &mut profile.hours[hour_of(now) as usize];
+        let players = players.min(u16::MAX as u32) as u16;
+        // Ковзне середнє: свіжі тижні важать більше за давні
+        *slot = Some(match *slot {
+            Some(average) => ((average as u32 * 3 + players as u32 + 2) / 4) as u16,
+            None => players,
+        });
+        profile.seen = now;
+    }
+
+    /// Забуває сервери, яких не бачили KEEP_FOR_SECS.
+    pub fn prune(&mut self, now: u64) {
+        self.servers.retain(|_, profile| now.saturating_sub(profile.seen) <= KEEP_FOR_SECS);
+    }
+
+    /// За історією, а поки її мало - за країною. None - сервер ніколи не
+    /// заповнюється або країна невідома.
+    pub fn prime_time(&self, id: &str, country: &str) -> Option<PrimeTime> {
+        match self.servers.get(id).filter(|p| p.hours.iter().flatten().count() >= MIN_HOURS) {
+            Some(profile) => busiest_window(&profile.hours),
+            None => by_region(country),
+        }
+    }
+}
+
+pub fn hour_of(secs: u64) -> u32 {
+    ((secs / HOUR_SECS) % 24) as u32
+}
+
+/// Зсув місцевого часу користувача від UTC, секунди.
+pub fn local_offset() -> i32 {
+    chrono::Local::now().offset().local_minus_utc()
+}
+
+// Вікно WINDOW годин з найбільшим середнім серед відомих годин
+fn busiest_window(hours: &[Option<u16>; 24]) -> Option<PrimeTime> {
+    let mut best: Option<(u32, u32)> = None;
+    for start in 0..24 {
+        let known: Vec<u32> = (0..WINDOW).filter_map(|i| hours[((start + i) % 24) as usize]).map(u32::from).collect();
+        if known.len() < 2 {
+            continue;
+        }
+        let average = known.iter().sum::<u32>() / known.len() as u32;
+        if average > 0 && best.is_none_or(|(_, top)| average > top) {
+            best = Some((start, average));
+        }
+    }
+    best.map(|(start, _)| PrimeTime { start, source: Source::History })
+}
+
+fn by_region(country: &str) -> Option<PrimeTime> {
+    let offset = utc_offset(country)?;
+    Some(PrimeTime { start: (REGION_START - offset).rem_euclid(24) as u32, source: Source::Region })
+}
+
+// Стандартний пояс країни, годин від UTC; у великих країнах - де більшість гравців
+fn utc_offset(country: &str) -> Option<i32> {
+    let offset = match country {
+        "GB" | "IE" | "PT" | "IS" => 0,
+        "FR" | "DE" | "NL" | "BE" | "LU" | "CH" | "AT" | "IT" | "ES" | "DK" | "NO" | "SE" | "PL" | "CZ" | "SK"
+        | "HU" | "HR" | "SI" | "RS" | "BA" | "MK" | "AL" | "ME" => 1,
+        "FI" | "EE" | "LV" | "LT" | "RO" | "BG" | "GR" | "MD" | "UA" | "IL" | "ZA" => 2,
+        "BY" | "RU" | "TR" | "SA" => 3,
+        "AE" => 4,
+        "KZ" | "UZ" | "TJ" | "TM" | "PK" | "IN" | "LK" => 5,
+        "KG" | "BD" | "NP" => 6,
+        "TH" | "VN" | "KH" | "LA" | "ID" | "MM" => 7,
+        "CN" | "HK" | "TW" | "MN" | "MY" | "SG" | "PH" => 8,
+        "JP" | "KR" => 9,
+        "AU" | "PG" => 10,
+        "NZ" | "FJ" => 12,
+        "BR" | "AR" | "UY" => -3,
+        "CL" | "VE" | "PY" | "BO" => -4,
+        "CA" | "CO" | "PE" | "EC" => -5,
+        "US" | "MX" => -6,
+        _ => return None,
+    };
+    Some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Початок доби UTC
+    const DAY: u64 = 20_000 * 24 * HOUR_SECS;
+
+    #[test]
+    fn region_fallback_until_enough_history() {
+        let mut log = PrimeTimeLog::default();
+        let ua = log.prime_time("1", "UA");
+        assert_eq!(ua, Some(PrimeTime { start: 16, source: Source::Region }));
+        assert_eq!(log.prime_time("1", "XX"), None);
+
+        for hour in 0..MIN_HOURS as u64 - 1 {
+            log.record("1", DAY + hour * HOUR_SECS, 80);
+        }
+        assert_eq!(log.prime_time("1", "UA"), ua);
+    }
+
+    #[test]
+    fn busiest_hours_win() {
+        let mut log = PrimeTimeLog::default();
+        for hour in 0..24 {
+            let players = if (2..6).contains(&hour) { 95 } else { 10 };
+            log.record("1", DAY + hour * HOUR_SECS, players);
+        }
+        assert_eq!(log.prime_time("1", "UA"), Some(PrimeTime { start: 2, source: Source::History }));
+
+        let mut empty = PrimeTimeLog::default();
+        for hour in 0..24 {
+            empty.record("2", DAY + hour * HOUR_SECS, 0);
+        }
+        assert_eq!(empty.prime_time("2", "UA"), None);
+    }
+
+    #[test]
+    fn converted_to_local_evening() {
+        // 17:00-21:00 UTC
+        let prime = PrimeTime { start: 17, source: Source::History };
+        assert_eq!(prime.local_hours(0), (17, 21));
+        assert_eq!(prime.local_hours(3 * 3600), (20, 0));
+        assert_eq!(prime.local_hours(-5 * 3600), (12, 16));
+        assert!(prime.in_evening(0));
+        assert!(prime.in_evening(3 * 3600));
+        assert!(!prime.in_evening(-5 * 3600));
+        assert!(!prime.in_evening(10 * 3600));
+        assert!(prime.contains(20));
+        assert!(!prime.contains(21));
+        assert!(PrimeTime { start: 22, source: Source::Region }.contains(1));
+    }
+
+    #[test]
+    fn stale_servers_are_pruned() {
+        let mut log = PrimeTimeLog::default();
+        log.record("old", DAY, 50);
+        log.record("fresh", DAY + KEEP_FOR_SECS, 50);
+        log.prune(DAY + KEEP_FOR_SECS + HOUR_SECS);
+        assert!(!log.servers.contains_key("old"));
+        assert!(log.servers.contains_key("fresh"));
+    }
+}
@@ -0,0 +1,184 @@
+// --- ДЖЕРЕЛА ДАНИХ ---
+//
+// Провайдер повертає сире тіло відповіді (JSON у форматі BattleMetrics),
+// а розбір і фільтрація лишаються в fetch_servers.
+
+pub enum PageRequest {
+    First {
+        min_players: u32,
+        max_players: u32,
+        // Додаткові параметри з панелі "Розширений запит"
+        extra_params: Vec<(String, String)>,
+    },
+    Next(String),
+}
+
+pub trait ServerProvider: Send + Sync {
+    fn fetch_page(&self, request: &PageRequest) -> Result<String, String>;
+}
+
+// --- BATTLEMETRICS API ---
+
+pub const BATTLEMETRICS_URL: &str = "https://api.battlemetrics.com/servers";
+
+pub struct BattleMetricsProvider {
+    client: reqwest::blocking::Client,
+    base_url: String,
+}
+
+impl BattleMetricsProvider {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            client: reqwest::blocking::Client::new(),
+            base_url: base_url.to_owned(),
+        }
+    }
+}
+
+pub fn first_page_params(min_players: u32, max_players: u32, extra_params: &[(String, String)]) -> Vec<(String, String)> {
+    let mut params: Vec<(String, String)> = [
+        ("filter[game]", "squad".to_owned()),
+        ("filter[status]", "online".to_owned()),
+        ("page[size]", "50".to_owned()),
+        ("sort", "-players".to_owned()),
+        ("filter[players][min]", min_players.to_string()),
+        ("filter[players][max]", max_players.to_string()),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_owned(), value))
+    .collect();
+    params.extend(extra_params.iter().cloned());
+    params
+}
+
+/// Перевірка рядка розширеного запиту. BattleMetrics приймає filter[..], page[..],
+/// fields[..], sort та include.
+pub fn validate_param(key: &str, value: &str) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("empty parameter name".to_owned());
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "[]_.-:".contains(c))
+    {
+        return Err(format!("invalid characters in \"{}\"", key));
+    }
+    let known_prefix = ["filter[", "page[", "fields["].iter().any(|p| key.starts_with(p));
+    if !known_prefix && key != "sort" && key != "include" {
+        return Err(format!("unsupported parameter \"{}\"", key));
+    }
+    if key.matches('[').count() != key.matches(']').count() {
+        return Err(format!("unbalanced brackets in \"{}\"", key));
+    }
+    if value.trim().is_empty() {
+        return Err(format!("empty value for \"{}\"", key));
+    }
+    Ok(())
+}
+
+/// URL першої сторінки - для попереднього перегляду в розширеному запиті.
+pub fn first_page_url(base_url: &str, min_players: u32, max_players: u32, extra_params: &[(String, String)]) -> String {
+    match reqwest::Url::parse_with_params(base_url, first_page_params(min_players, max_players, extra_params)) {
+        Ok(url) => url.to_string(),
+        Err(e) => format!("invalid URL: {}", e),
+    }
+}
+
+impl ServerProvider for BattleMetricsProvider {
+    fn fetch_page(&self, request: &PageRequest) -> Result<String, String> {
+        let request = match request {
+            PageRequest::First { min_players, max_players, extra_params } => {
+                println!("[LOG] New search initiated. Filters applied.");
+                self.client
+                    .get(&self.base_url)
+                    .query(&first_page_params(*min_players, *max_players, extra_params))
+            }
+            PageRequest::Next(url) => {
+                println!("[LOG] Loading next page from API...");
+                self.client.get(url)
+            }
+        };
+
+        request
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .and_then(|resp| resp.text())
+            .map_err(|e| e.to_string())
+    }
+}
+
+// --- ДЕМО-РЕЖИМ (офлайн, вбудовані дані) ---
+
+const DEMO_PAGES: [(&str, &str); 2] = [
+    ("demo://page/1", include_str!("../assets/demo/page1.json")),
+    ("demo://page/2", include_str!("../assets/demo/page2.json")),
+];
+
+pub struct DemoProvider;
+
+impl DemoProvider {
+    // Імітуємо серверний фільтр по кількості гравців. Як і в справжньому API,
+    // параметри фільтра переносяться в посилання на наступну сторінку.
+    fn serve(body: &str, min_players: u32, max_players: u32) -> Result<String, String> {
+        let mut json: serde_json::Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+        if let Some(data) = json.get_mut("data").and_then(|d| d.as_array_mut()) {
+            data.retain(|item| {
+                let players = item["attributes"]["players"].as_u64().unwrap_or(0) as u32;
+                players >= min_players && players <= max_players
+            });
+        }
+        if let Some(next) = json["links"]["next"].as_str() {
+            let next = format!("{}?min={}&max={}", next, min_players, max_players);
+            json["links"]["next"] = serde_json::Value::String(next);
+        }
+        Ok(json.to_string())
+    }
+
+    // filter[ids][whitelist]=1,2,3 - сервери з усіх сторінок за id
+    fn serve_ids(ids: &str) -> Result<String, String> {
+        let ids: Vec<&str> = ids.split(',').map(str::trim).collect();
+        let mut data = Vec::new();
+        for (_, body) in DEMO_PAGES {
+            let json: serde_json::Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+            if let Some(items) = json["data"].as_array() {
+                data.extend(items.iter().filter(|item| item["id"].as_str().is_some_and(|id| ids.contains(&id))).cloned());
+            }
+        }
+        Ok(serde_json::json!({ "data": data, "links": {} }).to_string())
+    }
+}
+
+impl ServerProvider for DemoProvider {
+    fn fetch_page(&self, request: &PageRequest) -> Result<String, String> {
+        match request {
+            PageRequest::First { min_players, max_players, extra_params } => {
+                if let Some((_, ids)) = extra_params.iter().find(|(key, _)| key == "filter[ids][whitelist]") {
+                    println!("[LOG] Demo mode: serving servers by id");
+                    return Self::serve_ids(ids);
+                }
+                println!("[LOG] Demo mode: serving bundled page 1");
+                Self::serve(DEMO_PAGES[0].1, *min_players, *max_players)
+            }
+            PageRequest::Next(url) => {
+                println!("[LOG] Demo mode: serving {}", url);
+                let (page_url, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+                let mut min_players = 0;
+                let mut max_players = u32::MAX;
+                for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+                    match key {
+                        "min" => min_players = value.parse().unwrap_or(min_players),
+                        "max" => max_players = value.parse().unwrap_or(max_players),
+                        _ => {}
+                    }
+                }
+                let body = DEMO_PAGES
+                    .iter()
+                    .find(|(demo_url, _)| *demo_url == page_url)
+                    .map(|(_, body)| *body)
+                    .ok_or_else(|| format!("unknown demo page: {}", url))?;
+                Self::serve(body, min_players, max_players)
+            }
+        }
+    }
+}
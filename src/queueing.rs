@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+use crate::ServerItem;
+
+// --- ЧЕРГА НА КІЛЬКА СЕРВЕРІВ ("зайти на будь-який з цих") ---
+//
+// Поки асистент працює, раз на CHECK_INTERVAL запитуємо лише вибрані сервери;
+// перший, де звільнилось місце, - сповіщення або автовхід, і асистент зупиняється.
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+pub struct QueueAssistant {
+    pub targets: Vec<String>,
+    pub running: bool,
+    pub auto_join: bool,
+    pub pending: bool,
+    last_checked: Option<Instant>,
+}
+
+impl QueueAssistant {
+    pub fn toggle(&mut self, id: &str) {
+        match self.targets.iter().position(|target| target == id) {
+            Some(index) => {
+                self.targets.remove(index);
+            }
+            None => self.targets.push(id.to_owned()),
+        }
+        if self.targets.is_empty() {
+            self.running = false;
+        }
+    }
+
+    pub fn is_target(&self, id: &str) -> bool {
+        self.targets.iter().any(|target| target == id)
+    }
+
+    pub fn start(&mut self) {
+        self.running = true;
+        // Перша перевірка - одразу
+        self.last_checked = None;
+    }
+
+    /// Чи настане час перевірки протягом `window` (батч підхоплює трохи раніше).
+    pub fn is_due_within(&self, window: Duration) -> bool {
+        self.running && !self.pending && self.last_checked.is_none_or(|at| at.elapsed() + window >= CHECK_INTERVAL)
+    }
+
+    pub fn started(&mut self) {
+        self.pending = true;
+        self.last_checked = Some(Instant::now());
+    }
+
+    pub fn last_checked(&self) -> Option<Instant> {
+        self.last_checked
+    }
+}
+
+/// Сервер з вільним місцем і без загальної черги; з кількох - найбільш заповнений.
+pub fn pick_free(servers: &[ServerItem]) -> Option<&ServerItem> {
+    servers
+        .iter()
+        .filter(|s| s.players < s.max_players && s.public_queue == 0)
+        .max_by_key(|s| s.players)
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+// --- ЗНАЧКИ "ТОП N% ЗА ОНЛАЙНОМ" ---
+//
+// Рахуються для кожної вкладки окремо, тобто в межах її набору фільтрів:
+// "топ 5% серед серверів UA" означає серед тих, що пройшли фільтри.
+// Порівнюємо з країною сервера і з усім списком, показуємо вужчий поріг.
+
+// Пороги значків, у відсотках; далі за 10% значок уже нічого не каже
+const TIERS: [u32; 3] = [1, 5, 10];
+// У меншій групі відсоток нічого не важить ("топ 10%" з п'яти серверів)
+const MIN_GROUP: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Badge {
+    pub top_percent: u32,
+    // None - серед усіх серверів вкладки
+    pub country: Option<String>,
+}
+
+/// (id, гравці, країна) усіх серверів вкладки -> значки тих, хто в топі.
+pub fn compute<'a>(servers: impl Iterator<Item = (&'a str, u32, &'a str)>) -> HashMap<String, Badge> {
+    let servers: Vec<(&str, u32, &str)> = servers.collect();
+    let mut by_country: HashMap<&str, Vec<u32>> = HashMap::new();
+    for &(_, players, country) in &servers {
+        by_country.entry(country).or_default().push(players);
+    }
+    let all: Vec<u32> = servers.iter().map(|&(_, players, _)| players).collect();
+
+    let mut badges = HashMap::new();
+    for &(id, players, country) in &servers {
+        if players == 0 {
+            continue;
+        }
+        let overall = tier(players, &all);
+        let local = by_country.get(country).and_then(|group| tier(players, group));
+        // Країна - конкретніше, тож вона перемагає за рівного порогу
+        let badge = match (local, overall) {
+            (Some(local), Some(overall)) if overall < local => Badge { top_percent: overall, country: None },
+            (Some(local), _) => Badge { top_percent: local, country: Some(country.to_owned()) },
+            (None, Some(overall)) => Badge { top_percent: overall, country: None },
+            (None, None) => continue,
+        };
+        badges.insert(id.to_owned(), badge);
+    }
+    badges
+}
+
+fn tier(players: u32, group: &[u32]) -> Option<u32> {
+    if group.len() < MIN_GROUP {
+        return None;
+    }
+    // Сервери з рівним онлайном ділять місце
+    let ahead = group.iter().filter(|&&other| other > players).count();
+    let top = ((ahead + 1) * 100).div_ceil(group.len()) as u32;
+    TIERS.into_iter().find(|&tier| top <= tier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn badges(servers: &[(String, u32, &'static str)]) -> HashMap<String, Badge> {
+        compute(servers.iter().map(|(id, players, country)| (id.as_str(), *players, *country)))
+    }
+
+    #[test]
+    fn overall_and_country_tiers() {
+        let mut servers: Vec<(String, u32, &str)> = (1..=100).map(|n| (format!("us{}", n), n, "US")).collect();
+        servers.extend((0..10).map(|n| (format!("ua{}", n), n, "UA")));
+        servers.extend((200..210).map(|n| (format!("de{}", n), n, "DE")));
+        let badges = badges(&servers);
+        // За рівного порогу перемагає країна
+        assert_eq!(badges["us100"], Badge { top_percent: 1, country: Some("US".into()) });
+        assert_eq!(badges["us96"], Badge { top_percent: 5, country: Some("US".into()) });
+        assert!(!badges.contains_key("us80"));
+        // У своїй країні лише топ 10%, серед усіх - топ 1%
+        assert_eq!(badges["de209"], Badge { top_percent: 1, country: None });
+        assert_eq!(badges["de205"], Badge { top_percent: 5, country: None });
+        // Серед усіх UA-сервер далеко, але в своїй країні - перший
+        assert_eq!(badges["ua9"], Badge { top_percent: 10, country: Some("UA".into()) });
+        assert!(!badges.contains_key("ua8"));
+        assert!(!badges.contains_key("ua0"));
+    }
+
+    #[test]
+    fn small_groups_and_ties() {
+        let few: Vec<(String, u32, &str)> = (1..10).map(|n| (n.to_string(), n * 10, "DE")).collect();
+        assert!(badges(&few).is_empty());
+        // Десять серверів з рівним онлайном ділять перше місце - топ 10%
+        let tied: Vec<(String, u32, &str)> = (0..10).map(|n| (n.to_string(), 50, "DE")).collect();
+        let badges = badges(&tied);
+        assert_eq!(badges.len(), 10);
+        assert!(badges.values().all(|badge| badge.top_percent == 10 && badge.country.as_deref() == Some("DE")));
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+
+use crate::provider::{PageRequest, ServerProvider};
+
+// --- ЗАПИС І ВІДТВОРЕННЯ ВІДПОВІДЕЙ API (для баг-репортів) ---
+//
+// Формат: JSON Lines, один запис на запит. Файл можна прикріпити до issue,
+// а потім відтворити через --replay <файл>.
+
+#[derive(Serialize, Deserialize)]
+struct RecordedPage {
+    request: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+fn request_key(request: &PageRequest) -> String {
+    match request {
+        PageRequest::First { min_players, max_players, extra_params } => {
+            let mut key = format!("first?min={}&max={}", min_players, max_players);
+            for (name, value) in extra_params {
+                key.push_str(&format!("&{}={}", name, value));
+            }
+            key
+        }
+        PageRequest::Next(url) => url.clone(),
+    }
+}
+
+pub struct RecordingProvider {
+    inner: Box<dyn ServerProvider>,
+    file: Mutex<File>,
+}
+
+impl RecordingProvider {
+    /// Обгортає провайдер записом. Якщо файл не відкривається - працюємо без запису.
+    pub fn wrap(inner: Box<dyn ServerProvider>, path: &str) -> Box<dyn ServerProvider> {
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => {
+                println!("[LOG] Recording API traffic to {}", path);
+                Box::new(Self { inner, file: Mutex::new(file) })
+            }
+            Err(e) => {
+                println!("[ERR] Failed to open recording file: {}", e);
+                inner
+            }
+        }
+    }
+}
+
+impl ServerProvider for RecordingProvider {
+    fn fetch_page(&self, request: &PageRequest) -> Result<String, String> {
+        let result = self.inner.fetch_page(request);
+        let record = RecordedPage {
+            request: request_key(request),
+            body: result.as_ref().ok().cloned(),
+            error: result.as_ref().err().cloned(),
+        };
+
+        if let Ok(line) = serde_json::to_string(&record) {
+            if let Ok(mut file) = self.file.lock() {
+                if let Err(e) = writeln!(file, "{}", line) {
+                    println!("[ERR] Failed to write recording: {}", e);
+                }
+            }
+        }
+        result
+    }
+}
+
+pub struct ReplayProvider {
+    pages: Mutex<Vec<Option<RecordedPage>>>,
+}
+
+impl ReplayProvider {
+    pub fn new(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut pages = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<RecordedPage>(line) {
+                Ok(page) => pages.push(Some(page)),
+                Err(e) => println!("[ERR] Skipping recording line {}: {}", number + 1, e),
+            }
+        }
+        println!("[LOG] Replaying {} recorded responses from {}", pages.len(), path);
+        Ok(Self { pages: Mutex::new(pages) })
+    }
+}
+
+impl ServerProvider for ReplayProvider {
+    // Кожен запис віддається один раз, у порядку запису: так повторне
+    // оновлення відтворює наступний знімок, як це було в оригінальній сесії.
+    fn fetch_page(&self, request: &PageRequest) -> Result<String, String> {
+        let key = request_key(request);
+        let mut pages = self.pages.lock().map_err(|e| e.to_string())?;
+        let slot = pages
+            .iter_mut()
+            .find(|slot| slot.as_ref().is_some_and(|p| p.request == key))
+            .ok_or_else(|| format!("no recorded response for {}", key))?;
+
+        match slot.take() {
+            Some(RecordedPage { body: Some(body), .. }) => Ok(body),
+            Some(RecordedPage { error: Some(error), .. }) => Err(error),
+            _ => Err(format!("empty recorded response for {}", key)),
+        }
+    }
+}
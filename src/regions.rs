@@ -0,0 +1,37 @@
+// --- РЕГІОНИ (масовий бан країн, тест пінгу) ---
+
+pub struct Region {
+    // Ключ перекладу назви
+    pub key: &'static str,
+    pub countries: &'static [&'static str],
+}
+
+pub const REGIONS: [Region; 5] = [
+    Region {
+        key: "region_europe",
+        countries: &[
+            "GB", "IE", "FR", "DE", "NL", "BE", "LU", "CH", "AT", "IT", "ES", "PT", "DK", "NO", "SE", "FI", "IS",
+            "EE", "LV", "LT", "PL", "CZ", "SK", "HU", "RO", "BG", "GR", "HR", "SI", "RS", "BA", "MK", "AL", "ME",
+            "MD", "UA", "BY", "RU",
+        ],
+    },
+    Region {
+        key: "region_north_america",
+        countries: &["US", "CA", "MX"],
+    },
+    Region {
+        key: "region_asia",
+        countries: &[
+            "CN", "HK", "TW", "JP", "KR", "MN", "KZ", "UZ", "KG", "TJ", "TM", "IN", "PK", "BD", "LK", "NP",
+            "TH", "VN", "MY", "SG", "ID", "PH", "KH", "LA", "MM",
+        ],
+    },
+    Region {
+        key: "region_south_america",
+        countries: &["BR", "AR", "CL", "CO", "PE", "VE", "UY", "PY", "BO", "EC"],
+    },
+    Region {
+        key: "region_oceania",
+        countries: &["AU", "NZ", "PG", "FJ"],
+    },
+];
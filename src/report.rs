@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::overlay::escape_html;
+use crate::sessions::{self, PlaySession};
+
+// --- ТИЖНЕВИЙ ПІДСУМОК ---
+
+const WEEK_SECS: u64 = 7 * 86_400;
+
+pub struct WeeklySummary {
+    pub from: String,
+    pub to: String,
+    pub sessions: usize,
+    pub total_minutes: u64,
+    // (сервер, хвилини), від найбільшого
+    pub servers: Vec<(String, u64)>,
+    // (карта, скільки разів заходили на ній), від найчастішої
+    pub maps: Vec<(String, usize)>,
+    // (день, хвилини) у хронологічному порядку
+    pub days: Vec<(String, u64)>,
+}
+
+impl WeeklySummary {
+    pub fn build(all: &[PlaySession], now: u64) -> Self {
+        let since = now.saturating_sub(WEEK_SECS);
+        let week: Vec<&PlaySession> = all.iter().filter(|s| s.started >= since).collect();
+
+        let mut servers: HashMap<&str, u64> = HashMap::new();
+        let mut maps: HashMap<&str, usize> = HashMap::new();
+        let mut days: BTreeMap<String, u64> = BTreeMap::new();
+        for session in &week {
+            *servers.entry(&session.server_name).or_default() += session.minutes();
+            if !session.map.is_empty() {
+                *maps.entry(&session.map).or_default() += 1;
+            }
+            *days.entry(sessions::date_label(session.started)).or_default() += session.minutes();
+        }
+
+        let mut servers: Vec<(String, u64)> = servers.into_iter().map(|(k, v)| (k.to_owned(), v)).collect();
+        servers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let mut maps: Vec<(String, usize)> = maps.into_iter().map(|(k, v)| (k.to_owned(), v)).collect();
+        maps.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Self {
+            from: sessions::date_label(since),
+            to: sessions::date_label(now),
+            sessions: week.len(),
+            total_minutes: week.iter().map(|s| s.minutes()).sum(),
+            servers,
+            maps,
+            days: days.into_iter().collect(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Squad weekly summary ({} – {})\n\n", self.from, self.to);
+        out.push_str(&format!(
+            "- Sessions: {}\n- Playtime: {}\n\n",
+            self.sessions,
+            hours(self.total_minutes)
+        ));
+        out.push_str("## Servers\n\n| Server | Playtime |\n|---|---|\n");
+        for (name, minutes) in &self.servers {
+            out.push_str(&format!("| {} | {} |\n", name.replace('|', "\\|"), hours(*minutes)));
+        }
+        out.push_str("\n## Maps\n\n| Map | Sessions |\n|---|---|\n");
+        for (map, count) in &self.maps {
+            out.push_str(&format!("| {} | {} |\n", map, count));
+        }
+        out.push_str("\n## Days\n\n| Day | Playtime |\n|---|---|\n");
+        for (day, minutes) in &self.days {
+            out.push_str(&format!("| {} | {} |\n", day, hours(*minutes)));
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let table = |title: &str, header: &str, rows: Vec<(String, String)>| {
+            let mut html = format!("<h2>{}</h2>\n<table>\n<tr><th>{}</th></tr>\n", title, header);
+            for (left, right) in rows {
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", escape_html(&left), escape_html(&right)));
+            }
+            html.push_str("</table>\n");
+            html
+        };
+
+        let mut body = format!(
+            "<h1>Squad weekly summary ({} – {})</h1>\n<p>Sessions: {}<br>Playtime: {}</p>\n",
+            self.from,
+            self.to,
+            self.sessions,
+            hours(self.total_minutes)
+        );
+        body.push_str(&table(
+            "Servers",
+            "Server</th><th>Playtime",
+            self.servers.iter().map(|(n, m)| (n.clone(), hours(*m))).collect(),
+        ));
+        body.push_str(&table(
+            "Maps",
+            "Map</th><th>Sessions",
+            self.maps.iter().map(|(n, c)| (n.clone(), c.to_string())).collect(),
+        ));
+        body.push_str(&table(
+            "Days",
+            "Day</th><th>Playtime",
+            self.days.iter().map(|(d, m)| (d.clone(), hours(*m))).collect(),
+        ));
+
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Squad weekly summary</title>\
+             <style>body{{font-family:sans-serif;}}td,th{{padding:2px 12px;text-align:left;}}</style>\
+             </head><body>\n{}</body></html>\n",
+            body
+        )
+    }
+}
+
+pub fn hours(minutes: u64) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn session(name: &str, map: &str, days_ago: u64, minutes: u64) -> PlaySession {
+        let started = NOW - days_ago * 86_400;
+        PlaySession { server_name: name.into(), map: map.into(), started, ended: started + minutes * 60, ..Default::default() }
+    }
+
+    #[test]
+    fn summary_of_last_seven_days() {
+        let all = [
+            session("A | EU", "Narva", 1, 90),
+            session("B", "Gorodok", 1, 30),
+            session("B", "Narva", 2, 75),
+            session("B", "", 3, 5),
+            session("Old", "Narva", 8, 600),
+        ];
+        let summary = WeeklySummary::build(&all, NOW);
+        assert_eq!((summary.from.as_str(), summary.to.as_str()), ("2023-11-07", "2023-11-14"));
+        assert_eq!((summary.sessions, summary.total_minutes), (4, 200));
+        assert_eq!(summary.servers, vec![("B".to_owned(), 110), ("A | EU".to_owned(), 90)]);
+        assert_eq!(summary.maps, vec![("Narva".to_owned(), 2), ("Gorodok".to_owned(), 1)]);
+        assert_eq!(summary.days.first(), Some(&("2023-11-11".to_owned(), 5)));
+
+        let markdown = summary.to_markdown();
+        assert!(markdown.contains("| A \\| EU | 1h 30m |"));
+        assert!(markdown.contains("- Playtime: 3h 20m"));
+        let html = WeeklySummary::build(&[session("<b>", "", 0, 1)], NOW).to_html();
+        assert!(html.contains("<td>&lt;b&gt;</td>"));
+    }
+
+    #[test]
+    fn hours_format() {
+        assert_eq!(hours(0), "0h 00m");
+        assert_eq!(hours(125), "2h 05m");
+    }
+}
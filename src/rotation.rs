@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// --- РОТАЦІЯ КАРТ ЗАКРІПЛЕНИХ СЕРВЕРІВ ---
+//
+// З кожного оновлення записуємо зміну карти. Коли в журналі послідовність
+// повторюється, вважаємо її ротацією сервера і прогнозуємо наступні карти.
+
+const MAX_CHANGES: usize = 200;
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct MapChange {
+    pub map: String,
+    // Секунди від UNIX epoch, коли карту вперше побачили
+    pub at: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct RotationLog {
+    changes: HashMap<String, Vec<MapChange>>,
+}
+
+impl RotationLog {
+    /// Повертає true, якщо карта змінилась (перший запис сервера - не зміна).
+    pub fn record(&mut self, id: &str, map: &str, now: u64) -> bool {
+        let changes = self.changes.entry(id.to_owned()).or_default();
+        if changes.last().is_some_and(|last| last.map == map) {
+            return false;
+        }
+        let changed = !changes.is_empty();
+        changes.push(MapChange { map: map.to_owned(), at: now });
+        if changes.len() > MAX_CHANGES {
+            changes.remove(0);
+        }
+        changed
+    }
+
+    /// Ротація, що закінчується поточною картою. Потрібен хоча б один повний
+    /// повтор циклу, інакше None.
+    pub fn rotation(&self, id: &str) -> Option<Vec<String>> {
+        let maps: Vec<&str> = self.changes.get(id)?.iter().map(|c| c.map.as_str()).collect();
+        (2..=maps.len() / 2)
+            .find(|&period| (0..maps.len() - period).all(|i| maps[i] == maps[i + period]))
+            .map(|period| maps[maps.len() - period..].iter().map(|m| m.to_string()).collect())
+    }
+
+    /// Останні побачені карти (для випадку, коли ротацію ще не видно).
+    pub fn recent(&self, id: &str, count: usize) -> Vec<String> {
+        let changes = match self.changes.get(id) {
+            Some(changes) => changes,
+            None => return Vec::new(),
+        };
+        changes.iter().skip(changes.len().saturating_sub(count)).map(|c| c.map.clone()).collect()
+    }
+
+    /// Середня тривалість карти в хвилинах. Перший запис - не зміна, а момент,
+    /// коли сервер почали відстежувати, тому його інтервал не рахуємо.
+    pub fn average_minutes(&self, id: &str) -> Option<u64> {
+        let changes = self.changes.get(id)?;
+        if changes.len() < 3 {
+            return None;
+        }
+        let intervals: Vec<u64> = changes[1..].windows(2).map(|w| w[1].at.saturating_sub(w[0].at) / 60).collect();
+        Some(intervals.iter().sum::<u64>() / intervals.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(maps: &[&str]) -> RotationLog {
+        let mut log = RotationLog::default();
+        for (index, map) in maps.iter().enumerate() {
+            log.record("1", map, index as u64 * 3600);
+        }
+        log
+    }
+
+    fn rotation(maps: &[&str]) -> Option<Vec<String>> {
+        log(maps).rotation("1")
+    }
+
+    #[test]
+    fn detects_repeated_period() {
+        assert_eq!(rotation(&["A", "B", "C", "A", "B", "C"]), Some(vec!["A".into(), "B".into(), "C".into()]));
+        assert_eq!(rotation(&["A", "B", "A", "B"]), Some(vec!["A".into(), "B".into()]));
+        // Закінчується поточною картою, навіть посеред циклу
+        assert_eq!(rotation(&["A", "B", "C", "A", "B", "C", "A"]), Some(vec!["B".into(), "C".into(), "A".into()]));
+    }
+
+    #[test]
+    fn needs_a_full_repeat() {
+        assert_eq!(rotation(&[]), None);
+        assert_eq!(rotation(&["A"]), None);
+        assert_eq!(rotation(&["A", "B", "C", "A", "B"]), None);
+        assert_eq!(rotation(&["A", "B", "C", "D", "E", "F"]), None);
+    }
+
+    #[test]
+    fn repeated_map_is_not_a_change() {
+        let mut log = RotationLog::default();
+        assert!(!log.record("1", "A", 0));
+        assert!(!log.record("1", "A", 60));
+        assert!(log.record("1", "B", 120));
+        assert_eq!(log.recent("1", 5), vec!["A", "B"]);
+        assert_eq!(log.recent("1", 1), vec!["B"]);
+        assert!(log.recent("2", 5).is_empty());
+    }
+
+    #[test]
+    fn average_skips_the_first_interval() {
+        let mut log = RotationLog::default();
+        log.record("1", "A", 0);
+        log.record("1", "B", 500 * 60);
+        assert_eq!(log.average_minutes("1"), None);
+        log.record("1", "C", 560 * 60);
+        log.record("1", "D", 640 * 60);
+        assert_eq!(log.average_minutes("1"), Some(70));
+    }
+}
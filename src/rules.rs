@@ -0,0 +1,157 @@
+// --- ПРАВИЛА БЛОКУВАННЯ (формат як у AdBlock) ---
+//
+// country:RU      - сховати сервери з країни
+// name~wagner     - сховати, якщо назва містить підрядок (без регістру;
+//                   також "W4GNER", "ВАГНЕР" - див. normalize.rs)
+// id:123456       - сховати конкретний сервер BattleMetrics
+// !id:999         - виняток: ніколи не ховати (перекриває всі інші бани)
+// # коментар
+
+use crate::normalize::Folded;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuleKind {
+    Country(String),
+    NameContains(String),
+    Id(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub kind: RuleKind,
+    pub exception: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+fn parse_line(line: &str) -> Result<Option<Rule>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    let (exception, body) = match line.strip_prefix('!') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, line),
+    };
+
+    let kind = if let Some(code) = body.strip_prefix("country:") {
+        RuleKind::Country(code.trim().to_uppercase())
+    } else if let Some(word) = body.strip_prefix("name~") {
+        RuleKind::NameContains(word.trim().to_lowercase())
+    } else if let Some(id) = body.strip_prefix("id:") {
+        RuleKind::Id(id.trim().to_string())
+    } else {
+        return Err(format!("unknown rule: {}", line));
+    };
+
+    let value = match &kind {
+        RuleKind::Country(v) | RuleKind::NameContains(v) | RuleKind::Id(v) => v,
+    };
+    if value.is_empty() {
+        return Err(format!("empty value: {}", line));
+    }
+
+    Ok(Some(Rule { kind, exception }))
+}
+
+impl Rule {
+    fn matches(&self, id: &str, name: &Folded, country: &str) -> bool {
+        match &self.kind {
+            RuleKind::Country(code) => code == country,
+            RuleKind::NameContains(word) => name.contains(word),
+            RuleKind::Id(rule_id) => rule_id == id,
+        }
+    }
+}
+
+impl RuleSet {
+    /// Парсить текст правил. Некоректні рядки пропускаються і повертаються як помилки.
+    pub fn parse(text: &str) -> (RuleSet, Vec<String>) {
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            match parse_line(line) {
+                Ok(Some(rule)) => rules.push(rule),
+                Ok(None) => {}
+                Err(e) => errors.push(format!("line {}: {}", number + 1, e)),
+            }
+        }
+        (RuleSet { rules }, errors)
+    }
+
+    pub fn is_exception(&self, id: &str, name: &str, country: &str) -> bool {
+        let name = Folded::new(name);
+        self.rules
+            .iter()
+            .any(|r| r.exception && r.matches(id, &name, country))
+    }
+
+    pub fn is_blocked(&self, id: &str, name: &str, country: &str) -> bool {
+        let name = Folded::new(name);
+        self.rules
+            .iter()
+            .any(|r| !r.exception && r.matches(id, &name, country))
+    }
+
+    /// Сервер підпадає під бан і не має винятку.
+    pub fn hides(&self, id: &str, name: &str, country: &str) -> bool {
+        self.is_blocked(id, name, country) && !self.is_exception(id, name, country)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = "# бани\ncountry:ru\nname~Wagner\nid:123\n\n!id:999\n!name~ua squad\n";
+
+    #[test]
+    fn parses_every_rule_kind() {
+        let (set, errors) = RuleSet::parse(RULES);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(
+            set.rules,
+            vec![
+                Rule { kind: RuleKind::Country("RU".into()), exception: false },
+                Rule { kind: RuleKind::NameContains("wagner".into()), exception: false },
+                Rule { kind: RuleKind::Id("123".into()), exception: false },
+                Rule { kind: RuleKind::Id("999".into()), exception: true },
+                Rule { kind: RuleKind::NameContains("ua squad".into()), exception: true },
+            ]
+        );
+    }
+
+    #[test]
+    fn bans_by_country_name_and_id() {
+        let (set, _) = RuleSet::parse(RULES);
+        assert!(set.is_blocked("1", "Some Server", "RU"));
+        assert!(set.is_blocked("1", "W4GNER group", "DE"));
+        assert!(set.is_blocked("123", "Some Server", "DE"));
+        assert!(!set.is_blocked("1", "Some Server", "DE"));
+    }
+
+    #[test]
+    fn exceptions_override_bans() {
+        let (set, _) = RuleSet::parse(RULES);
+        assert!(set.is_blocked("999", "Server", "RU"));
+        assert!(!set.hides("999", "Server", "RU"));
+        assert!(set.is_blocked("5", "[UA Squad] Wagner hunters", "RU"));
+        assert!(!set.hides("5", "[UA Squad] Wagner hunters", "RU"));
+        assert!(set.hides("123", "Server", "RU"));
+        assert!(!set.hides("999", "Server", "DE"));
+    }
+
+    #[test]
+    fn errors_report_line_numbers() {
+        let (set, errors) = RuleSet::parse("country:RU\n# ok\nmap:gorodok\ncountry:\n! id:  \n");
+        assert_eq!(set.rules.len(), 1);
+        assert_eq!(
+            errors,
+            vec!["line 3: unknown rule: map:gorodok", "line 4: empty value: country:", "line 5: empty value: ! id:"]
+        );
+    }
+}
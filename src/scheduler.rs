@@ -0,0 +1,216 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+// --- ПЛАНУВАЛЬНИК ОНОВЛЕНЬ ---
+//
+// Усі запити до API йдуть через нього, щоб кілька вкладок не стартували
+// одночасно і не впирались у ліміт запитів BattleMetrics.
+
+pub const STAGGER: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+pub struct RefreshScheduler {
+    // Індекси вкладок, що чекають на оновлення
+    queue: VecDeque<usize>,
+    last_started: Option<Instant>,
+}
+
+impl RefreshScheduler {
+    pub fn enqueue(&mut self, tab: usize) {
+        if !self.queue.contains(&tab) {
+            self.queue.push_back(tab);
+        }
+    }
+
+    pub fn is_queued(&self, tab: usize) -> bool {
+        self.queue.contains(&tab)
+    }
+
+    /// Позначає, що запит щойно пішов (у тому числі поза чергою).
+    pub fn started(&mut self) {
+        self.last_started = Some(Instant::now());
+    }
+
+    /// Скільки лишилось чекати до наступного дозволеного запиту.
+    pub fn wait_time(&self) -> Duration {
+        self.last_started
+            .map_or(Duration::ZERO, |at| STAGGER.saturating_sub(at.elapsed()))
+    }
+
+    /// Наступна вкладка з черги, якщо вже можна робити запит.
+    pub fn next_due(&mut self) -> Option<usize> {
+        if self.wait_time() > Duration::ZERO {
+            return None;
+        }
+        self.queue.pop_front()
+    }
+
+    /// Вкладку закрито - прибираємо її і зсуваємо індекси наступних.
+    pub fn remove_tab(&mut self, tab: usize) {
+        self.queue.retain(|&queued| queued != tab);
+        for queued in self.queue.iter_mut() {
+            if *queued > tab {
+                *queued -= 1;
+            }
+        }
+    }
+
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+// --- АДАПТИВНА ЧАСТОТА ОПИТУВАННЯ ---
+//
+// Закріплені сервери, де онлайн швидко змінюється (сідінг, кінець матчу),
+// опитуються частіше, стабільні - рідше. У кожного сервера свій час
+// наступного опитування; усі, кому вже час, ідуть одним запитом, а сам
+// запит - не частіше POLL_MIN і через загальний STAGGER.
+
+pub const POLL_MIN: Duration = Duration::from_secs(30);
+pub const POLL_MAX: Duration = Duration::from_secs(4 * 60);
+// Скільки останніх замірів онлайну враховуємо
+const SAMPLES: usize = 5;
+
+#[derive(Default)]
+struct PollState {
+    samples: VecDeque<(Instant, u32)>,
+    // None - ще не опитували, тобто вже час
+    next_poll: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct AdaptivePolls {
+    servers: HashMap<String, PollState>,
+}
+
+impl AdaptivePolls {
+    /// Сервери з `ids`, яких уже час опитати.
+    pub fn due(&self, ids: &[String], now: Instant) -> Vec<String> {
+        ids.iter()
+            .filter(|id| self.servers.get(*id).and_then(|state| state.next_poll).is_none_or(|at| at <= now))
+            .cloned()
+            .collect()
+    }
+
+    /// Скільки чекати, поки хтось із `ids` стане на черзі.
+    pub fn wait_time(&self, ids: &[String], now: Instant) -> Duration {
+        ids.iter()
+            .map(|id| {
+                self.servers
+                    .get(id)
+                    .and_then(|state| state.next_poll)
+                    .map_or(Duration::ZERO, |at| at.saturating_duration_since(now))
+            })
+            .min()
+            .unwrap_or(POLL_MAX)
+    }
+
+    /// Результат опитування; None - онлайн невідомий (офлайн, запит не вдався
+    /// або запис не розібрався).
+    pub fn record(&mut self, id: &str, players: Option<u32>, now: Instant) {
+        let state = self.servers.entry(id.to_owned()).or_default();
+        if let Some(players) = players {
+            state.samples.push_back((now, players));
+            if state.samples.len() > SAMPLES {
+                state.samples.pop_front();
+            }
+        }
+        let interval = interval_for(volatility(&state.samples));
+        state.next_poll = Some(now + interval);
+    }
+
+    pub fn interval(&self, id: &str) -> Duration {
+        interval_for(self.servers.get(id).map_or(0.0, |state| volatility(&state.samples)))
+    }
+
+    pub fn retain(&mut self, ids: &[String]) {
+        self.servers.retain(|id, _| ids.contains(id));
+    }
+}
+
+// Середня зміна онлайну, гравців за хвилину
+fn volatility(samples: &VecDeque<(Instant, u32)>) -> f32 {
+    let (first, last) = match (samples.front(), samples.back()) {
+        (Some(first), Some(last)) if samples.len() > 1 => (first, last),
+        _ => return 0.0,
+    };
+    let minutes = last.0.duration_since(first.0).as_secs_f32() / 60.0;
+    if minutes <= 0.0 {
+        return 0.0;
+    }
+    let changed: u32 = samples.iter().zip(samples.iter().skip(1)).map(|(a, b)| a.1.abs_diff(b.1)).sum();
+    changed as f32 / minutes
+}
+
+// 0 гравців/хв - POLL_MAX, 1 - удвічі частіше, від ~7 - POLL_MIN
+fn interval_for(volatility: f32) -> Duration {
+    POLL_MAX.div_f32(1.0 + volatility).clamp(POLL_MIN, POLL_MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_is_staggered_and_deduplicated() {
+        let mut scheduler = RefreshScheduler::default();
+        scheduler.enqueue(2);
+        scheduler.enqueue(0);
+        scheduler.enqueue(2);
+        assert_eq!(scheduler.pending(), 2);
+        assert_eq!(scheduler.wait_time(), Duration::ZERO);
+        assert_eq!(scheduler.next_due(), Some(2));
+        scheduler.started();
+        // Наступна вкладка чекає STAGGER після попереднього запиту
+        assert_eq!(scheduler.next_due(), None);
+        assert!(scheduler.wait_time() > Duration::ZERO && scheduler.wait_time() <= STAGGER);
+        assert!(scheduler.is_queued(0));
+    }
+
+    #[test]
+    fn closing_a_tab_shifts_later_indices() {
+        let mut scheduler = RefreshScheduler::default();
+        for tab in [0, 1, 3] {
+            scheduler.enqueue(tab);
+        }
+        scheduler.remove_tab(1);
+        assert_eq!(scheduler.queue, [0, 2]);
+    }
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn busy_servers_are_polled_sooner() {
+        let start = Instant::now();
+        let ids = vec!["calm".to_owned(), "seeding".to_owned()];
+        let mut polls = AdaptivePolls::default();
+        // Ще не опитані - одразу на черзі
+        assert_eq!(polls.due(&ids, start), ids);
+        for minute in 0..4 {
+            let at = start + secs(minute * 60);
+            polls.record("calm", Some(80), at);
+            polls.record("seeding", Some(10 + minute as u32 * 10), at);
+        }
+        assert_eq!(polls.interval("calm"), POLL_MAX);
+        assert_eq!(polls.interval("seeding"), POLL_MIN);
+        let last = start + secs(180);
+        assert!(polls.due(&ids, last).is_empty());
+        assert_eq!(polls.wait_time(&ids, last), POLL_MIN);
+        assert_eq!(polls.due(&ids, last + POLL_MIN), vec!["seeding".to_owned()]);
+    }
+
+    #[test]
+    fn unknown_players_keep_the_interval() {
+        let start = Instant::now();
+        let mut polls = AdaptivePolls::default();
+        polls.record("1", None, start);
+        assert_eq!(polls.interval("1"), POLL_MAX);
+        assert_eq!(polls.wait_time(&["1".to_owned()], start), POLL_MAX);
+        assert_eq!(polls.wait_time(&[], start), POLL_MAX);
+        polls.retain(&[]);
+        assert_eq!(polls.due(&["1".to_owned()], start).len(), 1);
+    }
+}
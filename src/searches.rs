@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::filters::FilterSet;
+
+// --- ЗБЕРЕЖЕНІ ПОШУКИ ТА ФОНОВИЙ МОНІТОРИНГ ---
+
+pub const MONITOR_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct SavedSearch {
+    pub name: String,
+    pub filters: FilterSet,
+    pub monitor: bool,
+}
+
+/// Стан моніторингу одного пошуку (не зберігається між запусками).
+#[derive(Default)]
+pub struct MonitorState {
+    pub last_checked: Option<Instant>,
+    pub pending: bool,
+    // None до першої перевірки: перший результат - це базовий набір, а не "нові"
+    known_ids: Option<HashSet<String>>,
+    pub new_count: usize,
+}
+
+impl MonitorState {
+    pub fn is_due(&self) -> bool {
+        !self.pending && self.last_checked.is_none_or(|at| at.elapsed() >= MONITOR_INTERVAL)
+    }
+
+    /// Приймає id серверів, що зараз відповідають пошуку. Повертає кількість нових.
+    pub fn update(&mut self, ids: Vec<String>) -> usize {
+        self.pending = false;
+        self.last_checked = Some(Instant::now());
+        match &mut self.known_ids {
+            Some(known) => {
+                let mut fresh = 0;
+                for id in ids {
+                    if known.insert(id) {
+                        fresh += 1;
+                    }
+                }
+                self.new_count += fresh;
+                fresh
+            }
+            None => {
+                self.known_ids = Some(ids.into_iter().collect());
+                0
+            }
+        }
+    }
+}
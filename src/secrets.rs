@@ -0,0 +1,107 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// --- ПАРОЛІ Й ТОКЕНИ В СХОВИЩІ ОС ---
+//
+// Секрети (пароль WebDAV, ключ S3, токен Gist) не пишуться в app.ron, його
+// резервні копії чи zip-бекап: вони лежать у сховищі облікових даних ОС.
+// Linux: Secret Service через secret-tool (libsecret), macOS: Keychain через
+// security, Windows: файл, зашифрований DPAPI поточного користувача
+// (ConvertFrom-SecureString у PowerShell), поруч із конфігом.
+// Секрет передається утилітам через stdin, а не в аргументах командного рядка.
+
+const SERVICE: &str = "squad-browser";
+
+pub fn load(account: &str) -> Option<String> {
+    let secret = if cfg!(target_os = "windows") {
+        let blob = std::fs::read_to_string(dpapi_path(account)?).ok()?;
+        run(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "$s = ConvertTo-SecureString ([Console]::In.ReadToEnd().Trim()); \
+                 [Console]::Out.Write([Net.NetworkCredential]::new('', $s).Password)",
+            ],
+            &blob,
+        )
+        .ok()?
+    } else if cfg!(target_os = "macos") {
+        run("security", &["find-generic-password", "-s", SERVICE, "-a", account, "-w"], "").ok()?
+    } else {
+        run("secret-tool", &["lookup", "service", SERVICE, "account", account], "").ok()?
+    };
+    // security і secret-tool додають перенос рядка
+    let secret = secret.trim_end_matches(['\r', '\n']).to_owned();
+    (!secret.is_empty()).then_some(secret)
+}
+
+pub fn store(account: &str, secret: &str) -> Result<(), String> {
+    if secret.is_empty() {
+        return delete(account);
+    }
+    if cfg!(target_os = "windows") {
+        let blob = run(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "ConvertTo-SecureString ([Console]::In.ReadToEnd()) -AsPlainText -Force | ConvertFrom-SecureString",
+            ],
+            secret,
+        )?;
+        let path = dpapi_path(account).ok_or_else(|| "no config directory".to_owned())?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, blob.trim()).map_err(|e| e.to_string())
+    } else if cfg!(target_os = "macos") {
+        // security -i читає команди зі stdin, тож пароль не видно в списку процесів
+        let quoted = secret.replace('\\', "\\\\").replace('"', "\\\"");
+        let command = format!("add-generic-password -U -s {} -a \"{}\" -w \"{}\"\n", SERVICE, account, quoted);
+        run("security", &["-i"], &command).map(|_| ())
+    } else {
+        let label = format!("Squad Browser ({})", account);
+        run("secret-tool", &["store", "--label", &label, "service", SERVICE, "account", account], secret).map(|_| ())
+    }
+}
+
+pub fn delete(account: &str) -> Result<(), String> {
+    if cfg!(target_os = "windows") {
+        match dpapi_path(account) {
+            Some(path) if path.exists() => std::fs::remove_file(path).map_err(|e| e.to_string()),
+            _ => Ok(()),
+        }
+    } else if cfg!(target_os = "macos") {
+        // Запису могло й не бути
+        let _ = run("security", &["delete-generic-password", "-s", SERVICE, "-a", account], "");
+        Ok(())
+    } else {
+        let _ = run("secret-tool", &["clear", "service", SERVICE, "account", account], "");
+        Ok(())
+    }
+}
+
+fn dpapi_path(account: &str) -> Option<PathBuf> {
+    eframe::storage_dir(crate::APP_NAME).map(|dir| dir.join(format!("{}.dpapi", account)))
+}
+
+fn run(program: &str, args: &[&str], input: &str) -> Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{}: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).map_err(|e| format!("{}: {}", program, e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("{}: {}", program, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(format!("{} exited with {}", program, output.status))
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+// --- СИГНАЛИ СПІЛЬНОТ "МИ ЗАРАЗ СІДУЄМО СЕРВЕР X" ---
+//
+// Координаційний сервер спільнот віддає JSON зі списком серверів, які саме
+// зараз заповнюють. Такий сервер отримує значок у списку і, за бажанням,
+// сповіщення - щоб гравці приєднувались до сідування, поки воно йде.
+//
+// Контракт: [{"server_id": "1234567", "community": "...", "message": "...",
+// "started": 1700000000, "until": 1700003600}] або {"seeding": [...]}.
+// Обов'язковий лише server_id (ID BattleMetrics, підходить і "id");
+// started/until - секунди від UNIX epoch.
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// Сигнал без "until" вважаємо застарілим через стільки після "started"
+const MAX_AGE_SECS: u64 = 3 * 60 * 60;
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct SeedingSettings {
+    pub url: String,
+    pub alert: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SeedCall {
+    pub server_id: String,
+    pub community: String,
+    pub message: String,
+    pub started: u64,
+}
+
+impl SeedCall {
+    /// Один сигнал - одне сповіщення, навіть якщо він тримається в кількох відповідях.
+    pub fn key(&self) -> String {
+        format!("{}|{}|{}", self.server_id, self.community, self.started)
+    }
+}
+
+pub fn fetch(url: &str, now: u64) -> Result<Vec<SeedCall>, String> {
+    let body = reqwest::blocking::get(url)
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.text())
+        .map_err(|e| e.to_string())?;
+    parse(&body, now)
+}
+
+pub fn parse(body: &str, now: u64) -> Result<Vec<SeedCall>, String> {
+    let json: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    let list = match &json {
+        Value::Array(list) => list,
+        other => other
+            .get("seeding")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "expected an array or {\"seeding\": [...]}".to_owned())?,
+    };
+    let text = |item: &Value, key: &str| item.get(key).and_then(Value::as_str).unwrap_or_default().trim().to_owned();
+    let mut calls = Vec::new();
+    for item in list {
+        // ID буває і рядком, і числом
+        let server_id = match item.get("server_id").or_else(|| item.get("id")) {
+            Some(Value::String(id)) => id.trim().to_owned(),
+            Some(Value::Number(id)) => id.to_string(),
+            _ => continue,
+        };
+        if server_id.is_empty() {
+            continue;
+        }
+        let started = item.get("started").and_then(Value::as_u64).unwrap_or(0);
+        let until = item.get("until").and_then(Value::as_u64);
+        let expired = match until {
+            Some(until) => until <= now,
+            None => started != 0 && now.saturating_sub(started) > MAX_AGE_SECS,
+        };
+        if expired {
+            continue;
+        }
+        calls.push(SeedCall { server_id, community: text(item, "community"), message: text(item, "message"), started });
+    }
+    Ok(calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn parses_both_shapes_and_id_types() {
+        let list = r#"[{"server_id": " 123 ", "community": " UA Squad ", "message": "Join!", "started": 1699999000}, {"id": 456}]"#;
+        let calls = parse(list, NOW).unwrap();
+        assert_eq!(
+            calls[0],
+            SeedCall { server_id: "123".into(), community: "UA Squad".into(), message: "Join!".into(), started: 1_699_999_000 }
+        );
+        assert_eq!(calls[1].server_id, "456");
+        assert_eq!(calls[0].key(), "123|UA Squad|1699999000");
+
+        let wrapped = r#"{"seeding": [{"server_id": "789"}]}"#;
+        assert_eq!(parse(wrapped, NOW).unwrap().len(), 1);
+        assert!(parse(r#"{"servers": []}"#, NOW).is_err());
+        assert!(parse("oops", NOW).is_err());
+    }
+
+    #[test]
+    fn skips_expired_and_idless_calls() {
+        let body = format!(
+            r#"[{{"server_id": "1", "until": {ended}}}, {{"server_id": "2", "until": {later}}},
+                {{"server_id": "3", "started": {old}}}, {{"server_id": "4", "started": {recent}}},
+                {{"server_id": ""}}, {{"community": "no id"}}]"#,
+            ended = NOW,
+            later = NOW + 60,
+            old = NOW - MAX_AGE_SECS - 1,
+            recent = NOW - MAX_AGE_SECS,
+        );
+        let ids: Vec<String> = parse(&body, NOW).unwrap().into_iter().map(|call| call.server_id).collect();
+        assert_eq!(ids, vec!["2", "4"]);
+    }
+}
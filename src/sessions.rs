@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// --- ІГРОВІ СЕСІЇ (облік часу гри) ---
+//
+// Сесія починається з натискання "Приєднатись" і закінчується, коли процес
+// гри зникає. Якщо гра так і не запустилась - сесію відкидаємо.
+
+const GAME_PROCESS: &str = "SquadGame";
+const CHECK_EVERY: Duration = Duration::from_secs(15);
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct PlaySession {
+    pub server_id: String,
+    pub server_name: String,
+    // Карта на момент приєднання
+    pub map: String,
+    // Секунди від UNIX epoch
+    pub started: u64,
+    pub ended: u64,
+}
+
+impl PlaySession {
+    pub fn minutes(&self) -> u64 {
+        self.ended.saturating_sub(self.started) / 60
+    }
+}
+
+struct ActiveSession {
+    session: PlaySession,
+    joined_at: Instant,
+    game_seen: bool,
+}
+
+#[derive(Default)]
+pub struct SessionTracker {
+    active: Option<ActiveSession>,
+    last_check: Option<Instant>,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+impl SessionTracker {
+    pub fn start(&mut self, server_id: &str, server_name: &str, map: &str) {
+        println!("[LOG] Session started on {}", server_name);
+        self.active = Some(ActiveSession {
+            session: PlaySession {
+                server_id: server_id.to_owned(),
+                server_name: server_name.to_owned(),
+                map: map.to_owned(),
+                started: now_secs(),
+                ended: 0,
+            },
+            joined_at: Instant::now(),
+            game_seen: false,
+        });
+        self.last_check = None;
+    }
+
+    pub fn active_server(&self) -> Option<&str> {
+        self.active.as_ref().map(|a| a.session.server_name.as_str())
+    }
+
+    /// Викликається щокадру; сам обмежує частоту перевірок процесу.
+    /// Повертає завершену сесію, коли гру закрили.
+    pub fn poll(&mut self) -> Option<PlaySession> {
+        let active = self.active.as_mut()?;
+        if self.last_check.is_some_and(|at| at.elapsed() < CHECK_EVERY) {
+            return None;
+        }
+        self.last_check = Some(Instant::now());
+
+        let running = game_running();
+        if running {
+            active.game_seen = true;
+            return None;
+        }
+        if !active.game_seen {
+            if active.joined_at.elapsed() > LAUNCH_TIMEOUT {
+                println!("[LOG] Game did not start, session dropped");
+                self.active = None;
+            }
+            return None;
+        }
+
+        let mut session = self.active.take()?.session;
+        session.ended = now_secs();
+        println!("[LOG] Session ended: {} min on {}", session.minutes(), session.server_name);
+        Some(session)
+    }
+
+    pub fn check_interval(&self) -> Option<Duration> {
+        self.active.as_ref().map(|_| CHECK_EVERY)
+    }
+}
+
+#[cfg(windows)]
+fn game_running() -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    Command::new("tasklist")
+        .args(["/FI", &format!("IMAGENAME eq {}*", GAME_PROCESS), "/NH"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).contains(GAME_PROCESS))
+        .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+fn game_running() -> bool {
+    Command::new("pgrep")
+        .arg(GAME_PROCESS)
+        .output()
+        .map(|out| out.status.success())
+        .unwrap_or(false)
+}
+
+/// Дата (UTC) у форматі РРРР-ММ-ДД - без окремої бібліотеки для дат.
+pub fn date_label(secs: u64) -> String {
+    // Алгоритм civil_from_days (Howard Hinnant)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Хвилини гри по днях і серверах: день -> (назва сервера -> хвилини).
+pub fn playtime_by_day(sessions: &[PlaySession]) -> BTreeMap<String, BTreeMap<String, u64>> {
+    let mut days: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+    for session in sessions {
+        *days
+            .entry(date_label(session.started))
+            .or_default()
+            .entry(session.server_name.clone())
+            .or_default() += session.minutes();
+    }
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(name: &str, started: u64, minutes: u64) -> PlaySession {
+        PlaySession { server_name: name.into(), started, ended: started + minutes * 60, ..Default::default() }
+    }
+
+    #[test]
+    fn date_labels() {
+        assert_eq!(date_label(0), "1970-01-01");
+        assert_eq!(date_label(951_782_400), "2000-02-29");
+        assert_eq!(date_label(1_700_000_000), "2023-11-14");
+        assert_eq!(date_label(1_735_689_599), "2024-12-31");
+    }
+
+    #[test]
+    fn playtime_grouped_by_day_and_server() {
+        let day = 1_700_000_000 - 1_700_000_000 % 86_400;
+        let sessions = [
+            session("A", day + 60, 90),
+            session("A", day + 7_200, 30),
+            session("B", day + 86_400, 45),
+            // Незавершена сесія (ended = 0) не дає від'ємного часу
+            PlaySession { server_name: "B".into(), started: day, ended: 0, ..Default::default() },
+        ];
+        let days = playtime_by_day(&sessions);
+        assert_eq!(days["2023-11-14"]["A"], 120);
+        assert_eq!(days["2023-11-14"]["B"], 0);
+        assert_eq!(days["2023-11-15"]["B"], 45);
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+// --- КОМПАКТНИЙ НАПІВПРОЗОРИЙ РЕЖИМ (поверх гри) ---
+//
+// Вікно без рамки, завжди зверху, з прозорим тлом. Для click-through вікно
+// перестає приймати мишу, тому вихід з режиму - клавіша F9.
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct OverlaySkin {
+    pub enabled: bool,
+    pub opacity: f32,
+    pub click_through: bool,
+}
+
+impl Default for OverlaySkin {
+    fn default() -> Self {
+        Self { enabled: false, opacity: 0.6, click_through: false }
+    }
+}
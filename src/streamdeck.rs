@@ -0,0 +1,347 @@
+use eframe::egui;
+use ring::digest;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// --- API ДЛЯ ПЛАГІНА STREAM DECK (локальний WebSocket) ---
+//
+// ws://127.0.0.1:47816 - події ("favorite_slot_free", "scan_done") і команди
+// ("join_favorite"). Опис повідомлень у JSON віддається з тієї ж адреси:
+// GET http://127.0.0.1:47816/schema або командою {"command": "schema"}.
+//
+// WebSocket тут мінімальний (RFC 6455): лише текстові кадри, без розширень.
+// Сторінки з чужих сайтів не пускаємо: браузер завжди шле Origin, а плагін
+// Stream Deck - ні, або шле локальний. "null" теж відкидаємо: його шле будь-яка
+// сторінка з пісочниці (iframe sandbox, data: URL).
+
+const DEFAULT_PORT: u16 = 47816;
+const PORT_ENV: &str = "SQUAD_DECK_PORT";
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+// Команди - крихітні JSON; більші кадри вважаємо помилкою
+const MAX_FRAME: u64 = 64 * 1024;
+
+const OP_TEXT: u8 = 0x1;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+pub fn port() -> u16 {
+    std::env::var(PORT_ENV).ok().and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT)
+}
+
+/// Машиночитний опис API: команди, відповіді, події.
+pub fn schema() -> Value {
+    let slot = json!({ "type": "integer", "minimum": 1, "description": "1-based position in the favorites list" });
+    json!({
+        "name": "squad-browser",
+        "version": env!("CARGO_PKG_VERSION"),
+        "endpoint": format!("ws://127.0.0.1:{}", port()),
+        "commands": {
+            "join_favorite": {
+                "description": "Launch Squad and join the favorite in the given slot",
+                "params": { "type": "object", "properties": { "command": { "const": "join_favorite" }, "slot": slot }, "required": ["command", "slot"] }
+            },
+            "refresh": {
+                "description": "Rescan the active tab; a scan_done event follows",
+                "params": { "type": "object", "properties": { "command": { "const": "refresh" } }, "required": ["command"] }
+            },
+            "state": {
+                "description": "Current favorites, answered with a state reply",
+                "params": { "type": "object", "properties": { "command": { "const": "state" } }, "required": ["command"] }
+            },
+            "schema": {
+                "description": "This document",
+                "params": { "type": "object", "properties": { "command": { "const": "schema" } }, "required": ["command"] }
+            }
+        },
+        "replies": {
+            "ok": { "type": "object", "properties": { "reply": { "const": "ok" }, "command": { "type": "string" } } },
+            "error": { "type": "object", "properties": { "reply": { "const": "error" }, "message": { "type": "string" } } },
+            "state": {
+                "type": "object",
+                "properties": {
+                    "reply": { "const": "state" },
+                    "favorites": { "type": "array", "items": { "$ref": "#/definitions/favorite" } }
+                }
+            }
+        },
+        "events": {
+            "favorite_slot_free": {
+                "description": "A favorite went from full (or queued) to having a free player slot",
+                "message": {
+                    "type": "object",
+                    "properties": { "event": { "const": "favorite_slot_free" }, "favorite": { "$ref": "#/definitions/favorite" } }
+                }
+            },
+            "scan_done": {
+                "description": "A tab finished loading its server list",
+                "message": {
+                    "type": "object",
+                    "properties": {
+                        "event": { "const": "scan_done" },
+                        "tab": { "type": "string" },
+                        "servers": { "type": "integer" },
+                        "error": { "type": ["string", "null"] }
+                    }
+                }
+            }
+        },
+        "definitions": {
+            "favorite": {
+                "type": "object",
+                "properties": {
+                    "slot": slot,
+                    "id": { "type": "string" },
+                    "name": { "type": "string" },
+                    "map": { "type": "string" },
+                    "players": { "type": "integer" },
+                    "max_players": { "type": "integer" },
+                    "queue": { "type": "integer" },
+                    "free": { "type": "boolean" }
+                }
+            }
+        }
+    })
+}
+
+/// Команда від плагіна; відповідь іде лише цьому клієнту.
+pub struct Request {
+    pub message: Value,
+    reply: Sender<String>,
+}
+
+impl Request {
+    pub fn reply(self, message: Value) {
+        let _ = self.reply.send(message.to_string());
+    }
+}
+
+pub struct DeckServer {
+    rx: Receiver<Request>,
+    clients: Arc<Mutex<Vec<Sender<String>>>>,
+}
+
+impl DeckServer {
+    pub fn start(ctx: egui::Context) -> Option<Self> {
+        let listener = match TcpListener::bind(("127.0.0.1", port())) {
+            Ok(listener) => listener,
+            Err(e) => {
+                println!("[ERR] Stream Deck endpoint unavailable on port {}: {}", port(), e);
+                return None;
+            }
+        };
+        println!("[LOG] Stream Deck endpoint on ws://127.0.0.1:{}", port());
+        let (tx, rx) = channel();
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let registry = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (tx, ctx, registry) = (tx.clone(), ctx.clone(), registry.clone());
+                thread::spawn(move || {
+                    if let Err(e) = serve(stream, &tx, &ctx, &registry) {
+                        println!("[ERR] Stream Deck connection closed: {}", e);
+                    }
+                });
+            }
+        });
+        Some(Self { rx, clients })
+    }
+
+    pub fn try_recv(&self) -> Option<Request> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Подія всім підключеним плагінам; відключених прибираємо.
+    pub fn broadcast(&self, event: Value) {
+        let text = event.to_string();
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain(|client| client.send(text.clone()).is_ok());
+        }
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    tx: &Sender<Request>,
+    ctx: &egui::Context,
+    registry: &Mutex<Vec<Sender<String>>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut key = None;
+    let mut origin = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "sec-websocket-key" => key = Some(value.trim().to_owned()),
+                "origin" => origin = Some(value.trim().to_owned()),
+                _ => {}
+            }
+        }
+    }
+
+    let mut out = stream;
+    if !origin.as_deref().is_none_or(is_local_origin) {
+        return write!(out, "HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    }
+    let key = match key {
+        Some(key) => key,
+        None => {
+            // Звичайний HTTP-запит: віддаємо схему
+            let (status, body) = if request_line.starts_with("GET /schema ") {
+                ("200 OK", serde_json::to_string_pretty(&schema()).unwrap_or_default())
+            } else {
+                ("404 Not Found", String::new())
+            };
+            return write!(
+                out,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+        }
+    };
+    let accept = base64(digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, format!("{}{}", key, WS_GUID).as_bytes()).as_ref());
+    write!(
+        out,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept
+    )?;
+
+    // Окремий потік пише відповіді й події, цей - читає команди
+    let (client_tx, client_rx) = channel::<String>();
+    if let Ok(mut clients) = registry.lock() {
+        clients.push(client_tx.clone());
+    }
+    let mut writer = out.try_clone()?;
+    thread::spawn(move || {
+        for text in client_rx {
+            if write_frame(&mut writer, OP_TEXT, text.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        let (opcode, payload) = match read_frame(&mut reader) {
+            Ok(frame) => frame,
+            // Плагін просто закрив з'єднання
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        match opcode {
+            OP_TEXT => {
+                let message = match serde_json::from_slice::<Value>(&payload) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        let _ = client_tx.send(json!({ "reply": "error", "message": e.to_string() }).to_string());
+                        continue;
+                    }
+                };
+                if tx.send(Request { message, reply: client_tx.clone() }).is_err() {
+                    return Ok(());
+                }
+                ctx.request_repaint();
+            }
+            OP_PING => write_frame(&mut out, OP_PONG, &payload)?,
+            OP_CLOSE => return write_frame(&mut out, OP_CLOSE, &[]),
+            _ => {}
+        }
+    }
+}
+
+fn is_local_origin(origin: &str) -> bool {
+    origin.starts_with("file://")
+        || ["http://localhost", "http://127.0.0.1"].iter().any(|local| {
+            origin.strip_prefix(local).is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+        })
+}
+
+// Кадри клієнта завжди замасковані, сервера - ні
+fn read_frame(reader: &mut impl Read) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head)?;
+    let opcode = head[0] & 0x0F;
+    let masked = head[1] & 0x80 != 0;
+    let mut len = (head[1] & 0x7F) as u64;
+    if len == 126 {
+        let mut ext = [0u8; 2];
+        reader.read_exact(&mut ext)?;
+        len = u16::from_be_bytes(ext) as u64;
+    } else if len == 127 {
+        let mut ext = [0u8; 8];
+        reader.read_exact(&mut ext)?;
+        len = u64::from_be_bytes(ext);
+    }
+    if len > MAX_FRAME {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "frame too large"));
+    }
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    for (index, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[index % 4];
+    }
+    Ok((opcode, payload))
+}
+
+fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> std::io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_local_origins_are_accepted() {
+        for origin in ["http://localhost", "http://127.0.0.1:8080", "file:///C:/plugin/index.html"] {
+            assert!(is_local_origin(origin), "{}", origin);
+        }
+        for origin in ["null", "https://example.com", "http://localhost.example.com", "http://127.0.0.1.nip.io"] {
+            assert!(!is_local_origin(origin), "{}", origin);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use ring::{digest, hmac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::events::PlannedEvent;
+use crate::expr::SmartList;
+use crate::filters::FilterSet;
+use crate::notifications::QuietHours;
+use crate::searches::SavedSearch;
+use crate::{Language, ServerItem};
+
+// --- СИНХРОНІЗАЦІЯ ПРОФІЛЮ ЧЕРЕЗ СХОВИЩЕ КОРИСТУВАЧА ---
+//
+// Профіль (фільтри, обране, пошуки, правила) лежить одним файлом на WebDAV,
+// в S3-сумісному бакеті або в приватному Gist. Шляхи до файлів, історія та
+// налаштування вікна лишаються на кожному пристрої свої.
+//
+// Правило: змінилась лише одна сторона - вона і перемагає. Змінились обидві -
+// або пізніша зміна (last-write-wins), або питаємо користувача.
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const FILE_NAME: &str = "squad_profile.ron";
+// Обліковий запис секрету в сховищі ОС
+pub const SECRET_ACCOUNT: &str = "sync";
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+pub enum Backend {
+    #[default]
+    WebDav,
+    S3,
+    Gist,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    pub backend: Backend,
+    // WebDAV/S3: повний URL файлу; Gist: ID гіста
+    pub url: String,
+    // WebDAV: логін; S3: access key
+    pub username: String,
+    // WebDAV: пароль; S3: secret key; Gist: токен. Живе в сховищі ОС (secrets.rs);
+    // зі старих конфігів ще читається, щоб перенести його туди, але не пишеться
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub region: String,
+    pub device: String,
+    pub ask_on_conflict: bool,
+    // Стан останньої синхронізації: мітка віддаленого профілю і відбиток локального
+    pub last_remote: u64,
+    pub last_hash: u64,
+    // Коли локальний профіль востаннє змінився після синхронізації (0 - не змінювався)
+    pub local_changed_at: u64,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: Backend::WebDav,
+            url: String::new(),
+            username: String::new(),
+            secret: String::new(),
+            region: "us-east-1".to_owned(),
+            device: String::new(),
+            ask_on_conflict: true,
+            last_remote: 0,
+            last_hash: 0,
+            local_changed_at: 0,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Profile {
+    // Секунди від UNIX epoch на пристрої, що записав профіль
+    pub modified: u64,
+    pub device: String,
+    pub filters: FilterSet,
+    pub language: Language,
+    pub block_rules: String,
+    pub pinned: Vec<ServerItem>,
+    pub seed_threshold: u32,
+    pub saved_searches: Vec<SavedSearch>,
+    pub smart_lists: Vec<SmartList>,
+    pub events: Vec<PlannedEvent>,
+    pub quiet_hours: QuietHours,
+}
+
+impl Profile {
+    /// Відбиток вмісту без мітки часу і пристрою: чи змінився профіль з минулої синхронізації.
+    pub fn hash(&self) -> u64 {
+        let content = Profile { modified: 0, device: String::new(), ..self.clone() };
+        // FNV-1a: стабільний між версіями Rust, на відміну від DefaultHasher
+        ron::ser::to_string(&content)
+            .unwrap_or_default()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+    }
+}
+
+/// Що робити після того, як отримали віддалений профіль.
+pub enum Decision {
+    UpToDate,
+    Push,
+    Pull(Box<Profile>),
+    Conflict(Box<Profile>),
+}
+
+pub fn decide(settings: &SyncSettings, local_hash: u64, remote: Option<Profile>) -> Decision {
+    let remote = match remote {
+        Some(remote) => remote,
+        None => return Decision::Push,
+    };
+    let remote_changed = remote.modified != settings.last_remote;
+    let local_changed = local_hash != settings.last_hash;
+    match (remote_changed, local_changed) {
+        (false, false) => Decision::UpToDate,
+        (false, true) => Decision::Push,
+        (true, false) => Decision::Pull(Box::new(remote)),
+        // Той самий вміст з іншого пристрою - конфлікту немає
+        (true, true) if remote.hash() == local_hash => Decision::Pull(Box::new(remote)),
+        (true, true) if settings.ask_on_conflict => Decision::Conflict(Box::new(remote)),
+        (true, true) if remote.modified > settings.local_changed_at => Decision::Pull(Box::new(remote)),
+        (true, true) => Decision::Push,
+    }
+}
+
+fn client() -> Result<reqwest::blocking::Client, String> {
+    reqwest::blocking::Client::builder()
+        .timeout(TIMEOUT)
+        .user_agent("squad-browser")
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Віддалений профіль; None - його ще ніхто не записав.
+pub fn download(settings: &SyncSettings) -> Result<Option<Profile>, String> {
+    let text = match settings.backend {
+        Backend::WebDav => {
+            let request = client()?.get(&settings.url).basic_auth(&settings.username, Some(&settings.secret));
+            plain_get(request)?
+        }
+        Backend::S3 => plain_get(s3_request(settings, reqwest::Method::GET, Vec::new())?)?,
+        Backend::Gist => gist_get(settings)?,
+    };
+    match text {
+        Some(text) => ron::from_str(&text).map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+pub fn upload(settings: &SyncSettings, profile: &Profile) -> Result<(), String> {
+    let body = ron::ser::to_string_pretty(profile, ron::ser::PrettyConfig::default()).map_err(|e| e.to_string())?;
+    let request = match settings.backend {
+        Backend::WebDav => client()?.put(&settings.url).basic_auth(&settings.username, Some(&settings.secret)).body(body),
+        Backend::S3 => s3_request(settings, reqwest::Method::PUT, body.into_bytes())?,
+        Backend::Gist => gist_request(settings, reqwest::Method::PATCH)?.json(&json!({ "files": { FILE_NAME: { "content": body } } })),
+    };
+    request.send().and_then(|resp| resp.error_for_status()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn plain_get(request: reqwest::blocking::RequestBuilder) -> Result<Option<String>, String> {
+    let resp = request.send().map_err(|e| e.to_string())?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    resp.error_for_status().and_then(|resp| resp.text()).map(Some).map_err(|e| e.to_string())
+}
+
+// --- GIST ---
+
+fn gist_request(settings: &SyncSettings, method: reqwest::Method) -> Result<reqwest::blocking::RequestBuilder, String> {
+    let url = format!("https://api.github.com/gists/{}", settings.url.trim());
+    Ok(client()?
+        .request(method, url)
+        .bearer_auth(&settings.secret)
+        .header("Accept", "application/vnd.github+json"))
+}
+
+fn gist_get(settings: &SyncSettings) -> Result<Option<String>, String> {
+    let json: Value = gist_request(settings, reqwest::Method::GET)?
+        .send()
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.json())
+        .map_err(|e| e.to_string())?;
+    let file = match json.get("files").and_then(|files| files.get(FILE_NAME)) {
+        Some(file) => file,
+        None => return Ok(None),
+    };
+    // Великі файли API віддає обрізаними - тоді читаємо raw_url
+    if file.get("truncated").and_then(Value::as_bool) == Some(true) {
+        if let Some(raw_url) = file.get("raw_url").and_then(Value::as_str) {
+            return plain_get(client()?.get(raw_url).bearer_auth(&settings.secret));
+        }
+    }
+    Ok(file.get("content").and_then(Value::as_str).map(str::to_owned))
+}
+
+// --- S3 (підпис AWS Signature V4) ---
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes()).as_ref().to_vec()
+}
+
+// URL у стилі шляху: https://s3.<region>.amazonaws.com/<bucket>/<file> або адреса MinIO тощо
+fn s3_request(
+    settings: &SyncSettings,
+    method: reqwest::Method,
+    body: Vec<u8>,
+) -> Result<reqwest::blocking::RequestBuilder, String> {
+    let url = reqwest::Url::parse(settings.url.trim()).map_err(|e| e.to_string())?;
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_owned(),
+        (None, _) => return Err("URL has no host".to_owned()),
+    };
+    let now = chrono::Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = sha256_hex(&body);
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        method,
+        url.path(),
+        url.query().unwrap_or(""),
+        host,
+        payload_hash,
+        amz_date,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, settings.region.trim());
+    let string_to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", amz_date, scope, sha256_hex(canonical_request.as_bytes()));
+    let mut key = hmac_sha256(format!("AWS4{}", settings.secret).as_bytes(), &date);
+    for part in [settings.region.trim(), "s3", "aws4_request"] {
+        key = hmac_sha256(&key, part);
+    }
+    let signature = hex(&hmac_sha256(&key, &string_to_sign));
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        settings.username.trim(),
+        scope,
+        signed_headers,
+        signature
+    );
+
+    Ok(client()?
+        .request(method, url)
+        .header("x-amz-date", amz_date)
+        .header("x-amz-content-sha256", payload_hash)
+        .header("Authorization", authorization)
+        .body(body))
+}
+
+/// Результати фонових запитів для головного потоку.
+pub enum SyncResult {
+    Fetched(Result<Option<Profile>, String>),
+    // Мітка і відбиток профілю, який щойно записали
+    Uploaded(Result<(u64, u64), String>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(modified: u64, rules: &str) -> Profile {
+        Profile { modified, device: "laptop".into(), block_rules: rules.into(), ..Default::default() }
+    }
+
+    fn settings(last_remote: u64, last_hash: u64, ask_on_conflict: bool, local_changed_at: u64) -> SyncSettings {
+        SyncSettings { last_remote, last_hash, ask_on_conflict, local_changed_at, ..Default::default() }
+    }
+
+    #[test]
+    fn one_sided_changes() {
+        let synced = profile(100, "country:RU").hash();
+        let edited = profile(0, "country:CN").hash();
+        let state = settings(100, synced, true, 0);
+        assert!(matches!(decide(&state, synced, None), Decision::Push));
+        assert!(matches!(decide(&state, synced, Some(profile(100, "country:RU"))), Decision::UpToDate));
+        assert!(matches!(decide(&state, edited, Some(profile(100, "country:RU"))), Decision::Push));
+        assert!(matches!(decide(&state, synced, Some(profile(200, "country:CN"))), Decision::Pull(_)));
+    }
+
+    #[test]
+    fn both_sides_changed() {
+        let synced = profile(100, "country:RU").hash();
+        let local = profile(0, "country:CN").hash();
+        // Той самий вміст з іншого пристрою
+        let same = settings(100, synced, true, 150);
+        assert!(matches!(decide(&same, local, Some(profile(200, "country:CN"))), Decision::Pull(_)));
+
+        let remote = || Some(profile(200, "id:1"));
+        assert!(matches!(decide(&settings(100, synced, true, 150), local, remote()), Decision::Conflict(_)));
+        // Last-write-wins: пізніша зміна перемагає
+        assert!(matches!(decide(&settings(100, synced, false, 150), local, remote()), Decision::Pull(_)));
+        assert!(matches!(decide(&settings(100, synced, false, 250), local, remote()), Decision::Push));
+    }
+
+    #[test]
+    fn hash_ignores_stamp_and_device() {
+        let mut other = profile(999, "country:RU");
+        other.device = "desktop".into();
+        assert_eq!(other.hash(), profile(1, "country:RU").hash());
+        assert_ne!(other.hash(), profile(1, "country:CN").hash());
+    }
+
+    #[test]
+    fn secret_is_not_serialized() {
+        let settings = SyncSettings { secret: "hunter2".into(), ..Default::default() };
+        let saved = ron::to_string(&settings).unwrap();
+        assert!(!saved.contains("hunter2"));
+        // Зі старого конфігу секрет ще читається, щоб перенести його в сховище ОС
+        let legacy: SyncSettings = ron::from_str("(secret: \"hunter2\")").unwrap();
+        assert_eq!(legacy.secret, "hunter2");
+    }
+
+    #[test]
+    fn signing_primitives() {
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        // RFC 4231, тест 2
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", "what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
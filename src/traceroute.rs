@@ -0,0 +1,153 @@
+use eframe::egui;
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+// --- ТРАСУВАННЯ МАРШРУТУ ДО СЕРВЕРА ---
+//
+// Сирі сокети потребують прав адміністратора, тому запускаємо системну
+// утиліту (tracert на Windows, traceroute деінде) і розбираємо її вивід
+// рядок за рядком, щоб вузли з'являлися по мірі відповіді.
+
+pub struct Hop {
+    pub number: u32,
+    // Порожній - жоден пакет не повернувся
+    pub host: String,
+    // Час кожної проби в мс; None - відповіді не було
+    pub probes: Vec<Option<f32>>,
+}
+
+impl Hop {
+    pub fn loss_percent(&self) -> f32 {
+        if self.probes.is_empty() {
+            return 0.0;
+        }
+        let lost = self.probes.iter().filter(|probe| probe.is_none()).count();
+        lost as f32 * 100.0 / self.probes.len() as f32
+    }
+
+    pub fn average(&self) -> Option<f32> {
+        let answered: Vec<f32> = self.probes.iter().flatten().copied().collect();
+        if answered.is_empty() {
+            return None;
+        }
+        Some(answered.iter().sum::<f32>() / answered.len() as f32)
+    }
+}
+
+enum TraceEvent {
+    Hop(Hop),
+    Done(Result<(), String>),
+}
+
+pub struct Trace {
+    pub server_id: String,
+    pub target: String,
+    pub hops: Vec<Hop>,
+    pub running: bool,
+    pub error: Option<String>,
+    rx: Receiver<TraceEvent>,
+}
+
+impl Trace {
+    pub fn start(server_id: &str, ip: &str, ctx: egui::Context) -> Self {
+        let (tx, rx) = channel();
+        let target = ip.to_owned();
+        thread::spawn(move || {
+            let result = run(&target, &tx, &ctx);
+            let _ = tx.send(TraceEvent::Done(result));
+            ctx.request_repaint();
+        });
+        println!("[LOG] Traceroute to {} started", ip);
+        Self { server_id: server_id.to_owned(), target: ip.to_owned(), hops: Vec::new(), running: true, error: None, rx }
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                TraceEvent::Hop(hop) => self.hops.push(hop),
+                TraceEvent::Done(result) => {
+                    self.running = false;
+                    self.error = result.err();
+                }
+            }
+        }
+    }
+
+    /// Перший вузол з втратами, після якого втрати вже не зникають:
+    /// поодинокі "*" на проміжних маршрутизаторах - норма, вони просто не відповідають на ICMP.
+    pub fn loss_starts_at(&self) -> Option<u32> {
+        let last_clean = self.hops.iter().rposition(|hop| hop.loss_percent() == 0.0);
+        let first_lossy = match last_clean {
+            Some(index) => index + 1,
+            None => 0,
+        };
+        self.hops.get(first_lossy).filter(|_| !self.running).map(|hop| hop.number)
+    }
+}
+
+#[cfg(windows)]
+fn command(ip: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let mut command = Command::new("tracert");
+    command.args(["-d", "-w", "1000", ip]).creation_flags(CREATE_NO_WINDOW);
+    command
+}
+
+#[cfg(not(windows))]
+fn command(ip: &str) -> Command {
+    let mut command = Command::new("traceroute");
+    command.args(["-n", "-q", "3", "-w", "1", ip]);
+    command
+}
+
+fn run(ip: &str, tx: &Sender<TraceEvent>, ctx: &egui::Context) -> Result<(), String> {
+    // Лише IP: значення йде аргументом у системну команду
+    ip.parse::<IpAddr>().map_err(|_| format!("not an IP address: {}", ip))?;
+    let mut child = command(ip).stdout(Stdio::piped()).stderr(Stdio::null()).spawn().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => "traceroute is not installed".to_owned(),
+        _ => e.to_string(),
+    })?;
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(hop) = parse_line(&line) {
+                let _ = tx.send(TraceEvent::Hop(hop));
+                ctx.request_repaint();
+            }
+        }
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if status.success() { Ok(()) } else { Err(format!("traceroute exited with {}", status)) }
+}
+
+// Windows: "  2    12 ms    <1 ms     *     10.0.0.1"
+// Unix:    " 2  10.0.0.1  12.345 ms  0.8 ms *"
+fn parse_line(line: &str) -> Option<Hop> {
+    let mut tokens = line.split_whitespace().peekable();
+    let number = tokens.next()?.parse::<u32>().ok()?;
+    let mut host = String::new();
+    let mut probes = Vec::new();
+    while let Some(token) = tokens.next() {
+        if token == "*" {
+            probes.push(None);
+            continue;
+        }
+        let value = token.trim_start_matches('<').parse::<f32>();
+        if let (Ok(value), Some(&"ms")) = (value, tokens.peek()) {
+            probes.push(Some(value));
+            tokens.next();
+            continue;
+        }
+        let address = token.trim_matches(|c| c == '[' || c == ']' || c == '(' || c == ')');
+        if host.is_empty() && address.parse::<IpAddr>().is_ok() {
+            host = address.to_owned();
+        }
+    }
+    if probes.is_empty() {
+        return None;
+    }
+    Some(Hop { number, host, probes })
+}
@@ -0,0 +1,113 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::bidi::Direction;
+
+// --- ПЕРЕКЛАДИ СПІЛЬНОТИ (lang/*.json) ---
+//
+// Файли з теки lang/ поруч із програмою читаються один раз при запуску.
+// Ім'я файлу без розширення - код мови. Формат:
+//   { "name": "Deutsch", "rtl": false, "strings": { "settings": "Einstellungen", ... } }
+// Ключі - ті самі, що в SquadApp::tr; відсутні беруться з англійської.
+
+pub const DIR: &str = "lang";
+
+#[derive(Deserialize)]
+struct File {
+    name: String,
+    #[serde(default)]
+    rtl: bool,
+    strings: HashMap<String, String>,
+}
+
+pub struct Translation {
+    pub code: String,
+    pub name: String,
+    pub direction: Direction,
+    // Рядки живуть до кінця роботи програми, як і вбудовані переклади
+    strings: HashMap<String, &'static str>,
+}
+
+impl Translation {
+    pub fn text(&self, key: &str) -> Option<&'static str> {
+        self.strings.get(key).copied()
+    }
+}
+
+#[derive(Default)]
+pub struct Translations {
+    pub list: Vec<Translation>,
+}
+
+impl Translations {
+    /// Тека поруч із виконуваним файлом, а також у робочій теці (для cargo run).
+    pub fn load() -> Self {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        if let Some(dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.join(DIR))) {
+            dirs.push(dir);
+        }
+        let local = PathBuf::from(DIR);
+        if !dirs.iter().any(|dir| same_dir(dir, &local)) {
+            dirs.push(local);
+        }
+
+        let mut list: Vec<Translation> = Vec::new();
+        for dir in dirs {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+            paths.sort();
+            for path in paths {
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let code = match path.file_stem().and_then(|s| s.to_str()) {
+                    Some(code) => code.to_owned(),
+                    None => continue,
+                };
+                if list.iter().any(|t| t.code == code) {
+                    continue;
+                }
+                match fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|raw| parse(&code, &raw)) {
+                    Ok(translation) => {
+                        println!("[LOG] Loaded translation {} ({} strings)", path.display(), translation.strings.len());
+                        list.push(translation);
+                    }
+                    Err(e) => println!("[ERR] Failed to load translation {}: {}", path.display(), e),
+                }
+            }
+        }
+        Self { list }
+    }
+
+    pub fn get(&self, code: &str) -> Option<&Translation> {
+        self.list.iter().find(|t| t.code == code)
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn parse(code: &str, raw: &str) -> Result<Translation, String> {
+    let file: File = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+    let strings = file
+        .strings
+        .into_iter()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(key, text)| (key, &*Box::leak(text.into_boxed_str())))
+        .collect();
+    Ok(Translation {
+        code: code.to_owned(),
+        name: if file.name.trim().is_empty() { code.to_owned() } else { file.name },
+        direction: if file.rtl { Direction::Rtl } else { Direction::Ltr },
+        strings,
+    })
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// --- ДОСТУПНІСТЬ ЗАКРІПЛЕНИХ СЕРВЕРІВ (SLA) ---
+//
+// Кожна перевірка присутності (presence.rs) рахується тут у погодинні кошики,
+// щоб конфіг не ріс на тисячі записів. Рахуються лише години, коли
+// програма була запущена: пропуски - це "немає даних", а не простій.
+
+const HOUR_SECS: u64 = 60 * 60;
+const KEEP_FOR_SECS: u64 = 30 * 24 * HOUR_SECS;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(default)]
+struct Bucket {
+    // Початок години, секунди від UNIX epoch
+    hour: u64,
+    checks: u32,
+    online: u32,
+}
+
+/// Відсоток часу онлайн і кількість перевірок, з яких він порахований.
+pub struct Uptime {
+    pub percent: f32,
+    pub checks: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct UptimeLog {
+    servers: HashMap<String, Vec<Bucket>>,
+}
+
+impl UptimeLog {
+    pub fn record(&mut self, id: &str, now: u64, online: bool) {
+        let hour = now - now % HOUR_SECS;
+        let buckets = self.servers.entry(id.to_owned()).or_default();
+        if buckets.last().map(|b| b.hour) != Some(hour) {
+            buckets.push(Bucket { hour, checks: 0, online: 0 });
+        }
+        if let Some(last) = buckets.last_mut() {
+            last.checks += 1;
+            last.online += online as u32;
+        }
+        // Відкріплені сервери з часом випадають самі
+        for buckets in self.servers.values_mut() {
+            buckets.retain(|b| now.saturating_sub(b.hour) <= KEEP_FOR_SECS);
+        }
+        self.servers.retain(|_, buckets| !buckets.is_empty());
+    }
+
+    pub fn over_days(&self, id: &str, now: u64, days: u64) -> Option<Uptime> {
+        let since = now.saturating_sub(days * 24 * HOUR_SECS);
+        let (checks, online) = self
+            .servers
+            .get(id)?
+            .iter()
+            .filter(|b| b.hour + HOUR_SECS > since)
+            .fold((0, 0), |(checks, online), b| (checks + b.checks, online + b.online));
+        if checks == 0 {
+            return None;
+        }
+        Some(Uptime { percent: online as f32 * 100.0 / checks as f32, checks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * HOUR_SECS;
+    const NOW: u64 = 20_000 * DAY;
+
+    #[test]
+    fn percent_over_days() {
+        let mut log = UptimeLog::default();
+        assert!(log.over_days("1", NOW, 7).is_none());
+        // 10 днів тому офлайн, останні дві години 3 з 4 перевірок онлайн
+        log.record("1", NOW - 10 * DAY, false);
+        log.record("1", NOW - HOUR_SECS, true);
+        log.record("1", NOW - HOUR_SECS + 60, false);
+        log.record("1", NOW, true);
+        log.record("1", NOW + 60, true);
+        let week = log.over_days("1", NOW + 60, 7).unwrap();
+        assert_eq!((week.percent, week.checks), (75.0, 4));
+        let month = log.over_days("1", NOW + 60, 30).unwrap();
+        assert_eq!((month.percent, month.checks), (60.0, 5));
+        // Одна погодинна корзина на годину
+        assert_eq!(log.servers["1"].len(), 3);
+    }
+
+    #[test]
+    fn old_buckets_and_servers_are_dropped() {
+        let mut log = UptimeLog::default();
+        log.record("unpinned", NOW, true);
+        log.record("1", NOW + KEEP_FOR_SECS + HOUR_SECS, true);
+        assert!(!log.servers.contains_key("unpinned"));
+        assert!(log.over_days("1", NOW + KEEP_FOR_SECS + HOUR_SECS, 30).is_some());
+    }
+}
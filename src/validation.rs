@@ -0,0 +1,37 @@
+use crate::rules::{RuleKind, RuleSet};
+use crate::filters::FilterSet;
+
+// --- ПЕРЕВІРКА НАЛАШТУВАНЬ ---
+//
+// Суперечливі фільтри не є помилкою, але тихо дають порожній або неочікуваний
+// результат - тому показуємо попередження з поясненням.
+
+pub enum Issue {
+    // Мін. гравців більше за макс. - жоден сервер не пройде
+    MinAboveMax,
+    // Країна в чорному списку, але є правило-виняток: виняток перемагає
+    CountryBannedAndAllowed(String),
+    // Пошук за назвою збігається з правилом блокування назви
+    NameFilterBlocked(String),
+}
+
+pub fn check(filters: &FilterSet, rules: &RuleSet) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    if filters.min_players() > filters.max_players() {
+        issues.push(Issue::MinAboveMax);
+    }
+
+    let name_filter = filters.name().trim().to_lowercase();
+    for rule in &rules.rules {
+        match &rule.kind {
+            RuleKind::Country(code) if rule.exception && filters.is_banned(code) => {
+                issues.push(Issue::CountryBannedAndAllowed(code.clone()));
+            }
+            RuleKind::NameContains(word) if !rule.exception && !name_filter.is_empty() && name_filter.contains(word.as_str()) => {
+                issues.push(Issue::NameFilterBlocked(word.clone()));
+            }
+            _ => {}
+        }
+    }
+    issues
+}
@@ -0,0 +1,98 @@
+// --- ВЕРСІЇ ГРИ ---
+//
+// Сервери Squad звітують версію як "v8.0.2.403285.879" - порівнюємо числа по черзі.
+
+use std::collections::HashMap;
+
+fn components(version: &str) -> Vec<u64> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+pub fn is_older(version: &str, than: &str) -> bool {
+    let (a, b) = (components(version), components(than));
+    !a.is_empty() && !b.is_empty() && a < b
+}
+
+// --- НАЙНОВІША ВЕРСІЯ ЗІ СКАНУ ---
+//
+// Не просто максимум: один сервер із вигаданою "v99" зробив би застарілими
+// всі інші. Беремо найновішу версію, яку (разом із новішими) звітують хоча б
+// MIN_SERVERS серверів і MIN_SHARE усіх. У день патча оновлені сервери
+// швидко набирають цю частку.
+//
+// Steam тут не допомагає: ISteamApps віддає лише номер білду Steam, а він
+// не зіставляється з рядком версії, який звітують сервери.
+
+const MIN_SERVERS: usize = 3;
+const MIN_SHARE: f32 = 0.05;
+
+#[derive(Default)]
+pub struct VersionCensus {
+    // id сервера -> остання звітована версія
+    servers: HashMap<String, String>,
+    // Порахована в update(), щоб не сортувати на кожен рядок списку
+    latest: String,
+}
+
+impl VersionCensus {
+    pub fn record(&mut self, id: &str, version: &str) {
+        if !components(version).is_empty() {
+            self.servers.insert(id.to_owned(), version.to_owned());
+        }
+    }
+
+    /// Перераховує найновішу версію після пачки record().
+    pub fn update(&mut self) {
+        self.latest = consensus(self.servers.values().map(String::as_str)).unwrap_or_default().to_owned();
+    }
+
+    pub fn latest(&self) -> &str {
+        &self.latest
+    }
+}
+
+pub fn consensus<'a>(versions: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let mut versions: Vec<(Vec<u64>, &str)> =
+        versions.map(|v| (components(v), v)).filter(|(parts, _)| !parts.is_empty()).collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+    let total = versions.len();
+    let needed = MIN_SERVERS.max((total as f32 * MIN_SHARE).ceil() as usize).min(total);
+    // Серед однакових версій беремо останню, щоб рахувати всю групу
+    versions
+        .iter()
+        .enumerate()
+        .find(|&(index, (parts, _))| index + 1 >= needed && versions.get(index + 1).is_none_or(|next| &next.0 != parts))
+        .map(|(_, (_, version))| *version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_bogus_server_does_not_raise_the_bar() {
+        let mut versions = vec!["v8.0.2.403285.879"; 40];
+        versions.push("v99.0");
+        assert_eq!(consensus(versions.into_iter()), Some("v8.0.2.403285.879"));
+    }
+
+    #[test]
+    fn an_update_counts_once_enough_servers_have_it() {
+        let mut versions = vec!["v8.0.2"; 40];
+        versions.extend(["v8.1.0"; 2]);
+        assert_eq!(consensus(versions.iter().copied()), Some("v8.0.2"));
+        versions.extend(["v8.1.0"; 3]);
+        assert_eq!(consensus(versions.iter().copied()), Some("v8.1.0"));
+    }
+
+    #[test]
+    fn small_scans_use_what_they_have() {
+        assert_eq!(consensus(["v8.0.2", "v7.5.1"].into_iter()), Some("v7.5.1"));
+        assert_eq!(consensus(["v8.0.2"].into_iter()), Some("v8.0.2"));
+        assert_eq!(consensus(["", "unknown"].into_iter()), None);
+    }
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+// --- ВЕТО КАРТ ДЛЯ СКРИМІВ ---
+//
+// Команди по черзі банять і вибирають карти з пулу; остання карта, що
+// лишилась, - вирішальна. Результат копіюється текстом у Discord.
+//
+// Бази шарів у програмі немає, тож пул за замовчуванням - вбудований список
+// карт Squad; його можна змінити в самому вікні.
+
+pub const DEFAULT_POOL: [&str; 16] = [
+    "Al Basrah",
+    "Anvil",
+    "Black Coast",
+    "Fallujah",
+    "Gorodok",
+    "Harju",
+    "Kokan",
+    "Lashkar Valley",
+    "Logar Valley",
+    "Manicouagan",
+    "Mutaha",
+    "Narva",
+    "Sanxian Islands",
+    "Skorpo",
+    "Sumari Bala",
+    "Yehorivka",
+];
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum Format {
+    // Усі банять по черзі до однієї карти
+    #[default]
+    BestOf1,
+    // Бан, бан, вибір, вибір, далі бани до вирішальної
+    BestOf3,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    Ban,
+    Pick,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct VetoSettings {
+    pub team_a: String,
+    pub team_b: String,
+    pub format: Format,
+    // Одна карта на рядок
+    pub pool: String,
+}
+
+impl Default for VetoSettings {
+    fn default() -> Self {
+        Self {
+            team_a: "Team A".to_owned(),
+            team_b: "Team B".to_owned(),
+            format: Format::default(),
+            pool: DEFAULT_POOL.join("\n"),
+        }
+    }
+}
+
+pub struct Step {
+    // 0 - команда A, 1 - команда B
+    pub team: usize,
+    pub action: Action,
+    pub map: String,
+}
+
+pub struct Veto {
+    pub teams: [String; 2],
+    pub format: Format,
+    pub pool: Vec<String>,
+    pub steps: Vec<Step>,
+}
+
+impl Veto {
+    pub fn new(settings: &VetoSettings) -> Self {
+        let mut pool: Vec<String> = Vec::new();
+        for map in settings.pool.lines() {
+            let map = map.trim();
+            if !map.is_empty() && !pool.iter().any(|m| m.eq_ignore_ascii_case(map)) {
+                pool.push(map.to_owned());
+            }
+        }
+        Self { teams: [settings.team_a.clone(), settings.team_b.clone()], format: settings.format, pool, steps: Vec::new() }
+    }
+
+    pub fn remaining(&self) -> Vec<&String> {
+        self.pool.iter().filter(|map| !self.steps.iter().any(|step| &step.map == *map)).collect()
+    }
+
+    /// Хто і що робить наступним; None - лишилась вирішальна карта.
+    pub fn next(&self) -> Option<(usize, Action)> {
+        if self.remaining().len() <= 1 {
+            return None;
+        }
+        let index = self.steps.len();
+        let action = match (self.format, index) {
+            (Format::BestOf3, 2 | 3) => Action::Pick,
+            _ => Action::Ban,
+        };
+        Some((index % 2, action))
+    }
+
+    pub fn choose(&mut self, map: &str) {
+        if let Some((team, action)) = self.next() {
+            if self.remaining().iter().any(|m| *m == map) {
+                self.steps.push(Step { team, action, map: map.to_owned() });
+            }
+        }
+    }
+
+    pub fn undo(&mut self) {
+        self.steps.pop();
+    }
+
+    pub fn decider(&self) -> Option<&String> {
+        let remaining = self.remaining();
+        if remaining.len() == 1 { remaining.first().copied() } else { None }
+    }
+
+    /// Карти, на яких граємо: вибрані по черзі, потім вирішальна.
+    pub fn maps(&self) -> Vec<&String> {
+        let picks = self.steps.iter().filter(|step| step.action == Action::Pick).map(|step| &step.map);
+        picks.chain(self.decider()).collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![format!("{} vs {}", self.teams[0], self.teams[1])];
+        for (index, step) in self.steps.iter().enumerate() {
+            let verb = match step.action {
+                Action::Ban => "bans",
+                Action::Pick => "picks",
+            };
+            lines.push(format!("{}. {} {} {}", index + 1, self.teams[step.team], verb, step.map));
+        }
+        if let Some(decider) = self.decider() {
+            lines.push(format!("Decider: {}", decider));
+        }
+        let maps: Vec<&str> = self.maps().iter().map(|map| map.as_str()).collect();
+        if !maps.is_empty() {
+            lines.push(format!("Maps: {}", maps.join(", ")));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn veto(format: Format, pool: &str) -> Veto {
+        Veto::new(&VetoSettings { team_a: "UA".into(), team_b: "PL".into(), format, pool: pool.into() })
+    }
+
+    #[test]
+    fn best_of_three_order_and_text() {
+        let mut veto = veto(Format::BestOf3, "Narva\nGorodok\n narva \n\nKokan\nMutaha\nAnvil");
+        assert_eq!(veto.pool.len(), 5);
+        let mut turns = Vec::new();
+        for map in ["Narva", "Gorodok", "Kokan", "Mutaha"] {
+            turns.push(veto.next().unwrap());
+            veto.choose(map);
+        }
+        assert_eq!(turns, vec![(0, Action::Ban), (1, Action::Ban), (0, Action::Pick), (1, Action::Pick)]);
+        assert_eq!(veto.next(), None);
+        assert_eq!(veto.maps(), vec!["Kokan", "Mutaha", "Anvil"]);
+        assert_eq!(
+            veto.to_text(),
+            "UA vs PL\n1. UA bans Narva\n2. PL bans Gorodok\n3. UA picks Kokan\n4. PL picks Mutaha\nDecider: Anvil\nMaps: Kokan, Mutaha, Anvil"
+        );
+    }
+
+    #[test]
+    fn best_of_one_bans_down_to_decider() {
+        let mut veto = veto(Format::BestOf1, "A\nB\nC");
+        veto.choose("B");
+        // Вже вибрана чи невідома карта ігнорується
+        veto.choose("B");
+        veto.choose("Z");
+        assert_eq!(veto.next(), Some((1, Action::Ban)));
+        veto.choose("A");
+        assert_eq!(veto.decider().map(String::as_str), Some("C"));
+        veto.choose("C");
+        assert_eq!(veto.steps.len(), 2);
+        veto.undo();
+        assert_eq!(veto.decider(), None);
+        assert_eq!(veto.remaining(), vec!["A", "C"]);
+    }
+}
@@ -0,0 +1,88 @@
+use eframe::egui;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+// --- СТЕЖЕННЯ ЗА ФАЙЛАМИ КОНФІГУРАЦІЇ ---
+
+pub struct FileWatcher {
+    watcher: RecommendedWatcher,
+    rx: Receiver<PathBuf>,
+    targets: Vec<PathBuf>,
+    dirs: HashSet<PathBuf>,
+}
+
+pub fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map(|dir| dir.join(path)).unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+impl FileWatcher {
+    pub fn new(ctx: egui::Context) -> Option<Self> {
+        let (tx, rx) = channel();
+        let handler = move |res: notify::Result<Event>| {
+            if let Ok(event) = res {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                    ctx.request_repaint();
+                }
+            }
+        };
+
+        match notify::recommended_watcher(handler) {
+            Ok(watcher) => Some(Self {
+                watcher,
+                rx,
+                targets: Vec::new(),
+                dirs: HashSet::new(),
+            }),
+            Err(e) => {
+                println!("[ERR] Failed to start file watcher: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Встановлює список файлів для стеження. Слідкуємо за батьківськими папками,
+    /// бо редактори часто зберігають файл через перейменування тимчасового.
+    pub fn set_targets(&mut self, paths: &[PathBuf]) {
+        let targets: Vec<PathBuf> = paths.iter().map(|p| absolute(p)).collect();
+        if targets == self.targets {
+            return;
+        }
+
+        let dirs: HashSet<PathBuf> = targets
+            .iter()
+            .filter_map(|p| p.parent().map(Path::to_path_buf))
+            .collect();
+
+        for dir in self.dirs.difference(&dirs) {
+            let _ = self.watcher.unwatch(dir);
+        }
+        for dir in dirs.difference(&self.dirs) {
+            if let Err(e) = self.watcher.watch(dir, RecursiveMode::NonRecursive) {
+                println!("[ERR] Failed to watch {}: {}", dir.display(), e);
+            }
+        }
+
+        self.targets = targets;
+        self.dirs = dirs;
+    }
+
+    /// Повертає файли зі списку стеження, які змінилися з минулого виклику.
+    pub fn changed(&self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        while let Ok(path) = self.rx.try_recv() {
+            if self.targets.contains(&path) && !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        changed
+    }
+}
@@ -0,0 +1,34 @@
+// --- КАРТА СВІТУ (плитки країн) ---
+//
+// Замість справжніх контурів - сітка плиток, розставлених приблизно за
+// географією: Європа не злипається в одну точку, а код країни видно завжди.
+
+pub const COLUMNS: u8 = 24;
+pub const ROWS: u8 = 12;
+
+/// Код країни, стовпчик, рядок.
+pub const TILES: &[(&str, u8, u8)] = &[
+    // Північна Америка
+    ("CA", 2, 1), ("US", 2, 2), ("MX", 2, 3),
+    // Південна Америка
+    ("CO", 3, 5), ("PE", 3, 6), ("BR", 4, 6), ("CL", 3, 7), ("AR", 4, 7),
+    // Європа
+    ("NO", 10, 0), ("SE", 11, 0), ("FI", 12, 0), ("EE", 13, 0),
+    ("IE", 7, 1), ("GB", 8, 1), ("DK", 10, 1), ("LT", 12, 1), ("LV", 13, 1), ("RU", 15, 1),
+    ("NL", 9, 2), ("DE", 10, 2), ("PL", 11, 2), ("BY", 13, 2),
+    ("BE", 8, 3), ("CZ", 10, 3), ("SK", 11, 3), ("UA", 13, 3),
+    ("FR", 8, 4), ("CH", 9, 4), ("AT", 10, 4), ("HU", 11, 4), ("RO", 12, 4), ("MD", 13, 4), ("GE", 15, 4),
+    ("PT", 6, 5), ("ES", 7, 5), ("IT", 9, 5), ("HR", 10, 5), ("RS", 11, 5), ("BG", 12, 5), ("TR", 14, 5),
+    ("GR", 12, 6),
+    // Близький Схід і Африка
+    ("IL", 14, 6), ("EG", 12, 7), ("SA", 15, 7), ("AE", 16, 7), ("ZA", 12, 10),
+    // Азія
+    ("KZ", 16, 3), ("JP", 22, 3), ("CN", 19, 4), ("KR", 21, 4), ("HK", 19, 5), ("TW", 21, 5),
+    ("IN", 17, 6), ("VN", 19, 6), ("PH", 21, 6), ("TH", 18, 7), ("MY", 18, 8), ("SG", 19, 8), ("ID", 20, 9),
+    // Океанія
+    ("AU", 21, 10), ("NZ", 23, 11),
+];
+
+pub fn has_tile(code: &str) -> bool {
+    TILES.iter().any(|(c, _, _)| *c == code)
+}